@@ -39,6 +39,12 @@ pub enum MatchLintBehaviour {
     Never,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ComparisonConstantSide {
+    Left,
+    Right,
+}
+
 #[derive(Debug)]
 pub struct MacroMatcher {
     pub name: String,
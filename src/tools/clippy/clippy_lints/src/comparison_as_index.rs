@@ -0,0 +1,88 @@
+use clippy_utils::comparisons::comparison_cast_to_int;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons that are cast to an integer and used as an index, like
+    /// `arr[(a < b) as usize]`.
+    ///
+    /// By default, comparisons that are part of arithmetic in the index, like
+    /// `arr[i + (a < b) as usize]`, are not linted. Set `comparison-as-index-in-arithmetic` to
+    /// lint them too.
+    ///
+    /// ### Why is this bad?
+    /// Selecting an element with a boolean is a non-obvious way to write a branch, and the reader
+    /// has to remember that `true` is `1`. An `if` states the intent clearly.
+    ///
+    /// ### Known problems
+    /// The pattern is sometimes used on purpose to avoid a branch, which is why this lint is in
+    /// the restriction group.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// let arr = ["no", "yes"];
+    /// let answer = arr[(a < b) as usize];
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// let answer = if a < b { "yes" } else { "no" };
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub COMPARISON_AS_INDEX,
+    restriction,
+    "using a comparison cast to an integer as an index"
+}
+
+impl_lint_pass!(ComparisonAsIndex => [COMPARISON_AS_INDEX]);
+
+pub struct ComparisonAsIndex {
+    pub comparison_as_index_in_arithmetic: bool,
+}
+
+impl<'tcx> LateLintPass<'tcx> for ComparisonAsIndex {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::Index(_, index, _) = expr.kind
+            && !expr.span.from_expansion()
+        {
+            if comparison_cast_to_int(cx, index).is_some() {
+                lint(cx, index);
+            } else if self.comparison_as_index_in_arithmetic {
+                check_arithmetic(cx, index);
+            }
+        }
+    }
+}
+
+/// Lints the comparison casts among the operands of the arithmetic expression `expr`.
+fn check_arithmetic<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    if let ExprKind::Binary(op, lhs, rhs) = expr.kind
+        && matches!(
+            op.node,
+            BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul | BinOpKind::Div | BinOpKind::Rem
+        )
+    {
+        for operand in [lhs, rhs] {
+            if comparison_cast_to_int(cx, operand).is_some() {
+                lint(cx, operand);
+            } else {
+                check_arithmetic(cx, operand);
+            }
+        }
+    }
+}
+
+fn lint(cx: &LateContext<'_>, cast: &Expr<'_>) {
+    span_lint_and_help(
+        cx,
+        COMPARISON_AS_INDEX,
+        cast.span,
+        "using a comparison cast to an integer as an index",
+        None,
+        "use an `if` or `bool::then` to make the selection explicit",
+    );
+}
@@ -103,6 +103,7 @@
     crate::collapsible_if::COLLAPSIBLE_ELSE_IF_INFO,
     crate::collapsible_if::COLLAPSIBLE_IF_INFO,
     crate::collection_is_never_read::COLLECTION_IS_NEVER_READ_INFO,
+    crate::comparison_as_index::COMPARISON_AS_INDEX_INFO,
     crate::comparison_chain::COMPARISON_CHAIN_INFO,
     crate::copies::BRANCHES_SHARING_CODE_INFO,
     crate::copies::IFS_SAME_COND_INFO,
@@ -183,6 +184,7 @@
     crate::format_push_string::FORMAT_PUSH_STRING_INFO,
     crate::formatting::POSSIBLE_MISSING_COMMA_INFO,
     crate::formatting::SUSPICIOUS_ASSIGNMENT_FORMATTING_INFO,
+    crate::formatting::SUSPICIOUS_COMPARISON_FORMATTING_INFO,
     crate::formatting::SUSPICIOUS_ELSE_FORMATTING_INFO,
     crate::formatting::SUSPICIOUS_UNARY_OP_FORMATTING_INFO,
     crate::four_forward_slashes::FOUR_FORWARD_SLASHES_INFO,
@@ -209,6 +211,7 @@
     crate::implicit_saturating_add::IMPLICIT_SATURATING_ADD_INFO,
     crate::implicit_saturating_sub::IMPLICIT_SATURATING_SUB_INFO,
     crate::implied_bounds_in_impls::IMPLIED_BOUNDS_IN_IMPLS_INFO,
+    crate::inconsistent_ord_comparisons::INCONSISTENT_ORD_COMPARISONS_INFO,
     crate::inconsistent_struct_constructor::INCONSISTENT_STRUCT_CONSTRUCTOR_INFO,
     crate::index_refutable_slice::INDEX_REFUTABLE_SLICE_INFO,
     crate::indexing_slicing::INDEXING_SLICING_INFO,
@@ -266,16 +269,20 @@
     crate::loops::ITER_NEXT_LOOP_INFO,
     crate::loops::MANUAL_FIND_INFO,
     crate::loops::MANUAL_FLATTEN_INFO,
+    crate::loops::MANUAL_IS_SORTED_INFO,
     crate::loops::MANUAL_MEMCPY_INFO,
+    crate::loops::MANUAL_MIN_MAX_LOOP_INFO,
     crate::loops::MANUAL_WHILE_LET_SOME_INFO,
     crate::loops::MISSING_SPIN_LOOP_INFO,
     crate::loops::MUT_RANGE_BOUND_INFO,
     crate::loops::NEEDLESS_RANGE_LOOP_INFO,
     crate::loops::NEVER_LOOP_INFO,
+    crate::loops::OFF_BY_ONE_SLICE_WINDOW_INFO,
     crate::loops::SAME_ITEM_PUSH_INFO,
     crate::loops::SINGLE_ELEMENT_LOOP_INFO,
     crate::loops::UNUSED_ENUMERATE_INDEX_INFO,
     crate::loops::WHILE_IMMUTABLE_CONDITION_INFO,
+    crate::loops::WHILE_LEN_COMPARISON_INFO,
     crate::loops::WHILE_LET_LOOP_INFO,
     crate::loops::WHILE_LET_ON_ITERATOR_INFO,
     crate::macro_use::MACRO_USE_IMPORTS_INFO,
@@ -294,9 +301,11 @@
     crate::manual_range_patterns::MANUAL_RANGE_PATTERNS_INFO,
     crate::manual_rem_euclid::MANUAL_REM_EUCLID_INFO,
     crate::manual_retain::MANUAL_RETAIN_INFO,
+    crate::manual_signum::MANUAL_SIGNUM_INFO,
     crate::manual_slice_size_calculation::MANUAL_SLICE_SIZE_CALCULATION_INFO,
     crate::manual_string_new::MANUAL_STRING_NEW_INFO,
     crate::manual_strip::MANUAL_STRIP_INFO,
+    crate::manual_tuple_match::MANUAL_TUPLE_MATCH_INFO,
     crate::map_unit_fn::OPTION_MAP_UNIT_FN_INFO,
     crate::map_unit_fn::RESULT_MAP_UNIT_FN_INFO,
     crate::match_result_ok::MATCH_RESULT_OK_INFO,
@@ -304,6 +313,7 @@
     crate::matches::INFALLIBLE_DESTRUCTURING_MATCH_INFO,
     crate::matches::MANUAL_FILTER_INFO,
     crate::matches::MANUAL_MAP_INFO,
+    crate::matches::MANUAL_RANGE_GUARD_INFO,
     crate::matches::MANUAL_UNWRAP_OR_INFO,
     crate::matches::MATCH_AS_REF_INFO,
     crate::matches::MATCH_BOOL_INFO,
@@ -376,12 +386,14 @@
     crate::methods::ITER_SKIP_NEXT_INFO,
     crate::methods::ITER_SKIP_ZERO_INFO,
     crate::methods::ITER_WITH_DRAIN_INFO,
+    crate::methods::MANUAL_CONTAINS_INFO,
     crate::methods::MANUAL_FILTER_MAP_INFO,
     crate::methods::MANUAL_FIND_MAP_INFO,
     crate::methods::MANUAL_NEXT_BACK_INFO,
     crate::methods::MANUAL_OK_OR_INFO,
     crate::methods::MANUAL_SATURATING_ARITHMETIC_INFO,
     crate::methods::MANUAL_SPLIT_ONCE_INFO,
+    crate::methods::MANUAL_STR_FIND_INFO,
     crate::methods::MANUAL_STR_REPEAT_INFO,
     crate::methods::MANUAL_TRY_FOLD_INFO,
     crate::methods::MAP_CLONE_INFO,
@@ -440,6 +452,7 @@
     crate::methods::UNNECESSARY_LITERAL_UNWRAP_INFO,
     crate::methods::UNNECESSARY_SORT_BY_INFO,
     crate::methods::UNNECESSARY_TO_OWNED_INFO,
+    crate::methods::UNSTABLE_SORT_BY_FIELD_SUBSET_INFO,
     crate::methods::UNWRAP_OR_DEFAULT_INFO,
     crate::methods::UNWRAP_USED_INFO,
     crate::methods::USELESS_ASREF_INFO,
@@ -521,30 +534,53 @@
     crate::octal_escapes::OCTAL_ESCAPES_INFO,
     crate::only_used_in_recursion::ONLY_USED_IN_RECURSION_INFO,
     crate::operators::ABSURD_EXTREME_COMPARISONS_INFO,
+    crate::operators::ABSURD_FLOAT_SIGN_COMPARISONS_INFO,
     crate::operators::ARITHMETIC_SIDE_EFFECTS_INFO,
     crate::operators::ASSIGN_OP_PATTERN_INFO,
+    crate::operators::ASYMMETRIC_CAST_COMPARISONS_INFO,
     crate::operators::BAD_BIT_MASK_INFO,
+    crate::operators::BITWISE_JOINED_COMPARISONS_INFO,
+    crate::operators::CHECKED_UNWRAP_COMPARISONS_INFO,
     crate::operators::CMP_OWNED_INFO,
+    crate::operators::COMPARISON_CONSTANT_SIDE_INFO,
+    crate::operators::DEREF_SELF_COMPARISONS_INFO,
     crate::operators::DOUBLE_COMPARISONS_INFO,
+    crate::operators::DOUBLE_NEGATED_COMPARISON_INFO,
+    crate::operators::DURATION_CONVERSION_COMPARISONS_INFO,
     crate::operators::DURATION_SUBSEC_INFO,
+    crate::operators::ENUM_CAST_COMPARISONS_INFO,
+    crate::operators::ENUM_CAST_LITERAL_COMPARISONS_INFO,
     crate::operators::EQ_OP_INFO,
     crate::operators::ERASING_OP_INFO,
     crate::operators::FLOAT_ARITHMETIC_INFO,
     crate::operators::FLOAT_CMP_INFO,
     crate::operators::FLOAT_CMP_CONST_INFO,
+    crate::operators::FLOAT_COMPARISON_IN_COMPARATOR_INFO,
     crate::operators::FLOAT_EQUALITY_WITHOUT_ABS_INFO,
+    crate::operators::FLOAT_KEY_EQUALITY_INFO,
+    crate::operators::GUARDED_UNWRAP_COMPARISONS_INFO,
+    crate::operators::HARDCODED_ARRAY_LEN_COMPARISONS_INFO,
+    crate::operators::IDENTICAL_CALL_COMPARISONS_INFO,
     crate::operators::IDENTITY_OP_INFO,
     crate::operators::IMPOSSIBLE_COMPARISONS_INFO,
     crate::operators::INEFFECTIVE_BIT_MASK_INFO,
+    crate::operators::INEXACT_FLOAT_LITERAL_COMPARISONS_INFO,
     crate::operators::INTEGER_DIVISION_INFO,
+    crate::operators::LOOP_INVARIANT_COMPARISONS_INFO,
+    crate::operators::MANUAL_IS_POWER_OF_TWO_INFO,
     crate::operators::MISREFACTORED_ASSIGN_OP_INFO,
     crate::operators::MODULO_ARITHMETIC_INFO,
     crate::operators::MODULO_ONE_INFO,
     crate::operators::NEEDLESS_BITWISE_BOOL_INFO,
     crate::operators::OP_REF_INFO,
+    crate::operators::OPTION_ORDERING_COMPARISONS_INFO,
+    crate::operators::ORDERING_BY_SUBTRACTION_INFO,
     crate::operators::PTR_EQ_INFO,
     crate::operators::REDUNDANT_COMPARISONS_INFO,
+    crate::operators::SATURATING_WRAPPING_COMPARISONS_INFO,
     crate::operators::SELF_ASSIGNMENT_INFO,
+    crate::operators::SINGLE_BIT_EQUALITY_INFO,
+    crate::operators::TRUNCATING_CAST_COMPARISONS_INFO,
     crate::operators::VERBOSE_BIT_MASK_INFO,
     crate::option_env_unwrap::OPTION_ENV_UNWRAP_INFO,
     crate::option_if_let_else::OPTION_IF_LET_ELSE_INFO,
@@ -571,6 +607,7 @@
     crate::question_mark::QUESTION_MARK_INFO,
     crate::question_mark_used::QUESTION_MARK_USED_INFO,
     crate::ranges::MANUAL_RANGE_CONTAINS_INFO,
+    crate::ranges::MANUAL_RANGE_MATCHES_INFO,
     crate::ranges::RANGE_MINUS_ONE_INFO,
     crate::ranges::RANGE_PLUS_ONE_INFO,
     crate::ranges::REVERSED_EMPTY_RANGES_INFO,
@@ -669,6 +706,7 @@
     crate::types::REDUNDANT_ALLOCATION_INFO,
     crate::types::TYPE_COMPLEXITY_INFO,
     crate::types::VEC_BOX_INFO,
+    crate::unchecked_clamp_bounds::UNCHECKED_CLAMP_BOUNDS_INFO,
     crate::undocumented_unsafe_blocks::UNDOCUMENTED_UNSAFE_BLOCKS_INFO,
     crate::undocumented_unsafe_blocks::UNNECESSARY_SAFETY_COMMENT_INFO,
     crate::unicode::INVISIBLE_CHARACTERS_INFO,
@@ -1,7 +1,8 @@
-use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_note};
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_note, span_lint_and_sugg};
 use clippy_utils::is_span_if;
 use clippy_utils::source::snippet_opt;
 use rustc_ast::ast::{BinOpKind, Block, Expr, ExprKind, StmtKind, UnOp};
+use rustc_errors::Applicability;
 use rustc_lint::{EarlyContext, EarlyLintPass, LintContext};
 use rustc_middle::lint::in_external_macro;
 use rustc_session::{declare_lint_pass, declare_tool_lint};
@@ -113,11 +114,38 @@
     "possible missing comma in array"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons whose operator is directly followed by a negated operand, like
+    /// `a >-b` or `a<=-b`.
+    ///
+    /// ### Why is this bad?
+    /// `>-` looks like an arrow or a single operator, so it is easy to misread what is compared.
+    /// `a <-b` is already rejected by the compiler for this reason.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// if a >-b {}
+    /// ```
+    ///
+    /// Use instead:
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// if a > -b {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub SUSPICIOUS_COMPARISON_FORMATTING,
+    style,
+    "comparison operators directly followed by a unary `-`, like `a >-b`"
+}
+
 declare_lint_pass!(Formatting => [
     SUSPICIOUS_ASSIGNMENT_FORMATTING,
     SUSPICIOUS_UNARY_OP_FORMATTING,
     SUSPICIOUS_ELSE_FORMATTING,
-    POSSIBLE_MISSING_COMMA
+    POSSIBLE_MISSING_COMMA,
+    SUSPICIOUS_COMPARISON_FORMATTING,
 ]);
 
 impl EarlyLintPass for Formatting {
@@ -132,6 +160,7 @@ fn check_block(&mut self, cx: &EarlyContext<'_>, block: &Block) {
     fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &Expr) {
         check_assign(cx, expr);
         check_unop(cx, expr);
+        check_comparison(cx, expr);
         check_else(cx, expr);
         check_array(cx, expr);
     }
@@ -197,6 +226,33 @@ fn check_unop(cx: &EarlyContext<'_>, expr: &Expr) {
     }
 }
 
+/// Implementation of the `SUSPICIOUS_COMPARISON_FORMATTING` lint.
+fn check_comparison(cx: &EarlyContext<'_>, expr: &Expr) {
+    if let ExprKind::Binary(ref binop, ref lhs, ref rhs) = expr.kind
+        // `<-` is a separate token, which the parser already rejects
+        && matches!(binop.node, BinOpKind::Gt | BinOpKind::Ge | BinOpKind::Le)
+        && !expr.span.from_expansion() && !lhs.span.from_expansion() && !rhs.span.from_expansion()
+        && let ExprKind::Unary(UnOp::Neg, ref operand) = rhs.kind
+        // no space between the comparison and the `-`
+        && binop.span.hi() == rhs.span.lo()
+        // no space after the `-` either, otherwise it's `SUSPICIOUS_UNARY_OP_FORMATTING`
+        && snippet_opt(cx, rhs.span.until(operand.span)).as_deref() == Some("-")
+        && let op_span = lhs.span.between(operand.span)
+        && let binop_str = BinOpKind::to_string(&binop.node)
+        && snippet_opt(cx, op_span).is_some_and(|snippet| snippet.trim_start() == format!("{binop_str}-"))
+    {
+        span_lint_and_sugg(
+            cx,
+            SUSPICIOUS_COMPARISON_FORMATTING,
+            op_span,
+            &format!("`{binop_str}-` looks like a single operator, but this compares with a negated value"),
+            &format!("put a space between `{binop_str}` and `-`"),
+            format!(" {binop_str} -"),
+            Applicability::MachineApplicable,
+        );
+    }
+}
+
 /// Implementation of the `SUSPICIOUS_ELSE_FORMATTING` lint for weird `else`.
 fn check_else(cx: &EarlyContext<'_>, expr: &Expr) {
     if let ExprKind::If(_, then, Some(else_)) = &expr.kind
@@ -0,0 +1,103 @@
+use clippy_utils::comparisons::ord_field_comparisons;
+use clippy_utils::diagnostics::span_lint_and_note;
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual `Ord` and `PartialOrd` impls that compare the same field in opposite
+    /// directions, e.g. `self.x < other.x` in `lt` and in `gt`.
+    ///
+    /// ### Why is this bad?
+    /// The methods of the impl disagree about how values are ordered, so sorting or comparing
+    /// them gives different results depending on which method is used.
+    ///
+    /// ### Known problems
+    /// This is a heuristic. It only looks at comparisons of fields of `self` and `other`, and in
+    /// `cmp` and `partial_cmp` only at those deciding an `if` that returns an `Ordering`.
+    /// Comparisons that are negated are ignored.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::cmp::Ordering;
+    /// #[derive(PartialEq)]
+    /// struct Version {
+    ///     major: u32,
+    /// }
+    ///
+    /// impl PartialOrd for Version {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         self.major.partial_cmp(&other.major)
+    ///     }
+    ///
+    ///     fn lt(&self, other: &Self) -> bool {
+    ///         self.major < other.major
+    ///     }
+    ///
+    ///     fn gt(&self, other: &Self) -> bool {
+    ///         self.major < other.major
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::cmp::Ordering;
+    /// #[derive(PartialEq)]
+    /// struct Version {
+    ///     major: u32,
+    /// }
+    ///
+    /// impl PartialOrd for Version {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         self.major.partial_cmp(&other.major)
+    ///     }
+    ///
+    ///     fn lt(&self, other: &Self) -> bool {
+    ///         self.major < other.major
+    ///     }
+    ///
+    ///     fn gt(&self, other: &Self) -> bool {
+    ///         self.major > other.major
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub INCONSISTENT_ORD_COMPARISONS,
+    restriction,
+    "`Ord` or `PartialOrd` impls comparing the same field in opposite directions"
+}
+
+declare_lint_pass!(InconsistentOrdComparisons => [INCONSISTENT_ORD_COMPARISONS]);
+
+impl<'tcx> LateLintPass<'tcx> for InconsistentOrdComparisons {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if let ItemKind::Impl(impl_) = item.kind
+            && !item.span.from_expansion()
+            && let Some(trait_id) = impl_.of_trait.and_then(|trait_ref| trait_ref.trait_def_id())
+            && matches!(cx.tcx.get_diagnostic_name(trait_id), Some(sym::Ord | sym::PartialOrd))
+        {
+            let comparisons = ord_field_comparisons(cx, impl_);
+            for (i, comparison) in comparisons.iter().enumerate() {
+                // Compare against the first comparison of the field, so an impl that orders a
+                // field in reverse everywhere is not linted.
+                if let Some(first) = comparisons[..i].iter().find(|first| first.field == comparison.field)
+                    && first.is_consistent() != comparison.is_consistent()
+                {
+                    span_lint_and_note(
+                        cx,
+                        INCONSISTENT_ORD_COMPARISONS,
+                        comparison.span,
+                        &format!(
+                            "field `{}` is compared in the opposite direction elsewhere in this impl",
+                            comparison.field
+                        ),
+                        Some(first.span),
+                        "the other comparison is here",
+                    );
+                }
+            }
+        }
+    }
+}
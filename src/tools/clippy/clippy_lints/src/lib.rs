@@ -86,6 +86,7 @@
 mod cognitive_complexity;
 mod collapsible_if;
 mod collection_is_never_read;
+mod comparison_as_index;
 mod comparison_chain;
 mod copies;
 mod copy_iterator;
@@ -149,6 +150,7 @@
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
 mod implied_bounds_in_impls;
+mod inconsistent_ord_comparisons;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
 mod indexing_slicing;
@@ -195,9 +197,11 @@
 mod manual_range_patterns;
 mod manual_rem_euclid;
 mod manual_retain;
+mod manual_signum;
 mod manual_slice_size_calculation;
 mod manual_string_new;
 mod manual_strip;
+mod manual_tuple_match;
 mod map_unit_fn;
 mod match_result_ok;
 mod matches;
@@ -322,6 +326,7 @@
 mod transmute;
 mod tuple_array_conversions;
 mod types;
+mod unchecked_clamp_bounds;
 mod undocumented_unsafe_blocks;
 mod unicode;
 mod uninit_vec;
@@ -519,6 +524,8 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         ref await_holding_invalid_types,
         cargo_ignore_publish,
         cognitive_complexity_threshold,
+        comparison_as_index_in_arithmetic,
+        comparison_constant_side,
         ref disallowed_macros,
         ref disallowed_methods,
         ref disallowed_names,
@@ -534,6 +541,7 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
         ref ignore_interior_mutability,
         large_error_threshold,
         literal_representation_threshold,
+        manual_tuple_match_threshold,
         matches_for_let_else,
         max_fn_params_bools,
         max_include_file_size,
@@ -953,7 +961,13 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(|_| Box::new(default_instead_of_iter_empty::DefaultIterEmpty));
     store.register_late_pass(move |_| Box::new(manual_rem_euclid::ManualRemEuclid::new(msrv())));
     store.register_late_pass(move |_| Box::new(manual_retain::ManualRetain::new(msrv())));
-    store.register_late_pass(move |_| Box::new(operators::Operators::new(verbose_bit_mask_threshold)));
+    store.register_late_pass(move |_| {
+        Box::new(operators::Operators::new(
+            verbose_bit_mask_threshold,
+            comparison_constant_side,
+            msrv(),
+        ))
+    });
     store.register_late_pass(|_| Box::<std_instead_of_core::StdReexports>::default());
     store.register_late_pass(move |_| Box::new(instant_subtraction::InstantSubtraction::new(msrv())));
     store.register_late_pass(|_| Box::new(partialeq_to_none::PartialeqToNone));
@@ -1066,6 +1080,19 @@ pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &'static Conf) {
     store.register_late_pass(move |_| Box::new(manual_hash_one::ManualHashOne::new(msrv())));
     store.register_late_pass(|_| Box::new(iter_without_into_iter::IterWithoutIntoIter));
     store.register_late_pass(|_| Box::new(iter_over_hash_type::IterOverHashType));
+    store.register_late_pass(|_| Box::new(inconsistent_ord_comparisons::InconsistentOrdComparisons));
+    store.register_late_pass(move |_| {
+        Box::new(comparison_as_index::ComparisonAsIndex {
+            comparison_as_index_in_arithmetic,
+        })
+    });
+    store.register_late_pass(|_| Box::new(manual_signum::ManualSignum));
+    store.register_late_pass(|_| Box::new(unchecked_clamp_bounds::UncheckedClampBounds));
+    store.register_late_pass(move |_| {
+        Box::new(manual_tuple_match::ManualTupleMatch {
+            manual_tuple_match_threshold,
+        })
+    });
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
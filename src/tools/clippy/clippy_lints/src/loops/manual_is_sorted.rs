@@ -0,0 +1,266 @@
+use super::MANUAL_IS_SORTED;
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
+use clippy_utils::usage::is_potentially_mutated;
+use clippy_utils::{eq_expr_value, higher, path_to_local_id, peel_blocks_with_stmt};
+use rustc_ast::ast::{LitKind, RangeLimits};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, BindingAnnotation, Block, Expr, ExprKind, HirId, Local, Node, Pat, PatKind, StmtKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::{sym, Span};
+
+/// Which of the two adjacent elements the loop counter is the index of.
+#[derive(Clone, Copy)]
+enum Window {
+    /// `for i in 0..v.len() - 1`, comparing `v[i]` and `v[i + 1]`.
+    CounterFirst,
+    /// `for i in 1..v.len()`, comparing `v[i - 1]` and `v[i]`.
+    CounterSecond,
+}
+
+/// One of the two adjacent elements.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Element {
+    First,
+    Second,
+}
+
+/// Checks `for i in 0..v.len() - 1` and `for i in 1..v.len()` loops that compare adjacent
+/// elements of `v` and record the result in a `bool` that is set to `true` right before the loop.
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    pat: &'tcx Pat<'_>,
+    arg: &'tcx Expr<'_>,
+    body: &'tcx Expr<'_>,
+    expr: &'tcx Expr<'_>,
+    span: Span,
+) {
+    // `is_sorted` is unstable.
+    if !cx.tcx.features().declared(sym!(is_sorted)) {
+        return;
+    }
+    if let PatKind::Binding(BindingAnnotation::NONE, counter, _, None) = pat.kind
+        && let Some(higher::Range {
+            start: Some(start),
+            end: Some(end),
+            limits: RangeLimits::HalfOpen,
+        }) = higher::Range::hir(arg)
+        && let Some((slice, window)) = window(start, end)
+        && has_is_sorted(cx, cx.typeck_results().expr_ty(slice))
+        && let Some((block, local, acc)) = accumulator_before(cx, expr)
+        && let Some(descending) = adjacent_comparison(cx, body, acc, |e| element(cx, e, slice, counter, window))
+    {
+        let mut app = Applicability::MachineApplicable;
+        let slice = snippet_with_applicability(cx, slice.span, "..", &mut app);
+        let (help, method) = if descending {
+            (
+                "use `is_sorted_by` with the reversed comparison instead",
+                ".is_sorted_by(|a, b| b.partial_cmp(a))",
+            )
+        } else {
+            ("use `is_sorted` instead", ".is_sorted()")
+        };
+        let acc_snip = snippet_with_applicability(cx, local.pat.span, "..", &mut app);
+        // `let mut sorted` keeps its `mut` only if it is still needed.
+        let acc = if mutated_after(cx, block, expr, acc) {
+            &acc_snip
+        } else {
+            acc_snip.trim_start_matches("mut ")
+        };
+        span_lint_and_sugg(
+            cx,
+            MANUAL_IS_SORTED,
+            local.span.to(span),
+            &format!("this loop checks whether `{slice}` is sorted"),
+            help,
+            format!("let {acc} = {slice}{method};"),
+            app,
+        );
+    }
+}
+
+/// Returns the slice and the window if the range is `0..v.len() - 1` or `1..v.len()`.
+fn window<'a>(start: &Expr<'_>, end: &'a Expr<'a>) -> Option<(&'a Expr<'a>, Window)> {
+    let (len, window) = match (int_lit(start)?, end.kind) {
+        (0, ExprKind::Binary(op, len, one)) if op.node == BinOpKind::Sub && int_lit(one) == Some(1) => {
+            (len, Window::CounterFirst)
+        },
+        (1, _) => (end, Window::CounterSecond),
+        _ => return None,
+    };
+    match len.kind {
+        ExprKind::MethodCall(path, slice, [], _) if path.ident.name == sym::len => Some((slice, window)),
+        _ => None,
+    }
+}
+
+/// Returns the value of `expr` if it is an integer literal.
+fn int_lit(expr: &Expr<'_>) -> Option<u128> {
+    if let ExprKind::Lit(lit) = expr.kind
+        && let LitKind::Int(value, _) = lit.node
+    {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Checks if `ty` derefs to a slice, so that it has `is_sorted`.
+fn has_is_sorted<'tcx>(cx: &LateContext<'tcx>, ty: ty::Ty<'tcx>) -> bool {
+    let ty = ty.peel_refs();
+    match ty.kind() {
+        ty::Slice(_) | ty::Array(..) => true,
+        _ => is_type_diagnostic_item(cx, ty, sym::Vec),
+    }
+}
+
+/// Returns the block around the loop `expr`, the statement before the loop and the local it
+/// declares, if that statement is `let mut acc = true;`.
+fn accumulator_before<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &Expr<'_>,
+) -> Option<(&'tcx Block<'tcx>, &'tcx Local<'tcx>, HirId)> {
+    let mut parent_iter = cx.tcx.hir().parent_iter(expr.hir_id);
+    let (stmt_id, Node::Stmt(_)) = parent_iter.next()? else {
+        return None;
+    };
+    let (_, Node::Block(block)) = parent_iter.next()? else {
+        return None;
+    };
+    let index = block.stmts.iter().position(|stmt| stmt.hir_id == stmt_id)?;
+    if let StmtKind::Local(local) = block.stmts.get(index.checked_sub(1)?)?.kind
+        && let PatKind::Binding(BindingAnnotation::MUT, acc, _, None) = local.pat.kind
+        && local.els.is_none()
+        && let Some(init) = local.init
+        && let ExprKind::Lit(lit) = init.kind
+        && let LitKind::Bool(true) = lit.node
+    {
+        Some((block, local, acc))
+    } else {
+        None
+    }
+}
+
+/// Returns whether the order is descending if `body` is `acc &= v[i] <= v[i + 1];`,
+/// `acc = acc && v[i] <= v[i + 1];` or `if v[i] > v[i + 1] { acc = false; break; }`, with the
+/// `break` being optional, or the same with the elements swapped.
+fn adjacent_comparison<'tcx>(
+    cx: &LateContext<'tcx>,
+    body: &'tcx Expr<'tcx>,
+    acc: HirId,
+    element: impl Fn(&Expr<'_>) -> Option<Element>,
+) -> Option<bool> {
+    // Returns the relation and whether the left operand is the second element.
+    let comparison = |e: &Expr<'_>| {
+        let ExprKind::Binary(op, lhs, rhs) = e.kind else {
+            return None;
+        };
+        let (rel, left, right) = normalize_comparison(op.node, lhs, rhs)?;
+        let (left, right) = (element(left)?, element(right)?);
+        (left != right).then_some((rel, left == Element::Second))
+    };
+    let sorted = match peel_blocks_with_stmt(body).kind {
+        ExprKind::AssignOp(op, lhs, sorted) if op.node == BinOpKind::BitAnd && path_to_local_id(lhs, acc) => sorted,
+        ExprKind::Assign(lhs, rhs, _) if path_to_local_id(lhs, acc) => {
+            let ExprKind::Binary(op, acc_again, sorted) = rhs.kind else {
+                return None;
+            };
+            if op.node != BinOpKind::And || !path_to_local_id(acc_again, acc) {
+                return None;
+            }
+            sorted
+        },
+        ExprKind::If(cond, then, None) if is_unset(then, acc) => {
+            let ExprKind::DropTemps(unsorted) = cond.kind else {
+                return None;
+            };
+            let (Rel::Lt, second_is_less) = comparison(unsorted)? else {
+                return None;
+            };
+            // `!(a > b)` is `a <= b` only if all elements are comparable.
+            let ExprKind::Binary(_, elem, _) = unsorted.kind else {
+                return None;
+            };
+            let ord = cx.tcx.get_diagnostic_item(sym::Ord)?;
+            return implements_trait(cx, cx.typeck_results().expr_ty(elem), ord, &[]).then_some(!second_is_less);
+        },
+        _ => return None,
+    };
+    match comparison(sorted)? {
+        (Rel::Le, second_is_less) => Some(second_is_less),
+        _ => None,
+    }
+}
+
+/// Checks if `then` is `{ acc = false; }` or `{ acc = false; break; }`.
+fn is_unset(then: &Expr<'_>, acc: HirId) -> bool {
+    let ExprKind::Block(block, _) = then.kind else {
+        return false;
+    };
+    let (assign, rest) = match (block.stmts, block.expr) {
+        ([assign], None) => (assign, None),
+        ([assign, brk], None) => (assign, Some(brk)),
+        _ => return false,
+    };
+    if let StmtKind::Semi(assign) = assign.kind
+        && let ExprKind::Assign(lhs, rhs, _) = assign.kind
+        && path_to_local_id(lhs, acc)
+        && let ExprKind::Lit(lit) = rhs.kind
+        && let LitKind::Bool(false) = lit.node
+    {
+        rest.map_or(true, |brk| {
+            matches!(brk.kind, StmtKind::Semi(e)
+                if matches!(e.kind, ExprKind::Break(dest, None) if dest.label.is_none()))
+        })
+    } else {
+        false
+    }
+}
+
+/// Returns which element `expr` is, if it is `v[i]`, `v[i + 1]` or `v[i - 1]` for the slice and
+/// the counter of the loop.
+fn element(cx: &LateContext<'_>, expr: &Expr<'_>, slice: &Expr<'_>, counter: HirId, window: Window) -> Option<Element> {
+    let ExprKind::Index(indexed, index, _) = expr.kind else {
+        return None;
+    };
+    if !eq_expr_value(cx, indexed, slice) {
+        return None;
+    }
+    if path_to_local_id(index, counter) {
+        return Some(match window {
+            Window::CounterFirst => Element::First,
+            Window::CounterSecond => Element::Second,
+        });
+    }
+    let ExprKind::Binary(op, lhs, rhs) = index.kind else {
+        return None;
+    };
+    if !path_to_local_id(lhs, counter) || int_lit(rhs) != Some(1) {
+        return None;
+    }
+    match (window, op.node) {
+        (Window::CounterFirst, BinOpKind::Add) => Some(Element::Second),
+        (Window::CounterSecond, BinOpKind::Sub) => Some(Element::First),
+        _ => None,
+    }
+}
+
+/// Checks if `acc` may be mutated after the loop `expr` in `block`, so that it has to stay
+/// mutable.
+fn mutated_after<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>, expr: &Expr<'_>, acc: HirId) -> bool {
+    block
+        .stmts
+        .iter()
+        .skip_while(|stmt| !matches!(stmt.kind, StmtKind::Expr(e) | StmtKind::Semi(e) if e.hir_id == expr.hir_id))
+        .skip(1)
+        .filter_map(|stmt| match stmt.kind {
+            StmtKind::Local(local) => local.init,
+            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+            StmtKind::Item(_) => None,
+        })
+        .chain(block.expr)
+        .any(|e| is_potentially_mutated(acc, e, cx))
+}
@@ -0,0 +1,123 @@
+use super::utils::make_iterator_snippet;
+use super::MANUAL_MIN_MAX_LOOP;
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::implements_trait;
+use clippy_utils::{higher, path_to_local, path_to_local_id, peel_blocks_with_stmt};
+use rustc_errors::Applicability;
+use rustc_hir::{BindingAnnotation, Expr, ExprKind, HirId, Pat, PatKind, StmtKind};
+use rustc_lint::LateContext;
+use rustc_span::{sym, Span};
+
+/// Checks `for x in v { if x > best { best = x; } }` loops, with `<` for the minimum, and the same
+/// with a projection like `x.score > best.score` on both sides of the comparison.
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    pat: &'tcx Pat<'_>,
+    arg: &'tcx Expr<'_>,
+    body: &'tcx Expr<'_>,
+    span: Span,
+) {
+    let (item, copied) = match pat.kind {
+        PatKind::Binding(BindingAnnotation::NONE, item, _, None) => (item, false),
+        PatKind::Ref(inner, _) if let PatKind::Binding(BindingAnnotation::NONE, item, _, None) = inner.kind => {
+            (item, true)
+        },
+        _ => return,
+    };
+    if let Some(higher::If {
+        cond,
+        then,
+        r#else: None,
+    }) = higher::If::hir(peel_blocks_with_stmt(body))
+        && let Some(acc) = assigned_item(then, item)
+        && let ExprKind::Binary(op, lhs, rhs) = cond.kind
+        && let Some((rel, left, right)) = normalize_comparison(op.node, lhs, rhs)
+        // `acc < item` computes the maximum, and `item < acc` the minimum.
+        && let Some((item_side, is_max, projected)) = same_projection(right, left, item, acc)
+            .map(|projected| (right, true, projected))
+            .or_else(|| same_projection(left, right, item, acc).map(|projected| (left, false, projected)))
+        // `max` and `min` need a total order, which is not what `<` is for e.g. floats.
+        && let Some(ord) = cx.tcx.get_diagnostic_item(sym::Ord)
+        && implements_trait(cx, cx.typeck_results().expr_ty(item_side), ord, &[])
+    {
+        let mut app = Applicability::MaybeIncorrect;
+        let mut iter = make_iterator_snippet(cx, arg, &mut app);
+        if copied {
+            iter.push_str(".copied()");
+        }
+        let method = if is_max { "max" } else { "min" };
+        let (method, call) = if projected {
+            let item_name = cx.tcx.hir().name(item);
+            let key = snippet_with_applicability(cx, item_side.span, "..", &mut app);
+            let call = format!("{iter}.{method}_by_key(|{item_name}| {key})");
+            (format!("{method}_by_key"), call)
+        } else {
+            (method.to_owned(), format!("{iter}.{method}()"))
+        };
+        let acc = cx.tcx.hir().name(acc);
+        span_lint_and_then(
+            cx,
+            MANUAL_MIN_MAX_LOOP,
+            span,
+            &format!("manual implementation of `Iterator::{method}`"),
+            |diag| {
+                diag.help(format!("consider using `{call}`"));
+                diag.note(format!(
+                    "`{method}` returns `None` for an empty iterator and ignores the initial value of `{acc}`"
+                ));
+                // `max_by_key` returns the last of several elements with the largest key, and
+                // `min_by_key` the first of several with the smallest. Which one the loop keeps
+                // depends on whether it compares strictly. Equal plain values can't be told apart,
+                // so that only matters with a projection.
+                if projected && is_max == (rel == Rel::Lt) {
+                    let which = if is_max { "last" } else { "first" };
+                    diag.note(format!(
+                        "unlike the loop, `{method}` returns the {which} of the elements it considers equal"
+                    ));
+                }
+            },
+        );
+    }
+}
+
+/// Returns the accumulator if `then` is `{ acc = item; }`.
+fn assigned_item(then: &Expr<'_>, item: HirId) -> Option<HirId> {
+    let ExprKind::Block(block, _) = then.kind else {
+        return None;
+    };
+    let assign = match (block.stmts, block.expr) {
+        ([stmt], None) if let StmtKind::Semi(assign) = stmt.kind => assign,
+        ([], Some(assign)) => assign,
+        _ => return None,
+    };
+    if let ExprKind::Assign(lhs, rhs, _) = assign.kind
+        && path_to_local_id(rhs, item)
+        && let Some(acc) = path_to_local(lhs)
+        && acc != item
+    {
+        Some(acc)
+    } else {
+        None
+    }
+}
+
+/// Returns whether there is a projection if `item_side` and `acc_side` are `item` and `acc`, or
+/// access the same fields or call the same methods without arguments on them, like `item.score`
+/// and `acc.score`.
+fn same_projection(item_side: &Expr<'_>, acc_side: &Expr<'_>, item: HirId, acc: HirId) -> Option<bool> {
+    match (item_side.kind, acc_side.kind) {
+        (ExprKind::Field(item_base, item_field), ExprKind::Field(acc_base, acc_field))
+            if item_field.name == acc_field.name =>
+        {
+            same_projection(item_base, acc_base, item, acc).map(|_| true)
+        },
+        (ExprKind::MethodCall(item_path, item_recv, [], _), ExprKind::MethodCall(acc_path, acc_recv, [], _))
+            if item_path.ident.name == acc_path.ident.name =>
+        {
+            same_projection(item_recv, acc_recv, item, acc).map(|_| true)
+        },
+        _ => (path_to_local_id(item_side, item) && path_to_local_id(acc_side, acc)).then_some(false),
+    }
+}
@@ -6,17 +6,21 @@
 mod iter_next_loop;
 mod manual_find;
 mod manual_flatten;
+mod manual_is_sorted;
 mod manual_memcpy;
+mod manual_min_max_loop;
 mod manual_while_let_some;
 mod missing_spin_loop;
 mod mut_range_bound;
 mod needless_range_loop;
 mod never_loop;
+mod off_by_one_slice_window;
 mod same_item_push;
 mod single_element_loop;
 mod unused_enumerate_index;
 mod utils;
 mod while_immutable_condition;
+mod while_len_comparison;
 mod while_let_loop;
 mod while_let_on_iterator;
 
@@ -635,6 +639,147 @@
     "checking for emptiness of a `Vec` in the loop condition and popping an element in the body"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `while` loop conditions that compare a counter with a `len()` or `count()` call
+    /// on a value the loop does not change, like `while i < v.len()`.
+    ///
+    /// ### Why is this bad?
+    /// The length is computed again on every iteration, even though it cannot change. For
+    /// methods like `chars().count()`, that means going over the whole value every time.
+    ///
+    /// ### Known problems
+    /// Mutable references, values with interior mutability and loops with `unsafe` code are not
+    /// linted, since the value could be changed without being mutated directly in the loop.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let text = "abc";
+    /// let mut i = 0;
+    /// while i < text.chars().count() {
+    ///     i += 1;
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let text = "abc";
+    /// let len = text.chars().count();
+    /// let mut i = 0;
+    /// while i < len {
+    ///     i += 1;
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub WHILE_LEN_COMPARISON,
+    perf,
+    "comparing with the length of a value the loop does not change in a `while` condition"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for loops over the indices of a slice, like `for i in 0..v.len()` or
+    /// `while i < v.len()`, that index the same slice with an offset, like `v[i + 1]`.
+    ///
+    /// ### Why is this bad?
+    /// In the last iteration, `i` is `v.len() - 1`, so `v[i + 1]` is out of bounds and panics.
+    ///
+    /// ### Known problems
+    /// Only offsets that are integer literals are checked. Loops that compare the index or
+    /// `match` on it in the body are not linted, since that could be a guard against the last
+    /// iteration.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let v = vec![1, 2, 3];
+    /// for i in 0..v.len() {
+    ///     println!("{}", v[i + 1] - v[i]);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let v = vec![1, 2, 3];
+    /// for i in 0..v.len().saturating_sub(1) {
+    ///     println!("{}", v[i + 1] - v[i]);
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub OFF_BY_ONE_SLICE_WINDOW,
+    correctness,
+    "indexing a slice past its end in the last iteration of a loop over its indices"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for loops over the indices of a slice that compare each element with the next one
+    /// to find out whether the slice is sorted, like
+    /// `for i in 0..v.len() - 1 { sorted &= v[i] <= v[i + 1]; }`.
+    ///
+    /// ### Why is this bad?
+    /// `is_sorted` says the same more clearly, and `is_sorted_by` covers descending order.
+    ///
+    /// ### Known problems
+    /// `is_sorted` is unstable, so this is only linted when the `is_sorted` feature is enabled.
+    /// Only loops of the form `for i in 0..v.len() - 1` or `for i in 1..v.len()` whose body just
+    /// records the comparison in a `bool` declared right before the loop are linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let v = vec![1, 2, 3];
+    /// let mut sorted = true;
+    /// for i in 1..v.len() {
+    ///     if v[i - 1] > v[i] {
+    ///         sorted = false;
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```ignore
+    /// # let v = vec![1, 2, 3];
+    /// let sorted = v.is_sorted();
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_IS_SORTED,
+    complexity,
+    "checking whether a slice is sorted with a loop"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `for` loops that only keep the largest or smallest element seen so far, like
+    /// `if x > best { best = x; }`, also when comparing a field or the result of a method call
+    /// of both, like `if x.score > best.score { best = x; }`.
+    ///
+    /// ### Why is this bad?
+    /// `Iterator::max` and `Iterator::min`, or `max_by_key` and `min_by_key` with a projection,
+    /// say what the loop computes.
+    ///
+    /// ### Known problems
+    /// Unlike the loop, the iterator methods return `None` for an empty iterator and do not take
+    /// the initial value of the accumulator into account. With a projection, they might also
+    /// pick a different one of several elements with the same key, see their documentation.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let scores = vec![1, 4, 2];
+    /// let mut best = 0;
+    /// for &score in &scores {
+    ///     if score > best {
+    ///         best = score;
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let scores = vec![1, 4, 2];
+    /// let best = scores.iter().copied().max().unwrap_or(0);
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_MIN_MAX_LOOP,
+    complexity,
+    "using a loop to find the largest or smallest element of an iterator"
+}
+
 pub struct Loops {
     msrv: Msrv,
     enforce_iter_loop_reborrow: bool,
@@ -669,6 +814,10 @@ pub fn new(msrv: Msrv, enforce_iter_loop_reborrow: bool) -> Self {
     MANUAL_FIND,
     MANUAL_WHILE_LET_SOME,
     UNUSED_ENUMERATE_INDEX,
+    WHILE_LEN_COMPARISON,
+    OFF_BY_ONE_SLICE_WINDOW,
+    MANUAL_IS_SORTED,
+    MANUAL_MIN_MAX_LOOP,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Loops {
@@ -719,6 +868,8 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             while_immutable_condition::check(cx, condition, body);
             missing_spin_loop::check(cx, condition, body);
             manual_while_let_some::check(cx, condition, body, span);
+            while_len_comparison::check(cx, condition, body);
+            off_by_one_slice_window::check_while_loop(cx, condition, body);
         }
     }
 
@@ -748,6 +899,9 @@ fn check_for_loop<'tcx>(
         manual_flatten::check(cx, pat, arg, body, span);
         manual_find::check(cx, pat, arg, body, span, expr);
         unused_enumerate_index::check(cx, pat, arg, body);
+        off_by_one_slice_window::check_for_loop(cx, pat, arg, body);
+        manual_is_sorted::check(cx, pat, arg, body, expr, span);
+        manual_min_max_loop::check(cx, pat, arg, body, span);
     }
 
     fn check_for_loop_arg(&self, cx: &LateContext<'_>, _: &Pat<'_>, arg: &Expr<'_>) {
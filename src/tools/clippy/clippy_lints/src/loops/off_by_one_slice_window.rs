@@ -0,0 +1,167 @@
+use super::while_len_comparison::local_base;
+use super::OFF_BY_ONE_SLICE_WINDOW;
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::usage::mutated_variables;
+use clippy_utils::visitors::{contains_unsafe_block, for_each_expr, is_local_used};
+use clippy_utils::{eq_expr_value, higher, path_to_local, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_ast::ast::{LitKind, RangeLimits};
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, MatchSource, Pat, PatKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::sym;
+
+/// Checks `for i in a..v.len()` loops.
+pub(super) fn check_for_loop<'tcx>(
+    cx: &LateContext<'tcx>,
+    pat: &'tcx Pat<'_>,
+    arg: &'tcx Expr<'_>,
+    body: &'tcx Expr<'_>,
+) {
+    if let PatKind::Binding(_, counter, _, None) = pat.kind
+        && let Some(higher::Range {
+            end: Some(end),
+            limits: RangeLimits::HalfOpen,
+            ..
+        }) = higher::Range::hir(arg)
+        && let Some(receiver) = len_receiver(end)
+    {
+        check(cx, arg, counter, receiver, body);
+    }
+}
+
+/// Checks `while i < v.len()` loops.
+pub(super) fn check_while_loop<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>, body: &'tcx Expr<'tcx>) {
+    if let ExprKind::Binary(op, lhs, rhs) = cond.kind
+        && let Some((Rel::Lt, left, right)) = normalize_comparison(op.node, lhs, rhs)
+        && let Some(counter) = path_to_local(left)
+        && let Some(receiver) = len_receiver(right)
+    {
+        check(cx, cond, counter, receiver, body);
+    }
+}
+
+/// Lints the first index of the form `receiver[counter + k]` in `body`, where the loop `bound`
+/// lets `counter` go up to `receiver.len() - 1`.
+fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    bound: &Expr<'_>,
+    counter: HirId,
+    receiver: &'tcx Expr<'tcx>,
+    body: &'tcx Expr<'tcx>,
+) {
+    // If the receiver can change in the loop, it could have grown.
+    let Some(base) = local_base(receiver) else {
+        return;
+    };
+    let base_ty = cx.typeck_results().node_type(base);
+    if !base_ty.is_freeze(cx.tcx, cx.param_env)
+        || contains_unsafe_block(cx, body)
+        || mutated_variables(body, cx).map_or(true, |mutated| mutated.contains(&base))
+        || !is_indexable_by_position(cx, cx.typeck_results().expr_ty(receiver))
+    {
+        return;
+    }
+
+    let mut offset_index = None;
+    let guarded = for_each_expr(body, |e| {
+        match e.kind {
+            // Any comparison involving the counter could be a guard against the last iteration.
+            ExprKind::Binary(op, lhs, rhs)
+                if op.node.is_comparison() && (is_local_used(cx, lhs, counter) || is_local_used(cx, rhs, counter)) =>
+            {
+                return ControlFlow::Break(());
+            },
+            ExprKind::Match(scrutinee, _, MatchSource::Normal) if is_local_used(cx, scrutinee, counter) => {
+                return ControlFlow::Break(());
+            },
+            // The counter could be moved back before it is used.
+            ExprKind::Assign(lhs, ..) if path_to_local_id(lhs, counter) => {
+                return ControlFlow::Break(());
+            },
+            ExprKind::AssignOp(op, lhs, _) if op.node != BinOpKind::Add && path_to_local_id(lhs, counter) => {
+                return ControlFlow::Break(());
+            },
+            ExprKind::Index(indexed, index, _) if offset_index.is_none() && eq_expr_value(cx, indexed, receiver) => {
+                if let Some(offset) = counter_offset(index, counter) {
+                    offset_index = Some((e, offset));
+                }
+            },
+            _ => {},
+        }
+        ControlFlow::Continue(())
+    })
+    .is_some();
+
+    if !guarded && let Some((index_expr, offset)) = offset_index {
+        let receiver = snippet(cx, receiver.span, "..");
+        let counter = cx.tcx.hir().name(counter);
+        let last_index = if offset == 1 {
+            format!("{receiver}.len()")
+        } else {
+            format!("{receiver}.len() + {}", offset - 1)
+        };
+        span_lint_and_then(
+            cx,
+            OFF_BY_ONE_SLICE_WINDOW,
+            index_expr.span,
+            "this index is out of bounds in the last iteration of the loop",
+            |diag| {
+                diag.span_note(
+                    bound.span,
+                    format!(
+                        "`{counter}` is `{receiver}.len() - 1` in the last iteration, so this indexes `{last_index}`"
+                    ),
+                );
+                diag.help(format!(
+                    "consider stopping the loop earlier, e.g. at `{receiver}.len().saturating_sub({offset})`"
+                ));
+            },
+        );
+    }
+}
+
+/// Returns the receiver if `expr` is a `len()` call.
+fn len_receiver<'a>(expr: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+    match expr.kind {
+        ExprKind::MethodCall(path, receiver, [], _) if path.ident.name == sym::len => Some(receiver),
+        _ => None,
+    }
+}
+
+/// Checks if values of type `ty` are indexed by position, so that the index must be less than
+/// their `len()`.
+fn is_indexable_by_position<'tcx>(cx: &LateContext<'tcx>, ty: ty::Ty<'tcx>) -> bool {
+    let ty = ty.peel_refs();
+    match ty.kind() {
+        ty::Slice(_) | ty::Array(..) => true,
+        _ => is_type_diagnostic_item(cx, ty, sym::Vec) || is_type_diagnostic_item(cx, ty, sym::VecDeque),
+    }
+}
+
+/// Returns `k` if `index` is `counter + k` or `k + counter` for an integer literal `k > 0`.
+fn counter_offset(index: &Expr<'_>, counter: HirId) -> Option<u128> {
+    let ExprKind::Binary(op, lhs, rhs) = index.kind else {
+        return None;
+    };
+    if op.node != BinOpKind::Add {
+        return None;
+    }
+    let (local, literal) = if path_to_local_id(lhs, counter) {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    if path_to_local_id(local, counter)
+        && let ExprKind::Lit(lit) = literal.kind
+        && let LitKind::Int(offset, _) = lit.node
+        && offset > 0
+    {
+        Some(offset)
+    } else {
+        None
+    }
+}
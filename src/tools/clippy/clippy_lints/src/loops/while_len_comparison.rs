@@ -0,0 +1,67 @@
+use super::WHILE_LEN_COMPARISON;
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::path_to_local;
+use clippy_utils::source::snippet;
+use clippy_utils::usage::mutated_variables;
+use clippy_utils::visitors::contains_unsafe_block;
+use rustc_hir::{Expr, ExprKind, HirId};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>, body: &'tcx Expr<'tcx>) {
+    let ExprKind::Binary(op, lhs, rhs) = cond.kind else {
+        return;
+    };
+    let Some((_, left, right)) = normalize_comparison(op.node, lhs, rhs) else {
+        return;
+    };
+    let (len_call, method, receiver, counter) = match (len_call(left), len_call(right)) {
+        (Some((method, receiver)), None) => (left, method, receiver, right),
+        (None, Some((method, receiver))) => (right, method, receiver, left),
+        _ => return,
+    };
+    // With `unsafe` code, mutable references or interior mutability, the receiver could change
+    // without being mutated directly in the body.
+    if let Some(base) = local_base(receiver)
+        && let Some(counter) = path_to_local(counter)
+        && let Some(mutated) = mutated_variables(body, cx)
+        && mutated.contains(&counter)
+        && !mutated.contains(&base)
+        && let base_ty = cx.typeck_results().node_type(base)
+        && !base_ty.is_mutable_ptr()
+        && base_ty.is_freeze(cx.tcx, cx.param_env)
+        && !contains_unsafe_block(cx, body)
+    {
+        span_lint_and_help(
+            cx,
+            WHILE_LEN_COMPARISON,
+            len_call.span,
+            &format!("this loop condition calls `{method}` on every iteration"),
+            None,
+            &format!(
+                "`{}` is not changed in the loop, consider computing it once before the loop",
+                snippet(cx, len_call.span, "..")
+            ),
+        );
+    }
+}
+
+/// Returns the method name and the receiver if `expr` is a `len()` or `count()` call.
+fn len_call<'a>(expr: &'a Expr<'a>) -> Option<(Symbol, &'a Expr<'a>)> {
+    match expr.kind {
+        ExprKind::MethodCall(path, receiver, [], _) if matches!(path.ident.as_str(), "len" | "count") => {
+            Some((path.ident.name, receiver))
+        },
+        _ => None,
+    }
+}
+
+/// Returns the local that `expr` is computed from, if it only consists of that local, field
+/// accesses and method calls without arguments, like `self.text.chars()`.
+pub(super) fn local_base(expr: &Expr<'_>) -> Option<HirId> {
+    match expr.kind {
+        ExprKind::Field(base, _) | ExprKind::MethodCall(_, base, [], _) => local_base(base),
+        _ => path_to_local(expr),
+    }
+}
@@ -1,9 +1,11 @@
 use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint_and_then, span_lint_hir_and_then};
 use clippy_utils::higher::If;
 use clippy_utils::sugg::Sugg;
 use clippy_utils::ty::implements_trait;
-use clippy_utils::visitors::is_const_evaluatable;
+use clippy_utils::visitors::{for_each_expr, is_const_evaluatable};
 use clippy_utils::{
     eq_expr_value, in_constant, is_diag_trait_item, is_trait_method, path_res, path_to_local_id, peel_blocks,
     peel_blocks_with_stmt, MaybePath,
@@ -17,7 +19,8 @@
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::symbol::sym;
 use rustc_span::Span;
-use std::ops::Deref;
+use std::cmp::Ordering;
+use std::ops::{ControlFlow, Deref};
 
 declare_clippy_lint! {
     /// ### What it does
@@ -32,7 +35,8 @@
     ///
     /// `clamp` functions will panic if `max < min`, `max.is_nan()`, or `min.is_nan()`.
     /// Some may consider panicking in these situations to be desirable, but it also may
-    /// introduce panicking where there wasn't any before.
+    /// introduce panicking where there wasn't any before. If both bounds are constants, the
+    /// lint only fires when `min <= max`.
     ///
     /// See also [the discussion in the
     /// PR](https://github.com/rust-lang/rust-clippy/pull/9484#issuecomment-1278922613).
@@ -122,7 +126,9 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
                 .or_else(|| is_call_max_min_pattern(cx, expr))
                 .or_else(|| is_match_pattern(cx, expr))
                 .or_else(|| is_if_elseif_pattern(cx, expr));
-            if let Some(suggestion) = suggestion {
+            if let Some(suggestion) = suggestion
+                && !bounds_out_of_order(cx, &suggestion.params)
+            {
                 emit_suggestion(cx, &suggestion);
             }
         }
@@ -133,12 +139,30 @@ fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
             return;
         }
         for suggestion in is_two_if_pattern(cx, block) {
-            emit_suggestion(cx, &suggestion);
+            if !bounds_out_of_order(cx, &suggestion.params) {
+                emit_suggestion(cx, &suggestion);
+            }
         }
     }
     extract_msrv_attr!(LateContext);
 }
 
+/// Returns whether `min` and `max` are both constants and `min <= max` does not hold, in which
+/// case `clamp` would panic where the original code did not.
+fn bounds_out_of_order<'tcx>(cx: &LateContext<'tcx>, params: &InputMinMax<'tcx>) -> bool {
+    let typeck = cx.typeck_results();
+    if let Some(min) = constant(cx, typeck, params.min)
+        && let Some(max) = constant(cx, typeck, params.max)
+    {
+        !matches!(
+            Constant::partial_cmp(cx.tcx, typeck.expr_ty(params.min), &min, &max),
+            Some(Ordering::Less | Ordering::Equal)
+        )
+    } else {
+        false
+    }
+}
+
 fn emit_suggestion<'tcx>(cx: &LateContext<'tcx>, suggestion: &ClampSuggestion<'tcx>) {
     let ClampSuggestion {
         params: InputMinMax {
@@ -446,47 +470,43 @@ fn is_match_pattern<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Opt
 /// if x < min { x = min; }
 /// if x > max { x = max; }
 /// ```
+///
+/// Each guard sees the value written by the statement before it. Which bound is applied first
+/// only makes a difference when `min > max`, where `clamp` panics instead, but a bound that reads
+/// the input would be evaluated on the already clamped value, so such bounds are not linted.
 fn is_two_if_pattern<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) -> Vec<ClampSuggestion<'tcx>> {
     block_stmt_with_last(block)
         .tuple_windows()
         .filter_map(|(maybe_set_first, maybe_set_second)| {
             if let StmtKind::Expr(first_expr) = *maybe_set_first
                 && let StmtKind::Expr(second_expr) = *maybe_set_second
-                && let Some(If {
-                    cond: first_cond,
-                    then: first_then,
-                    r#else: None,
-                }) = If::hir(first_expr)
-                && let Some(If {
-                    cond: second_cond,
-                    then: second_then,
-                    r#else: None,
-                }) = If::hir(second_expr)
-                && let ExprKind::Assign(maybe_input_first_path, maybe_min_max_first, _) =
-                    peel_blocks_with_stmt(first_then).kind
-                && let ExprKind::Assign(maybe_input_second_path, maybe_min_max_second, _) =
-                    peel_blocks_with_stmt(second_then).kind
-                && eq_expr_value(cx, maybe_input_first_path, maybe_input_second_path)
-                && let Some(first_bin) = BinaryOp::new(first_cond)
-                && let Some(second_bin) = BinaryOp::new(second_cond)
-                && let Some(input_min_max) = is_clamp_meta_pattern(
-                    cx,
-                    &first_bin,
-                    &second_bin,
-                    maybe_min_max_first,
-                    maybe_min_max_second,
-                    None,
-                )
+                && let Some(first) = BoundAssignment::new(cx, first_expr)
+                && let Some(second) = BoundAssignment::new(cx, second_expr)
+                && first.is_upper != second.is_upper
+                && eq_expr_value(cx, first.target, second.target)
             {
+                let input = first.target;
+                let (min, max) = if first.is_upper {
+                    (second.bound, first.bound)
+                } else {
+                    (first.bound, second.bound)
+                };
+                let clampability = TypeClampability::is_clampable(cx, cx.typeck_results().expr_ty(input))?;
+                if [input, min, max].iter().any(|e| e.can_have_side_effects())
+                    || reads_expr(cx, min, input)
+                    || reads_expr(cx, max, input)
+                {
+                    return None;
+                }
                 Some(ClampSuggestion {
                     params: InputMinMax {
-                        input: maybe_input_first_path,
-                        min: input_min_max.min,
-                        max: input_min_max.max,
-                        is_float: input_min_max.is_float,
+                        input,
+                        min,
+                        max,
+                        is_float: clampability.is_float(),
                     },
                     span: first_expr.span.to(second_expr.span),
-                    make_assignment: Some(maybe_input_first_path),
+                    make_assignment: Some(input),
                     hir_with_ignore_attr: Some(first_expr.hir_id()),
                 })
             } else {
@@ -496,6 +516,67 @@ fn is_two_if_pattern<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) ->
         .collect()
 }
 
+/// A single `if` of the statement sequence form, like `if x > max { x = max; }`.
+struct BoundAssignment<'tcx> {
+    /// The place that is compared and assigned to, `x` above.
+    target: &'tcx Expr<'tcx>,
+    /// The bound that is assigned, `max` above.
+    bound: &'tcx Expr<'tcx>,
+    /// Whether `bound` is an upper bound, i.e. it is assigned when `target` is above it.
+    is_upper: bool,
+}
+
+impl<'tcx> BoundAssignment<'tcx> {
+    fn new(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<Self> {
+        let If {
+            cond,
+            then,
+            r#else: None,
+        } = If::hir(expr)?
+        else {
+            return None;
+        };
+        let ExprKind::Assign(target, bound, _) = peel_blocks_with_stmt(then).kind else {
+            return None;
+        };
+        let ExprKind::Binary(op, lhs, rhs) = peel_blocks(cond).kind else {
+            return None;
+        };
+        // Whether the comparison is strict does not matter, the result is the bound either way.
+        let (Rel::Lt | Rel::Le, below, above) = normalize_comparison(op.node, lhs, rhs)? else {
+            return None;
+        };
+        let bound = peel_blocks(bound);
+        if eq_expr_value(cx, above, target) && eq_expr_value(cx, below, bound) {
+            Some(Self {
+                target,
+                bound,
+                is_upper: true,
+            })
+        } else if eq_expr_value(cx, below, target) && eq_expr_value(cx, above, bound) {
+            Some(Self {
+                target,
+                bound,
+                is_upper: false,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Checks whether `expr` contains `needle`.
+fn reads_expr<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, needle: &Expr<'_>) -> bool {
+    for_each_expr(expr, |e| {
+        if eq_expr_value(cx, e, needle) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
+
 /// Targets patterns like
 ///
 /// ```no_run
@@ -0,0 +1,133 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant_full_int, FullInt};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::higher::If;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::{eq_expr_value, is_else_clause, peel_blocks};
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `if` chains that classify a signed integer as positive, negative or zero and
+    /// return `1`, `-1` or `0` accordingly, e.g.
+    /// `if x > 0 { 1 } else if x < 0 { -1 } else { 0 }`.
+    ///
+    /// ### Why is this bad?
+    /// That is what `signum` does, which is shorter and says what is meant.
+    ///
+    /// ### Known problems
+    /// Floats are not linted, since their `signum` returns `1.0` or `-1.0` for zeros and `NaN` for
+    /// `NaN`, instead of `0.0`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = -3i32;
+    /// let sign = if x > 0 {
+    ///     1
+    /// } else if x < 0 {
+    ///     -1
+    /// } else {
+    ///     0
+    /// };
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = -3i32;
+    /// let sign = x.signum();
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_SIGNUM,
+    complexity,
+    "manually reimplementing `signum`"
+}
+
+declare_lint_pass!(ManualSignum => [MANUAL_SIGNUM]);
+
+/// What a branch of the `if` chain tests about the value.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Sign {
+    Positive,
+    Negative,
+    Zero,
+}
+
+impl Sign {
+    /// The value `signum` returns for values of this sign.
+    fn signum(self) -> i128 {
+        match self {
+            Sign::Positive => 1,
+            Sign::Negative => -1,
+            Sign::Zero => 0,
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ManualSignum {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let Some(If {
+            cond: first_cond,
+            then: first_then,
+            r#else: Some(first_else),
+        }) = If::hir(expr)
+            && let Some(If {
+                cond: second_cond,
+                then: second_then,
+                r#else: Some(second_else),
+            }) = If::hir(peel_blocks(first_else))
+            && !expr.span.from_expansion()
+            && !is_else_clause(cx.tcx, expr)
+            && let Some((first_sign, value)) = sign_test(cx, first_cond)
+            && let Some((second_sign, second_value)) = sign_test(cx, second_cond)
+            && first_sign != second_sign
+            && eq_expr_value(cx, value, second_value)
+            && !value.can_have_side_effects()
+            && let ty = cx.typeck_results().expr_ty(value)
+            && matches!(ty.kind(), ty::Int(_))
+            && cx.typeck_results().expr_ty(expr) == ty
+        {
+            let else_sign = [Sign::Positive, Sign::Negative, Sign::Zero]
+                .into_iter()
+                .find(|&sign| sign != first_sign && sign != second_sign)
+                .unwrap();
+            let branches = [
+                (first_sign, first_then),
+                (second_sign, second_then),
+                (else_sign, second_else),
+            ];
+            if branches.iter().all(|&(sign, branch)| {
+                constant_full_int(cx, cx.typeck_results(), peel_blocks(branch)) == Some(FullInt::S(sign.signum()))
+            }) {
+                let mut app = Applicability::MachineApplicable;
+                let value = Sugg::hir_with_context(cx, value, expr.span.ctxt(), "..", &mut app).maybe_par();
+                span_lint_and_sugg(
+                    cx,
+                    MANUAL_SIGNUM,
+                    expr.span,
+                    "manual implementation of `signum`",
+                    "use",
+                    format!("{value}.signum()"),
+                    app,
+                );
+            }
+        }
+    }
+}
+
+/// Checks if `cond` compares some value with zero, like `x > 0`, `0 > x` or `x == 0`, and returns
+/// the sign that this tests for, together with the value.
+fn sign_test<'tcx>(cx: &LateContext<'tcx>, cond: &'tcx Expr<'tcx>) -> Option<(Sign, &'tcx Expr<'tcx>)> {
+    let is_zero = |e: &Expr<'_>| constant_full_int(cx, cx.typeck_results(), e) == Some(FullInt::S(0));
+    let ExprKind::Binary(op, lhs, rhs) = peel_blocks(cond).kind else {
+        return None;
+    };
+    match normalize_comparison(op.node, lhs, rhs)? {
+        (Rel::Lt, zero, value) if is_zero(zero) => Some((Sign::Positive, value)),
+        (Rel::Lt, value, zero) if is_zero(zero) => Some((Sign::Negative, value)),
+        (Rel::Eq, zero, value) | (Rel::Eq, value, zero) if is_zero(zero) => Some((Sign::Zero, value)),
+        _ => None,
+    }
+}
@@ -0,0 +1,118 @@
+use clippy_utils::comparisons::{pattern_equalities, PatternEquality};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::source::snippet;
+use clippy_utils::ty::is_copy;
+use clippy_utils::{if_sequence, is_else_clause};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `if` chains where the condition of every branch compares the same two or more
+    /// values with literals or unit variants, like a state machine that checks the current state
+    /// and event in each branch.
+    ///
+    /// Only chains with at least `manual-tuple-match-threshold` such branches are linted. Chains
+    /// with any other condition are not linted at all.
+    ///
+    /// ### Why is this bad?
+    /// A `match` on a tuple of the values lays out every combination that is handled as one arm,
+    /// which is easier to read than repeating the values in each condition.
+    ///
+    /// ### Known problems
+    /// `==` uses the `PartialEq` impl of the type, while a `match` compares variants structurally.
+    /// These only differ if the type implements `PartialEq` by hand.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #[derive(PartialEq)] enum State { Idle, Running, Done }
+    /// # #[derive(PartialEq)] enum Event { Start, Stop }
+    /// # let (mut state, event) = (State::Idle, Event::Start);
+    /// if state == State::Idle && event == Event::Start {
+    ///     state = State::Running;
+    /// } else if state == State::Running && event == Event::Stop {
+    ///     state = State::Done;
+    /// } else if state == State::Done && event == Event::Start {
+    ///     state = State::Running;
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #[derive(PartialEq)] enum State { Idle, Running, Done }
+    /// # #[derive(PartialEq)] enum Event { Start, Stop }
+    /// # let (mut state, event) = (State::Idle, Event::Start);
+    /// match (&state, &event) {
+    ///     (State::Idle, Event::Start) => state = State::Running,
+    ///     (State::Running, Event::Stop) => state = State::Done,
+    ///     (State::Done, Event::Start) => state = State::Running,
+    ///     _ => {},
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_TUPLE_MATCH,
+    pedantic,
+    "`if` chains comparing the same values in every branch, instead of a `match` on a tuple"
+}
+
+impl_lint_pass!(ManualTupleMatch => [MANUAL_TUPLE_MATCH]);
+
+pub struct ManualTupleMatch {
+    pub manual_tuple_match_threshold: u64,
+}
+
+impl<'tcx> LateLintPass<'tcx> for ManualTupleMatch {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if !matches!(expr.kind, ExprKind::If(..)) || expr.span.from_expansion() || is_else_clause(cx.tcx, expr) {
+            return;
+        }
+        let (conds, _) = if_sequence(expr);
+        if (conds.len() as u64) < self.manual_tuple_match_threshold {
+            return;
+        }
+        let Some(branches) = conds
+            .iter()
+            .map(|cond| pattern_equalities(cx, cond))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        let [first, rest @ ..] = &branches[..] else {
+            return;
+        };
+        if first.len() < 2
+            || !compares_each_once(first, first)
+            || !rest.iter().all(|branch| compares_each_once(first, branch))
+        {
+            return;
+        }
+        let values = first
+            .iter()
+            .map(|eq| {
+                let value = snippet(cx, eq.value.span, "..");
+                if is_copy(cx, cx.typeck_results().expr_ty(eq.value)) {
+                    value.into_owned()
+                } else {
+                    format!("&{value}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        span_lint_and_help(
+            cx,
+            MANUAL_TUPLE_MATCH,
+            expr.span,
+            "this `if` chain compares the same values in every branch",
+            None,
+            &format!("consider using `match ({values})` with an arm for each branch"),
+        );
+    }
+}
+
+/// Checks if `branch` compares each place that `first` compares exactly once, and nothing else.
+fn compares_each_once(first: &[PatternEquality<'_>], branch: &[PatternEquality<'_>]) -> bool {
+    first.len() == branch.len()
+        && first
+            .iter()
+            .all(|eq| branch.iter().filter(|other| other.place == eq.place).count() == 1)
+}
@@ -0,0 +1,111 @@
+use clippy_utils::comparisons::two_sided_bounds;
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::visitors::is_local_used;
+use clippy_utils::{path_to_local_id, sext};
+use rustc_errors::Applicability;
+use rustc_hir::{Arm, BindingAnnotation, Expr, ExprKind, Guard, PatKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+
+use super::MANUAL_RANGE_GUARD;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, scrutinee: &'tcx Expr<'_>, arms: &'tcx [Arm<'tcx>]) {
+    let ty = cx.typeck_results().expr_ty(scrutinee);
+    if !(ty.is_integral() || ty.is_char()) {
+        return;
+    }
+    for arm in arms {
+        // `n if lo <= n && n <= hi`, where `n` binds the scrutinee itself.
+        if let Some(Guard::If(guard)) = arm.guard
+            && !guard.span.from_expansion()
+            && let PatKind::Binding(BindingAnnotation::NONE, binding_id, ident, None) = arm.pat.kind
+            && let ExprKind::Binary(op, lhs, rhs) = guard.kind
+            && let Some(bounds) = two_sided_bounds(cx, op.node, lhs, rhs)
+            && path_to_local_id(bounds.value, binding_id)
+            && let Some(lower) = inclusive_bound(cx, ty, bounds.lower, bounds.lower_inclusive, 1)
+            && let Some(upper) = inclusive_bound(cx, ty, bounds.upper, bounds.upper_inclusive, -1)
+            && lower.value <= upper.value
+        {
+            let mut app = Applicability::MachineApplicable;
+            let lower = lower.render(cx, ty, &mut app);
+            let upper = upper.render(cx, ty, &mut app);
+            let pat = if is_local_used(cx, arm.body, binding_id) {
+                format!("{ident} @ {lower}..={upper}")
+            } else {
+                format!("{lower}..={upper}")
+            };
+            span_lint_and_sugg(
+                cx,
+                MANUAL_RANGE_GUARD,
+                arm.pat.span.to(guard.span),
+                "this match guard can be written as a range pattern",
+                "use a range pattern",
+                pat,
+                app,
+            );
+        }
+    }
+}
+
+/// A literal bound of a range, after making it inclusive.
+struct Bound<'tcx> {
+    /// The literal the bound was written as, if it was already inclusive.
+    expr: Option<&'tcx Expr<'tcx>>,
+    /// The inclusive value of the bound. Chars are stored as their code point.
+    value: i128,
+}
+
+impl Bound<'_> {
+    fn render(&self, cx: &LateContext<'_>, ty: Ty<'_>, app: &mut Applicability) -> String {
+        if let Some(expr) = self.expr {
+            snippet_with_applicability(cx, expr.span, "..", app).into_owned()
+        } else if ty.is_char() {
+            // The value was checked to be a valid char in `inclusive_bound`.
+            let c = u32::try_from(self.value)
+                .ok()
+                .and_then(char::from_u32)
+                .unwrap_or_default();
+            format!("{c:?}")
+        } else {
+            self.value.to_string()
+        }
+    }
+}
+
+/// Returns the inclusive bound of a range for the literal `expr`, which is moved by `step` if the
+/// comparison was exclusive.
+fn inclusive_bound<'tcx>(
+    cx: &LateContext<'tcx>,
+    ty: Ty<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    inclusive: bool,
+    step: i128,
+) -> Option<Bound<'tcx>> {
+    let lit = match expr.kind {
+        ExprKind::Unary(UnOp::Neg, inner) => inner,
+        _ => expr,
+    };
+    if !matches!(lit.kind, ExprKind::Lit(_)) {
+        return None;
+    }
+    let value = match (constant(cx, cx.typeck_results(), expr)?, ty.kind()) {
+        (Constant::Int(n), ty::Int(ity)) => sext(cx.tcx, n, *ity),
+        (Constant::Int(n), ty::Uint(_)) => i128::try_from(n).ok()?,
+        (Constant::Char(c), ty::Char) => i128::from(u32::from(c)),
+        _ => return None,
+    };
+    if inclusive {
+        return Some(Bound {
+            expr: Some(expr),
+            value,
+        });
+    }
+    let value = value.checked_add(step)?;
+    // Moving past a char boundary can end up in the surrogate range or past `char::MAX`.
+    if ty.is_char() && u32::try_from(value).ok().and_then(char::from_u32).is_none() {
+        return None;
+    }
+    Some(Bound { expr: None, value })
+}
@@ -2,6 +2,7 @@
 mod infallible_destructuring_match;
 mod manual_filter;
 mod manual_map;
+mod manual_range_guard;
 mod manual_unwrap_or;
 mod manual_utils;
 mod match_as_ref;
@@ -967,6 +968,37 @@
     "checks for unnecessary guards in match expressions"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for match arms on an integer or `char` that bind the scrutinee and only have a
+    /// guard comparing it with two literal bounds, like `n if n >= 0 && n <= 9`.
+    ///
+    /// ### Why is this bad?
+    /// The guard is a range pattern in disguise. Range patterns are shorter and are taken into
+    /// account by the exhaustiveness check of the match.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let n = 5;
+    /// match n {
+    ///     n if n >= 0 && n < 10 => println!("digit {n}"),
+    ///     _ => {},
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let n = 5;
+    /// match n {
+    ///     n @ 0..=9 => println!("digit {n}"),
+    ///     _ => {},
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_RANGE_GUARD,
+    style,
+    "match guards that compare the scrutinee with two literal bounds"
+}
+
 pub struct Matches {
     msrv: Msrv,
     infallible_destructuring_match_linted: bool,
@@ -1009,6 +1041,7 @@ pub fn new(msrv: Msrv) -> Self {
     MANUAL_MAP,
     MANUAL_FILTER,
     REDUNDANT_GUARDS,
+    MANUAL_RANGE_GUARD,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Matches {
@@ -1057,6 +1090,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     match_on_vec_items::check(cx, ex);
                     match_str_case_mismatch::check(cx, ex, arms);
                     redundant_guards::check(cx, arms);
+                    manual_range_guard::check(cx, ex, arms);
 
                     if !in_constant(cx, expr.hir_id) {
                         manual_unwrap_or::check(cx, expr, ex, arms);
@@ -0,0 +1,55 @@
+use clippy_utils::comparisons::param_equality;
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::is_trait_method;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::Sugg;
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind, Mutability};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::sym;
+
+use super::MANUAL_CONTAINS;
+
+/// Checks `slice.iter().any(|x| *x == target)` and `slice.iter().all(|x| *x != target)`, where
+/// `recv` is `slice` and `method` is `any` or `all`.
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    method: &str,
+    recv: &'tcx Expr<'_>,
+    arg: &'tcx Expr<'_>,
+) {
+    if is_trait_method(cx, expr, sym::Iterator)
+        && let ty::Ref(_, slice_ty, _) = cx.typeck_results().expr_ty_adjusted(recv).kind()
+        && let ty::Slice(elem_ty) = slice_ty.kind()
+        && let ExprKind::Closure(closure) = arg.kind
+        && let Some(eq) = param_equality(cx, cx.tcx.hir().body(closure.body))
+        && eq.is_eq == (method == "any")
+    {
+        let mut app = Applicability::MachineApplicable;
+        // `contains` takes a reference to the element type, so bail if `==` compares the element
+        // with some other type.
+        let other_ty = cx.typeck_results().expr_ty(eq.other);
+        let needle = if other_ty == *elem_ty {
+            Sugg::hir_with_applicability(cx, eq.other, "..", &mut app).addr()
+        } else if let ty::Ref(_, inner, Mutability::Not) = other_ty.kind()
+            && inner == elem_ty
+        {
+            Sugg::hir_with_applicability(cx, eq.other, "..", &mut app)
+        } else {
+            return;
+        };
+        let recv = snippet_with_applicability(cx, recv.span, "..", &mut app);
+        let negation = if eq.is_eq { "" } else { "!" };
+        span_lint_and_sugg(
+            cx,
+            MANUAL_CONTAINS,
+            expr.span,
+            &format!("using `{method}` to check whether a slice contains a value"),
+            "use `contains` instead",
+            format!("{negation}{recv}.contains({needle})"),
+            app,
+        );
+    }
+}
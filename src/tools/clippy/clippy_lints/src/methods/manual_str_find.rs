@@ -0,0 +1,47 @@
+use clippy_utils::comparisons::{search_equality, SearchMethod};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::method_chain_args;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::is_type_lang_item;
+use rustc_ast::LitKind;
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind, LangItem};
+use rustc_lint::LateContext;
+
+use super::MANUAL_STR_FIND;
+
+/// Checks `s.bytes().position(|b| b == b'a')` and `s.as_bytes().iter().position(|b| *b == b'a')`.
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    if let Some(search) = search_equality(cx, expr)
+        && search.method == SearchMethod::Position
+        && let Some(str_recv) = bytes_of_str(cx, search.recv)
+        // `str::find` returns the byte index of the char, which is only the index of the byte for
+        // ASCII.
+        && let ExprKind::Lit(lit) = search.eq.other.kind
+        && let LitKind::Byte(byte) = lit.node
+        && byte.is_ascii()
+    {
+        let mut app = Applicability::MachineApplicable;
+        let str_recv = snippet_with_applicability(cx, str_recv.span, "..", &mut app);
+        span_lint_and_sugg(
+            cx,
+            MANUAL_STR_FIND,
+            expr.span,
+            "searching the bytes of a string for an ASCII character",
+            "use `find` instead",
+            format!("{str_recv}.find({:?})", char::from(byte)),
+            app,
+        );
+    }
+}
+
+/// Returns `s` if `expr` is `s.bytes()` or `s.as_bytes().iter()` for a `str` or `String` `s`.
+fn bytes_of_str<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+    let str_recv = if let Some(args) = method_chain_args(expr, &["bytes"]) {
+        args[0].0
+    } else {
+        method_chain_args(expr, &["as_bytes", "iter"])?[0].0
+    };
+    let ty = cx.typeck_results().expr_ty_adjusted(str_recv).peel_refs();
+    (ty.is_str() || is_type_lang_item(cx, ty, LangItem::String)).then_some(str_recv)
+}
@@ -49,9 +49,11 @@
 mod iter_skip_zero;
 mod iter_with_drain;
 mod iterator_step_by_zero;
+mod manual_contains;
 mod manual_next_back;
 mod manual_ok_or;
 mod manual_saturating_arithmetic;
+mod manual_str_find;
 mod manual_str_repeat;
 mod manual_try_fold;
 mod map_clone;
@@ -108,6 +110,7 @@
 mod unnecessary_literal_unwrap;
 mod unnecessary_sort_by;
 mod unnecessary_to_owned;
+mod unstable_sort_by_field_subset;
 mod unwrap_expect_used;
 mod useless_asref;
 mod utils;
@@ -3682,6 +3685,101 @@
     "calling the `try_from` and `try_into` trait methods when `From`/`Into` is implemented"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `slice.iter().any(|x| *x == target)` and `slice.iter().all(|x| *x != target)`.
+    ///
+    /// ### Why is this bad?
+    /// `slice.contains(&target)` says the same more concisely, and may be faster for some element
+    /// types.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// let values = [1, 2, 3];
+    /// let target = 2;
+    /// let found = values.iter().any(|x| *x == target);
+    /// let missing = values.iter().all(|x| *x != target);
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// let values = [1, 2, 3];
+    /// let target = 2;
+    /// let found = values.contains(&target);
+    /// let missing = !values.contains(&target);
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_CONTAINS,
+    pedantic,
+    "using `iter().any(..)` or `iter().all(..)` to check whether a slice contains a value"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `sort_unstable_by` calls whose comparator only compares some of the fields of
+    /// the sorted struct, like `v.sort_unstable_by(|a, b| a.x.cmp(&b.x))` for a struct with the
+    /// fields `x` and `y`.
+    ///
+    /// Only comparators that compare fields of their parameters with each other, and use the
+    /// parameters in no other way, are checked.
+    ///
+    /// ### Why is this bad?
+    /// Elements that are equal in the compared fields may end up in any order. If the other
+    /// fields were meant to break ties, the result changes from run to run of the algorithm in a
+    /// way that is easy to miss.
+    ///
+    /// ### Known problems
+    /// Ignoring some fields is often intended, which is why this lint is pedantic.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    /// # let mut points: Vec<Point> = vec![];
+    /// points.sort_unstable_by(|a, b| a.x.cmp(&b.x));
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # struct Point {
+    /// #     x: i32,
+    /// #     y: i32,
+    /// # }
+    /// # let mut points: Vec<Point> = vec![];
+    /// points.sort_unstable_by(|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y)));
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub UNSTABLE_SORT_BY_FIELD_SUBSET,
+    pedantic,
+    "an unstable sort whose comparator only compares some fields of the elements"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `s.bytes().position(|b| b == b'a')` and
+    /// `s.as_bytes().iter().position(|b| *b == b'a')` on a string `s`, where the searched byte is
+    /// an ASCII character.
+    ///
+    /// ### Why is this bad?
+    /// `s.find('a')` says the same more directly, and uses an optimized search. For ASCII
+    /// characters, the byte index it returns is the index of the byte.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// let line = "key=value";
+    /// let split = line.bytes().position(|b| b == b'=');
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// let line = "key=value";
+    /// let split = line.find('=');
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_STR_FIND,
+    pedantic,
+    "searching the bytes of a string for an ASCII character with `position`"
+}
+
 pub struct Methods {
     avoid_breaking_exported_api: bool,
     msrv: Msrv,
@@ -3831,6 +3929,9 @@ pub fn new(
     REDUNDANT_AS_STR,
     WAKER_CLONE_WAKE,
     UNNECESSARY_FALLIBLE_CONVERSIONS,
+    MANUAL_CONTAINS,
+    UNSTABLE_SORT_BY_FIELD_SUBSET,
+    MANUAL_STR_FIND,
 ]);
 
 /// Extracts a method call name, args, and `Span` of the method name.
@@ -4027,17 +4128,17 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                 ("add" | "offset" | "sub" | "wrapping_offset" | "wrapping_add" | "wrapping_sub", [_arg]) => {
                     zst_offset::check(cx, expr, recv);
                 },
-                ("all", [arg]) => {
-                    if let Some(("cloned", recv2, [], _, _)) = method_call(recv) {
-                        iter_overeager_cloned::check(
-                            cx,
-                            expr,
-                            recv,
-                            recv2,
-                            iter_overeager_cloned::Op::NeedlessMove(name, arg),
-                            false,
-                        );
-                    }
+                ("all", [arg]) => match method_call(recv) {
+                    Some(("cloned", recv2, [], _, _)) => iter_overeager_cloned::check(
+                        cx,
+                        expr,
+                        recv,
+                        recv2,
+                        iter_overeager_cloned::Op::NeedlessMove(name, arg),
+                        false,
+                    ),
+                    Some(("iter", recv, [], _, _)) => manual_contains::check(cx, expr, name, recv, arg),
+                    _ => {},
                 },
                 ("and_then", [arg]) => {
                     let biom_option_linted = bind_instead_of_map::OptionAndThenSome::check(cx, expr, recv, arg);
@@ -4062,6 +4163,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                     {
                         string_lit_chars_any::check(cx, expr, recv, param, peel_blocks(body.value), &self.msrv);
                     },
+                    Some(("iter", recv, [], _, _)) => manual_contains::check(cx, expr, name, recv, arg),
                     _ => {},
                 },
                 ("arg", [arg]) => {
@@ -4333,6 +4435,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                         unnecessary_lazy_eval::check(cx, expr, recv, arg, "or");
                     }
                 },
+                ("position", [_]) => manual_str_find::check(cx, expr),
                 ("push", [arg]) => {
                     path_buf_push_overwrite::check(cx, expr, arg);
                 },
@@ -4393,6 +4496,7 @@ fn check_methods<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
                 },
                 ("sort_unstable_by", [arg]) => {
                     unnecessary_sort_by::check(cx, expr, recv, arg, true);
+                    unstable_sort_by_field_subset::check(cx, expr, arg);
                 },
                 ("splitn" | "rsplitn", [count_arg, pat_arg]) => {
                     if let Some(Constant::Int(count)) = constant(cx, cx.typeck_results(), count_arg) {
@@ -0,0 +1,41 @@
+use clippy_utils::comparisons::comparator_fields;
+use clippy_utils::diagnostics::span_lint_and_note;
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::UNSTABLE_SORT_BY_FIELD_SUBSET;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, arg: &'tcx Expr<'_>) {
+    if let ExprKind::Closure(closure) = arg.kind
+        && let body = cx.tcx.hir().body(closure.body)
+        && let Some(compared) = comparator_fields(body)
+        && let [param, _] = body.params
+        && let ty::Adt(adt, _) = cx.typeck_results().pat_ty(param.pat).peel_refs().kind()
+        && adt.is_struct()
+    {
+        // Fields that cannot be accessed here cannot be compared either.
+        let module = cx.tcx.parent_module(expr.hir_id);
+        let ignored: Vec<_> = adt
+            .all_fields()
+            .filter(|field| field.vis.is_accessible_from(module, cx.tcx) && !compared.contains(&field.name))
+            .map(|field| format!("`{}`", field.name))
+            .collect();
+        if !ignored.is_empty() {
+            span_lint_and_note(
+                cx,
+                UNSTABLE_SORT_BY_FIELD_SUBSET,
+                expr.span,
+                &format!(
+                    "this unstable sort only compares some fields of `{}`",
+                    cx.tcx.item_name(adt.did())
+                ),
+                None,
+                &format!(
+                    "elements that only differ in {} end up in an unspecified order",
+                    ignored.join(", ")
+                ),
+            );
+        }
+    }
+}
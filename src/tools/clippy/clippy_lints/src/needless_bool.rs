@@ -60,6 +60,9 @@
     /// `x != true` and order comparisons such as `x < true` (or vice versa) and
     /// suggest using the variable directly.
     ///
+    /// If `x` is itself a comparison like `a < b`, `x == false` is written as `a >= b` if `a` and
+    /// `b` are integers, and as `!(a < b)` otherwise, since e.g. floats are not totally ordered.
+    ///
     /// ### Why is this bad?
     /// Unnecessary code.
     ///
@@ -400,7 +403,12 @@ fn suggest_bool_comparison<'a, 'tcx>(
     message: &str,
     conv_hint: impl FnOnce(Sugg<'a>) -> Sugg<'a>,
 ) {
-    let hint = Sugg::hir_with_context(cx, expr, e.span.ctxt(), "..", &mut app);
+    let mut hint = Sugg::hir_with_context(cx, expr, e.span.ctxt(), "..", &mut app);
+    // Negating `a < b` gives `a >= b` only if the values are totally ordered, which e.g. floats
+    // are not, so such comparisons are negated with `!(a < b)` instead.
+    if is_partial_order_comparison(cx, expr) {
+        hint = Sugg::MaybeParen(hint.to_string().into());
+    }
     span_lint_and_sugg(
         cx,
         BOOL_COMPARISON,
@@ -412,6 +420,19 @@ fn suggest_bool_comparison<'a, 'tcx>(
     );
 }
 
+/// Checks if `expr` is an ordering comparison of values that are not known to be totally
+/// ordered.
+fn is_partial_order_comparison(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let ExprKind::Binary(op, lhs, _) = expr.kind
+        && matches!(op.node, BinOpKind::Lt | BinOpKind::Le | BinOpKind::Gt | BinOpKind::Ge)
+    {
+        let ty = cx.typeck_results().expr_ty(lhs).peel_refs();
+        !(ty.is_integral() || ty.is_bool() || ty.is_char())
+    } else {
+        false
+    }
+}
+
 enum Expression {
     Bool(bool),
     RetBool(bool),
@@ -0,0 +1,82 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::eq_expr_value;
+use clippy_utils::source::snippet;
+use rustc_ast::LitKind;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::ABSURD_FLOAT_SIGN_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    // Only `non_negative < bound` and `non_negative <= bound` can be always false. The opposite
+    // direction is not always true, since it is false for NaN.
+    if let Some((rel @ (Rel::Lt | Rel::Le), non_negative, bound)) = normalize_comparison(op, lhs, rhs)
+        && cx.typeck_results().expr_ty(non_negative).is_floating_point()
+        && is_non_negative(cx, non_negative)
+        && let Some(bound_value) = float_constant(cx, bound)
+        && (bound_value < 0.0 || (rel == Rel::Lt && bound_value == 0.0))
+    {
+        span_lint_and_help(
+            cx,
+            ABSURD_FLOAT_SIGN_COMPARISONS,
+            expr.span,
+            "this comparison is always false",
+            None,
+            &format!(
+                "`{}` is never negative, so it can't be less than `{}`",
+                snippet(cx, non_negative.span, "x"),
+                snippet(cx, bound.span, "bound")
+            ),
+        );
+    }
+}
+
+fn float_constant<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> Option<f64> {
+    match constant(cx, cx.typeck_results(), expr)? {
+        Constant::F32(value) => Some(value.into()),
+        Constant::F64(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Checks if `expr` is known to never evaluate to a negative float. It may still evaluate to NaN.
+///
+/// This only recognizes the inherent float methods, not user methods with the same name.
+fn is_non_negative(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::MethodCall(path, _, args, _) => {
+            if let Some(method_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
+                && let Some(impl_id) = cx.tcx.impl_of_method(method_id)
+                && cx.tcx.type_of(impl_id).instantiate_identity().is_floating_point()
+            {
+                match (path.ident.as_str(), args) {
+                    ("abs" | "sqrt" | "exp" | "exp2", []) | ("hypot", [_]) => true,
+                    ("powi", [exponent]) => is_even_literal(exponent),
+                    _ => false,
+                }
+            } else {
+                false
+            }
+        },
+        ExprKind::Binary(op, left, right) => op.node == BinOpKind::Mul && eq_expr_value(cx, left, right),
+        _ => false,
+    }
+}
+
+fn is_even_literal(expr: &Expr<'_>) -> bool {
+    if let ExprKind::Lit(lit) = expr.kind
+        && let LitKind::Int(n, _) = lit.node
+    {
+        n % 2 == 0
+    } else {
+        false
+    }
+}
@@ -0,0 +1,85 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::int_bits;
+use clippy_utils::source::snippet;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty, UintTy};
+
+use super::ASYMMETRIC_CAST_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((_, left, right)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    let (cast, other) = match (left.kind, right.kind) {
+        (ExprKind::Cast(..), ExprKind::Cast(..)) => return,
+        (ExprKind::Cast(..), _) => (left, right),
+        (_, ExprKind::Cast(..)) => (right, left),
+        _ => return,
+    };
+    let ExprKind::Cast(inner, _) = cast.kind else {
+        return;
+    };
+    let typeck = cx.typeck_results();
+    // Comparing a cast with a constant is usually deliberate, e.g. `x as u8 == b'a'`, and the
+    // value of a cast constant is known.
+    if constant(cx, typeck, other).is_some() || constant(cx, typeck, inner).is_some() {
+        return;
+    }
+    if let Some(from) = int_range(cx, typeck.expr_ty(inner))
+        && let Some(to) = int_range(cx, typeck.expr_ty(cast))
+        && (from.min < to.min || from.max > to.max)
+    {
+        span_lint_and_note(
+            cx,
+            ASYMMETRIC_CAST_COMPARISONS,
+            expr.span,
+            "only one side of this comparison is cast, which can change its result",
+            None,
+            &format!(
+                "values of `{}` outside of `{}..={}` change when cast to `{}`",
+                snippet(cx, inner.span, ".."),
+                from.min.max(to.min),
+                from.max.min(to.max),
+                typeck.expr_ty(cast),
+            ),
+        );
+    }
+}
+
+/// The values an integer type can represent.
+pub(super) struct IntRange {
+    pub(super) min: i128,
+    pub(super) max: u128,
+}
+
+pub(super) fn int_range(cx: &LateContext<'_>, ty: Ty<'_>) -> Option<IntRange> {
+    match *ty.kind() {
+        ty::Int(ity) => {
+            let bits = int_bits(cx.tcx, ity);
+            Some(IntRange {
+                min: i128::MIN >> (128 - bits),
+                max: u128::MAX >> (129 - bits),
+            })
+        },
+        ty::Uint(uty) => {
+            let bits = match uty {
+                UintTy::Usize => cx.tcx.data_layout.pointer_size.bits(),
+                _ => uty.bit_width()?,
+            };
+            Some(IntRange {
+                min: 0,
+                max: u128::MAX >> (128 - bits),
+            })
+        },
+        _ => None,
+    }
+}
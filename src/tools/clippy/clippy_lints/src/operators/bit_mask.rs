@@ -1,5 +1,5 @@
 use clippy_utils::consts::{constant, Constant};
-use clippy_utils::diagnostics::span_lint;
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
 use rustc_hir::{BinOpKind, Expr, ExprKind};
 use rustc_lint::LateContext;
 use rustc_span::Span;
@@ -59,13 +59,19 @@ fn check_bit_mask(
         BinOpKind::Eq | BinOpKind::Ne => match bit_op {
             BinOpKind::BitAnd => {
                 if mask_value & cmp_value != cmp_value {
-                    if cmp_value != 0 {
-                        span_lint(
+                    let msg = format!("incompatible bit mask: `_ & {mask_value}` can never be equal to `{cmp_value}`");
+                    if mask_value == 1 {
+                        // `_ & 1` is a parity check, so point out which values make sense
+                        span_lint_and_help(
                             cx,
                             BAD_BIT_MASK,
                             span,
-                            &format!("incompatible bit mask: `_ & {mask_value}` can never be equal to `{cmp_value}`"),
+                            &msg,
+                            None,
+                            "`_ & 1` is `0` for even and `1` for odd values",
                         );
+                    } else if cmp_value != 0 {
+                        span_lint(cx, BAD_BIT_MASK, span, &msg);
                     }
                 } else if mask_value == 0 {
                     span_lint(cx, BAD_BIT_MASK, span, "&-masking with zero");
@@ -0,0 +1,74 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::BITWISE_JOINED_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let (op_str, lazy_op, skipped_if) = match op {
+        BinOpKind::BitAnd => ("&", "&&", "false"),
+        BinOpKind::BitOr => ("|", "||", "true"),
+        _ => return,
+    };
+    if let Some(lhs_cmp) = comparison(lhs)
+        && let Some(rhs_cmp) = comparison(rhs)
+        && cx.typeck_results().expr_ty(lhs).is_bool()
+        && cx.typeck_results().expr_ty(rhs).is_bool()
+        && lhs.span.eq_ctxt(expr.span)
+        && rhs.span.eq_ctxt(expr.span)
+    {
+        let has_side_effects = rhs_cmp.1.can_have_side_effects() || rhs_cmp.2.can_have_side_effects();
+        span_lint_and_then(
+            cx,
+            BITWISE_JOINED_COMPARISONS,
+            expr.span,
+            &format!("comparisons joined with `{op_str}` instead of `{lazy_op}`"),
+            |diag| {
+                let mut app = if has_side_effects {
+                    Applicability::MaybeIncorrect
+                } else {
+                    Applicability::MachineApplicable
+                };
+                // Rebuild the comparisons from their operands, to drop the parentheses around them.
+                let mut cmp_snippet = |(op, lhs, rhs): (BinOpKind, &Expr<'_>, &Expr<'_>)| {
+                    let lhs = snippet_with_applicability(cx, lhs.span, "..", &mut app);
+                    let rhs = snippet_with_applicability(cx, rhs.span, "..", &mut app);
+                    format!("{lhs} {} {rhs}", op.as_str())
+                };
+                let lhs = cmp_snippet(lhs_cmp);
+                let rhs = cmp_snippet(rhs_cmp);
+                diag.span_suggestion(
+                    expr.span,
+                    format!("use `{lazy_op}` to skip the second comparison when it does not matter"),
+                    format!("{lhs} {lazy_op} {rhs}"),
+                    app,
+                );
+                if has_side_effects {
+                    diag.note(format!(
+                        "the second comparison has side effects, which then do not happen when the first one is `{skipped_if}`"
+                    ));
+                }
+            },
+        );
+    }
+}
+
+/// Returns the operator and the operands of `expr` if it is a comparison.
+fn comparison<'a>(expr: &'a Expr<'a>) -> Option<(BinOpKind, &'a Expr<'a>, &'a Expr<'a>)> {
+    if let ExprKind::Binary(op, lhs, rhs) = expr.kind
+        && normalize_comparison(op.node, lhs, rhs).is_some()
+    {
+        Some((op.node, lhs, rhs))
+    } else {
+        None
+    }
+}
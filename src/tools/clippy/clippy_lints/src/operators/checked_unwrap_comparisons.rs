@@ -0,0 +1,63 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+use super::CHECKED_UNWRAP_COMPARISONS;
+
+/// The `checked_*` methods of integers that have both a `saturating_*` and a `wrapping_*`
+/// counterpart.
+const SATURATING_AND_WRAPPING: &[&str] = &["add", "sub", "mul", "pow"];
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((Rel::Lt | Rel::Le, left, right)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    for operand in [left, right] {
+        if let Some((checked, unwrap)) = checked_then_unwrapped(cx, operand)
+            && operand.span.eq_ctxt(expr.span)
+        {
+            let arith = checked.as_str().trim_start_matches("checked_");
+            span_lint_and_then(
+                cx,
+                CHECKED_UNWRAP_COMPARISONS,
+                operand.span,
+                &format!("comparing the unwrapped result of `{checked}`"),
+                |diag| {
+                    diag.note(format!(
+                        "`{unwrap}` reintroduces the panic that `{checked}` is meant to avoid"
+                    ));
+                    if SATURATING_AND_WRAPPING.contains(&arith) {
+                        diag.help(format!(
+                            "handle the `None` case, or use `saturating_{arith}` or `wrapping_{arith}` if that is what is meant"
+                        ));
+                    } else {
+                        diag.help("handle the `None` case");
+                    }
+                },
+            );
+        }
+    }
+}
+
+/// Checks if `expr` is `x.checked_*(..).unwrap()` or `x.checked_*(..).expect(..)` on an integer
+/// `x`, and returns the names of the `checked_*` method and of the unwrapping method.
+fn checked_then_unwrapped(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<(Symbol, Symbol)> {
+    if let ExprKind::MethodCall(unwrap, checked_call, unwrap_args, _) = expr.kind
+        && matches!((unwrap.ident.as_str(), unwrap_args), ("unwrap", []) | ("expect", [_]))
+        && let ExprKind::MethodCall(checked, value, _, _) = checked_call.kind
+        && checked.ident.as_str().starts_with("checked_")
+        && cx.typeck_results().expr_ty(value).peel_refs().is_integral()
+    {
+        Some((checked.ident.name, unwrap.ident.name))
+    } else {
+        None
+    }
+}
@@ -1,22 +1,78 @@
-use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::path_def_id;
-use clippy_utils::source::snippet;
+use clippy_utils::source::{snippet, snippet_with_applicability};
 use clippy_utils::ty::{implements_trait, is_copy};
 use rustc_errors::Applicability;
 use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
 use rustc_lint::LateContext;
 use rustc_middle::ty::Ty;
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 
 use super::CMP_OWNED;
 
 pub(super) fn check(cx: &LateContext<'_>, op: BinOpKind, lhs: &Expr<'_>, rhs: &Expr<'_>) {
     if op.is_comparison() {
+        if check_both(cx, op, lhs, rhs) {
+            return;
+        }
         check_op(cx, lhs, rhs, true);
         check_op(cx, rhs, lhs, false);
     }
 }
 
+/// Returns the receiver and the trait of the method if `expr` is a `to_string` or `to_owned` call.
+fn to_string_or_owned<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<(&'a Expr<'a>, Symbol)> {
+    if let ExprKind::MethodCall(_, arg, [], _) = expr.kind
+        && let Some(method_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
+        && let Some(trait_id) = cx.tcx.trait_of_item(method_id)
+        && let Some(name @ (sym::ToString | sym::ToOwned)) = cx.tcx.get_diagnostic_name(trait_id)
+    {
+        Some((arg, name))
+    } else {
+        None
+    }
+}
+
+/// Checks for `a.to_string() == b.to_string()` and the like, where `a == b` compiles. Returns
+/// whether it linted.
+fn check_both(cx: &LateContext<'_>, op: BinOpKind, lhs: &Expr<'_>, rhs: &Expr<'_>) -> bool {
+    if let Some((Rel::Eq | Rel::Ne, _, _)) = normalize_comparison(op, lhs, rhs)
+        && let Some((left, conversion)) = to_string_or_owned(cx, lhs)
+        && let Some((right, rhs_conversion)) = to_string_or_owned(cx, rhs)
+        && conversion == rhs_conversion
+        && let Some(eq_trait) = cx.tcx.lang_items().eq_trait()
+        && implements_trait(
+            cx,
+            cx.typeck_results().expr_ty(left),
+            eq_trait,
+            &[cx.typeck_results().expr_ty(right).into()],
+        )
+    {
+        // Values that are equal can have different string representations and vice versa, like
+        // `0.0` and `-0.0`.
+        let mut app = if conversion == sym::ToString {
+            Applicability::MaybeIncorrect
+        } else {
+            Applicability::MachineApplicable
+        };
+        let left = snippet_with_applicability(cx, left.span, "..", &mut app);
+        let right = snippet_with_applicability(cx, right.span, "..", &mut app);
+        span_lint_and_sugg(
+            cx,
+            CMP_OWNED,
+            lhs.span.to(rhs.span),
+            "this creates owned instances just for comparison",
+            "compare the values directly",
+            format!("{left} {} {right}", op.as_str()),
+            app,
+        );
+        true
+    } else {
+        false
+    }
+}
+
 #[derive(Default)]
 struct EqImpl {
     ty_eq_other: bool,
@@ -42,8 +98,11 @@ fn check_op(cx: &LateContext<'_>, expr: &Expr<'_>, other: &Expr<'_>, left: bool)
             if typeck
                 .type_dependent_def_id(expr.hir_id)
                 .and_then(|id| cx.tcx.trait_of_item(id))
-                .map_or(false, |id| {
-                    matches!(cx.tcx.get_diagnostic_name(id), Some(sym::ToString | sym::ToOwned))
+                .map_or(false, |id| match cx.tcx.get_diagnostic_name(id) {
+                    Some(sym::ToString | sym::ToOwned) => true,
+                    // Cloning `Copy` types is cheap, and linted by `clone_on_copy` anyway.
+                    Some(sym::Clone) => !is_copy(cx, typeck.expr_ty(expr)),
+                    _ => false,
                 }) =>
         {
             (arg, arg.span)
@@ -0,0 +1,82 @@
+use clippy_config::types::ComparisonConstantSide;
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::visitors::for_each_expr;
+use core::ops::ControlFlow;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::COMPARISON_CONSTANT_SIDE;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+    side: ComparisonConstantSide,
+) {
+    if normalize_comparison(op, lhs, rhs).is_none() {
+        return;
+    }
+    let typeck = cx.typeck_results();
+    let (misplaced, other, msg) = match side {
+        ComparisonConstantSide::Left => (rhs, lhs, "constant on the right side of a comparison"),
+        ComparisonConstantSide::Right => (lhs, rhs, "constant on the left side of a comparison"),
+    };
+    // Swapping operands of different types would call a different `PartialEq` or `PartialOrd` impl,
+    // which might not exist.
+    if constant(cx, typeck, misplaced).is_none()
+        || constant(cx, typeck, other).is_some()
+        || typeck.expr_ty(lhs) != typeck.expr_ty(rhs)
+    {
+        return;
+    }
+    let op = match op {
+        BinOpKind::Lt => BinOpKind::Gt,
+        BinOpKind::Le => BinOpKind::Ge,
+        BinOpKind::Gt => BinOpKind::Lt,
+        BinOpKind::Ge => BinOpKind::Le,
+        op => op,
+    };
+    // Swapping changes the evaluation order of the operands, so leave it to the user if the
+    // non-constant operand has side effects.
+    let mut app = if has_side_effects(cx, other) {
+        Applicability::MaybeIncorrect
+    } else {
+        Applicability::MachineApplicable
+    };
+    let left = snippet_with_applicability(cx, rhs.span, "..", &mut app);
+    let right = snippet_with_applicability(cx, lhs.span, "..", &mut app);
+    span_lint_and_sugg(
+        cx,
+        COMPARISON_CONSTANT_SIDE,
+        expr.span,
+        msg,
+        "swap the operands",
+        format!("{left} {} {right}", op.as_str()),
+        app,
+    );
+}
+
+/// Checks if evaluating `expr` can have side effects, i.e. if it contains calls, assignments or
+/// overloaded operators.
+fn has_side_effects<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) -> bool {
+    let typeck = cx.typeck_results();
+    for_each_expr(expr, |e| {
+        if typeck.is_method_call(e)
+            || matches!(
+                e.kind,
+                ExprKind::Call(..) | ExprKind::MethodCall(..) | ExprKind::Assign(..) | ExprKind::AssignOp(..)
+            )
+        {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
@@ -39,22 +39,22 @@ fn comparison_to_const<'tcx>(
 
 pub(super) fn check<'tcx>(
     cx: &LateContext<'tcx>,
-    and_op: Spanned<BinOpKind>,
+    op: Spanned<BinOpKind>,
     left_cond: &'tcx Expr<'tcx>,
     right_cond: &'tcx Expr<'tcx>,
     span: Span,
 ) {
-    if and_op.node == BinOpKind::And
-        // Ensure that the binary operator is &&
+    if matches!(op.node, BinOpKind::And | BinOpKind::Or)
+        // Ensure that the binary operator is && or ||
 
-        // Check that both operands to '&&' are themselves a binary operation
+        // Check that both operands to '&&' or '||' are themselves a binary operation
         // The `comparison_to_const` step also checks this, so this step is just an optimization
         && let ExprKind::Binary(_, _, _) = left_cond.kind
         && let ExprKind::Binary(_, _, _) = right_cond.kind
 
         && let typeck = cx.typeck_results()
 
-        // Check that both operands to '&&' compare a non-literal to a literal
+        // Check that both operands to '&&' or '||' compare a non-literal to a literal
         && let Some((left_cmp_op, left_expr, left_const_expr, left_const, left_type)) =
             comparison_to_const(cx, typeck, left_cond)
         && let Some((right_cmp_op, right_expr, right_const_expr, right_const, right_type)) =
@@ -80,30 +80,42 @@ pub(super) fn check<'tcx>(
         if left_cmp_op.direction() == right_cmp_op.direction() {
             let lhs_str = snippet(cx, left_cond.span, "<lhs>");
             let rhs_str = snippet(cx, right_cond.span, "<rhs>");
-            // We already know that either side of `&&` has no effect,
-            // but emit a different error message depending on which side it is
-            if left_side_is_useless(left_cmp_op, ordering) {
+            let op_str = op.node.as_str();
+            // We already know that either side has no effect, but emit a different error message
+            // depending on which side it is. With `||`, the looser comparison is the one to keep,
+            // so it is the other side than with `&&`.
+            if left_side_is_useless(left_cmp_op, ordering) == (op.node == BinOpKind::And) {
+                let (implying, implied) = if op.node == BinOpKind::And {
+                    (rhs_str, lhs_str)
+                } else {
+                    (lhs_str, rhs_str)
+                };
                 span_lint_and_note(
                     cx,
                     REDUNDANT_COMPARISONS,
                     span,
-                    "left-hand side of `&&` operator has no effect",
+                    &format!("left-hand side of `{op_str}` operator has no effect"),
                     Some(left_cond.span.until(right_cond.span)),
-                    &format!("`if `{rhs_str}` evaluates to true, {lhs_str}` will always evaluate to true as well"),
+                    &format!("`if `{implying}` evaluates to true, {implied}` will always evaluate to true as well"),
                 );
             } else {
+                let (implying, implied) = if op.node == BinOpKind::And {
+                    (lhs_str, rhs_str)
+                } else {
+                    (rhs_str, lhs_str)
+                };
                 span_lint_and_note(
                     cx,
                     REDUNDANT_COMPARISONS,
                     span,
-                    "right-hand side of `&&` operator has no effect",
-                    Some(and_op.span.to(right_cond.span)),
-                    &format!("`if `{lhs_str}` evaluates to true, {rhs_str}` will always evaluate to true as well"),
+                    &format!("right-hand side of `{op_str}` operator has no effect"),
+                    Some(op.span.to(right_cond.span)),
+                    &format!("`if `{implying}` evaluates to true, {implied}` will always evaluate to true as well"),
                 );
             }
             // We could autofix this error but choose not to,
             // because code triggering this lint probably not behaving correctly in the first place
-        } else if !comparison_is_possible(left_cmp_op.direction(), ordering) {
+        } else if op.node == BinOpKind::And && !comparison_is_possible(left_cmp_op.direction(), ordering) {
             let expr_str = snippet(cx, left_expr.span, "..");
             let lhs_str = snippet(cx, left_const_expr.span, "<lhs>");
             let rhs_str = snippet(cx, right_const_expr.span, "<rhs>");
@@ -0,0 +1,34 @@
+use clippy_utils::comparisons::{deref_place, normalize_comparison, PlaceProjection};
+use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::eq_expr_value;
+use rustc_hir::{BinOpKind, Expr};
+use rustc_lint::LateContext;
+
+use super::identical_call_comparisons::constant_result;
+use super::DEREF_SELF_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    // Comparisons of identical expressions are linted by `eq_op`.
+    if let Some((rel, left, right)) = normalize_comparison(op, lhs, rhs)
+        && !eq_expr_value(cx, left, right)
+        && let Some(place) = deref_place(cx, left)
+        && place.1.iter().filter(|&&p| p == PlaceProjection::Deref).count() == 1
+        && deref_place(cx, right).as_ref() == Some(&place)
+        && let Some(value) = constant_result(rel, cx.typeck_results().expr_ty(left))
+    {
+        span_lint_and_note(
+            cx,
+            DEREF_SELF_COMPARISONS,
+            expr.span,
+            &format!("this comparison is always {value}"),
+            None,
+            "both sides read the same value through the same reference",
+        );
+    }
+}
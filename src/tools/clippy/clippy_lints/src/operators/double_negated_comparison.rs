@@ -0,0 +1,66 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::get_parent_expr;
+use clippy_utils::sugg::{make_unop, Sugg};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
+use rustc_lint::LateContext;
+
+use super::DOUBLE_NEGATED_COMPARISON;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, arg: &'tcx Expr<'_>) {
+    // Only look at the outermost negation of a chain.
+    if let Some(parent) = get_parent_expr(cx, expr)
+        && let ExprKind::Unary(UnOp::Not, _) = parent.kind
+    {
+        return;
+    }
+    let mut negations = 1;
+    let mut inner = arg;
+    while let ExprKind::Unary(UnOp::Not, next) = inner.kind {
+        if inner.span.from_expansion() {
+            return;
+        }
+        negations += 1;
+        inner = next;
+    }
+    // Comparison operators always evaluate to `bool`, so `!` is never overloaded here.
+    if negations < 2
+        || inner.span.from_expansion()
+        || !matches!(inner.kind, ExprKind::Binary(op, lhs, rhs) if normalize_comparison(op.node, lhs, rhs).is_some())
+    {
+        return;
+    }
+    let mut app = Applicability::MachineApplicable;
+    let comparison = Sugg::hir_with_applicability(cx, inner, "..", &mut app);
+    // Keep one `!` instead of inverting the operator, which would be wrong for NaN.
+    let sugg = if negations % 2 == 1 {
+        make_unop("!", comparison).to_string()
+    } else if needs_parens(cx, expr) {
+        comparison.maybe_par().to_string()
+    } else {
+        comparison.to_string()
+    };
+    span_lint_and_sugg(
+        cx,
+        DOUBLE_NEGATED_COMPARISON,
+        expr.span,
+        "redundant double negation of a comparison",
+        "remove the double negation",
+        sugg,
+        app,
+    );
+}
+
+/// Checks if a comparison replacing `expr` needs parentheses, because the parent expression binds
+/// more tightly than a comparison or is a comparison itself.
+fn needs_parens(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    get_parent_expr(cx, expr).is_some_and(|parent| match parent.kind {
+        ExprKind::Binary(op, ..) => !matches!(op.node, BinOpKind::And | BinOpKind::Or),
+        ExprKind::Unary(..) | ExprKind::AddrOf(..) | ExprKind::Cast(..) | ExprKind::Field(..) | ExprKind::Index(..) => {
+            true
+        },
+        ExprKind::MethodCall(_, receiver, ..) => receiver.hir_id == expr.hir_id,
+        _ => false,
+    })
+}
@@ -0,0 +1,80 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::sugg::{self, Sugg};
+use clippy_utils::ty::{is_type_diagnostic_item, peel_mid_ty_refs};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_span::{sym, Symbol};
+
+use super::DURATION_CONVERSION_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if normalize_comparison(op, lhs, rhs).is_some()
+        && let Some((method, left)) = duration_conversion(cx, lhs)
+        && let Some((rhs_method, right)) = duration_conversion(cx, rhs)
+        && method == rhs_method
+        && lhs.span.eq_ctxt(rhs.span)
+    {
+        span_lint_and_then(
+            cx,
+            DURATION_CONVERSION_COMPARISONS,
+            expr.span,
+            &format!("comparing `Duration`s after converting both with `{method}`"),
+            |diag| {
+                let lossless = method.as_str() == "as_nanos";
+                let mut app = if lossless {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                let typeck = cx.typeck_results();
+                let (left_ty, right_ty) = (typeck.expr_ty(left), typeck.expr_ty(right));
+                let mut left = Sugg::hir_with_applicability(cx, left, "..", &mut app);
+                let mut right = Sugg::hir_with_applicability(cx, right, "..", &mut app);
+                // References can only be compared with references of the same depth.
+                if left_ty != right_ty {
+                    for _ in 0..peel_mid_ty_refs(left_ty).1 {
+                        left = left.deref();
+                    }
+                    for _ in 0..peel_mid_ty_refs(right_ty).1 {
+                        right = right.deref();
+                    }
+                }
+                diag.span_suggestion(
+                    expr.span,
+                    "compare the `Duration`s directly",
+                    sugg::make_binop(op.into(), &left, &right).to_string(),
+                    app,
+                );
+                if !lossless {
+                    diag.note(format!(
+                        "`{method}` loses precision, which the direct comparison does not ignore"
+                    ));
+                }
+            },
+        );
+    }
+}
+
+/// Returns the method and the receiver if `expr` converts a `Duration` with one of its `as_*`
+/// methods.
+fn duration_conversion<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<(Symbol, &'a Expr<'a>)> {
+    if let ExprKind::MethodCall(path, receiver, [], _) = expr.kind
+        && matches!(
+            path.ident.as_str(),
+            "as_secs" | "as_millis" | "as_micros" | "as_nanos" | "as_secs_f32" | "as_secs_f64"
+        )
+        && is_type_diagnostic_item(cx, cx.typeck_results().expr_ty(receiver).peel_refs(), sym::Duration)
+    {
+        Some((path.ident.name, receiver))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,70 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::implements_trait;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::Ty;
+
+use super::ENUM_CAST_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if let Some((rel @ (Rel::Eq | Rel::Ne), _, _)) = normalize_comparison(op, lhs, rhs)
+        && let Some((left, left_ty)) = enum_cast_operand(cx, lhs)
+        && let Some((right, right_ty)) = enum_cast_operand(cx, rhs)
+        && left_ty == right_ty
+    {
+        let is_partial_eq = cx
+            .tcx
+            .lang_items()
+            .eq_trait()
+            .is_some_and(|eq_trait| implements_trait(cx, left_ty, eq_trait, &[left_ty.into()]));
+        span_lint_and_then(
+            cx,
+            ENUM_CAST_COMPARISONS,
+            expr.span,
+            "comparing enum values by casting them to integers",
+            |diag| {
+                if is_partial_eq {
+                    let mut applicability = Applicability::MaybeIncorrect;
+                    let left = snippet_with_applicability(cx, left.span, "..", &mut applicability);
+                    let right = snippet_with_applicability(cx, right.span, "..", &mut applicability);
+                    let op = if rel == Rel::Eq { "==" } else { "!=" };
+                    diag.span_suggestion(
+                        expr.span,
+                        "compare the values directly",
+                        format!("{left} {op} {right}"),
+                        applicability,
+                    );
+                    diag.note("this is only equivalent if `PartialEq` is derived rather than implemented by hand");
+                } else {
+                    diag.help("derive `PartialEq` for the enum or use `matches!` against the expected variant");
+                }
+            },
+        );
+    }
+}
+
+/// If `expr` is an `as` cast of a field-less enum value to an integer, returns the cast operand
+/// together with the type of the enum.
+pub(super) fn enum_cast_operand<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+) -> Option<(&'tcx Expr<'tcx>, Ty<'tcx>)> {
+    if let ExprKind::Cast(inner, _) = expr.kind
+        && cx.typeck_results().expr_ty(expr).is_integral()
+        && let inner_ty = cx.typeck_results().expr_ty(inner)
+        && inner_ty.is_enum()
+    {
+        Some((inner, inner_ty))
+    } else {
+        None
+    }
+}
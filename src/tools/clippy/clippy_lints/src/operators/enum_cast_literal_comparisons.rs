@@ -0,0 +1,87 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::ty::{get_discriminant_value, EnumValue};
+use clippy_utils::{clip, sext, unsext};
+use rustc_ast::LitKind;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, IntTy, Ty, TyCtxt};
+
+use super::enum_cast_comparisons::enum_cast_operand;
+use super::ENUM_CAST_LITERAL_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((rel @ (Rel::Eq | Rel::Ne), _, _)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    let (cast, lit) = if is_int_literal(rhs) { (lhs, rhs) } else { (rhs, lhs) };
+    if let Some((operand, enum_ty)) = enum_cast_operand(cx, cast)
+        && is_int_literal(lit)
+        && let ty::Adt(adt, _) = *enum_ty.kind()
+        && let Some(Constant::Int(value)) = constant(cx, cx.typeck_results(), lit)
+    {
+        let int_ty = cx.typeck_results().expr_ty(cast);
+        let variants: Vec<String> = adt
+            .variants()
+            .indices()
+            .filter(|&i| cast_discriminant(cx.tcx, get_discriminant_value(cx.tcx, adt, i), int_ty) == Some(value))
+            .map(|i| cx.tcx.def_path_str(adt.variant(i).def_id))
+            .collect();
+        span_lint_and_then(
+            cx,
+            ENUM_CAST_LITERAL_COMPARISONS,
+            expr.span,
+            "comparing the discriminant of an enum value to an integer literal",
+            |diag| {
+                if variants.is_empty() {
+                    let always = if rel == Rel::Eq { "false" } else { "true" };
+                    diag.note(format!(
+                        "no variant of `{enum_ty}` has this discriminant, so the comparison is always `{always}`"
+                    ));
+                } else {
+                    let mut applicability = Applicability::MaybeIncorrect;
+                    let operand = snippet_with_applicability(cx, operand.span, "..", &mut applicability);
+                    let not = if rel == Rel::Eq { "" } else { "!" };
+                    diag.span_suggestion(
+                        expr.span,
+                        "match against the variant instead",
+                        format!("{not}matches!({operand}, {})", variants.join(" | ")),
+                        applicability,
+                    );
+                }
+            },
+        );
+    }
+}
+
+/// Returns whether `expr` is an integer literal, possibly negated.
+fn is_int_literal(expr: &Expr<'_>) -> bool {
+    let expr = match expr.kind {
+        ExprKind::Unary(UnOp::Neg, inner) => inner,
+        _ => expr,
+    };
+    matches!(expr.kind, ExprKind::Lit(lit) if matches!(lit.node, LitKind::Int(..)))
+}
+
+/// Returns the discriminant `value` after an `as` cast to the integer type `int_ty`, in the
+/// representation that `Constant::Int` uses for `int_ty`.
+fn cast_discriminant(tcx: TyCtxt<'_>, value: EnumValue, int_ty: Ty<'_>) -> Option<u128> {
+    let bits = match value {
+        EnumValue::Signed(x) => unsext(tcx, x, IntTy::I128),
+        EnumValue::Unsigned(x) => x,
+    };
+    match *int_ty.kind() {
+        ty::Int(ity) => Some(unsext(tcx, sext(tcx, bits, IntTy::I128), ity)),
+        ty::Uint(uty) => Some(clip(tcx, bits, uty)),
+        _ => None,
+    }
+}
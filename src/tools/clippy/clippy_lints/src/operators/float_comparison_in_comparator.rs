@@ -0,0 +1,70 @@
+use clippy_utils::comparisons::float_ordering_comparison;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, Node};
+use rustc_lint::LateContext;
+use rustc_span::sym;
+
+use super::FLOAT_COMPARISON_IN_COMPARATOR;
+
+/// Methods taking a closure that compares two elements.
+const COMPARATOR_METHODS: &[&str] = &[
+    "binary_search_by",
+    "is_sorted_by",
+    "max_by",
+    "min_by",
+    "select_nth_unstable_by",
+    "sort_by",
+    "sort_unstable_by",
+];
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if let Some((_, left, _)) = float_ordering_comparison(cx, op, lhs, rhs)
+        && in_comparator(cx, expr.hir_id)
+    {
+        let ty = cx.typeck_results().expr_ty(left).peel_refs();
+        span_lint_and_help(
+            cx,
+            FLOAT_COMPARISON_IN_COMPARATOR,
+            expr.span,
+            "comparing floats with a comparison operator when ordering them",
+            None,
+            &format!("use `{ty}::total_cmp` to get an ordering that also handles NaN"),
+        );
+    }
+}
+
+/// Checks if `hir_id` is inside a closure passed to a method like `sort_by`, or inside the `cmp`
+/// or `partial_cmp` method of an `Ord` or `PartialOrd` impl.
+fn in_comparator(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    let hir = cx.tcx.hir();
+    for (id, node) in hir.parent_iter(hir_id) {
+        match node {
+            Node::Expr(closure) if matches!(closure.kind, ExprKind::Closure(_)) => {
+                return if let Some(Node::Expr(parent)) = hir.find_parent(id)
+                    && let ExprKind::MethodCall(path, _, args, _) = parent.kind
+                {
+                    COMPARATOR_METHODS.contains(&path.ident.as_str()) && args.iter().any(|arg| arg.hir_id == id)
+                } else {
+                    false
+                };
+            },
+            Node::ImplItem(item) => {
+                return matches!(item.ident.as_str(), "cmp" | "partial_cmp")
+                    && cx
+                        .tcx
+                        .trait_id_of_impl(hir.get_parent_item(id).to_def_id())
+                        .and_then(|trait_id| cx.tcx.get_diagnostic_name(trait_id))
+                        .is_some_and(|name| matches!(name, sym::Ord | sym::PartialOrd));
+            },
+            Node::Item(_) | Node::TraitItem(_) => return false,
+            _ => {},
+        }
+    }
+    false
+}
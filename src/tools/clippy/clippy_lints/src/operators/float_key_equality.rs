@@ -0,0 +1,172 @@
+use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::comparisons::{normalize_comparison_peeled, Rel};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{eq_expr_value, get_parent_expr};
+use core::ops::ControlFlow;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, Node, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::Symbol;
+
+use super::FLOAT_KEY_EQUALITY;
+
+/// Methods taking a closure that tells whether two elements are duplicates.
+const DEDUP_METHODS: &[&str] = &["dedup_by", "partition_dedup_by"];
+
+/// Methods taking a closure that looks for an element.
+const SEARCH_METHODS: &[&str] = &["all", "any", "find", "position"];
+
+/// Methods taking a closure that decides which elements to keep.
+const FILTER_METHODS: &[&str] = &["filter", "retain", "retain_mut"];
+
+/// Methods adding an element to a collection.
+const INSERT_METHODS: &[&str] = &["insert", "push", "push_back"];
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+    msrv: &Msrv,
+) {
+    // `normalize_comparison` swaps the operands of equalities.
+    if let Some((rel @ (Rel::Eq | Rel::Ne), right, left)) = normalize_comparison_peeled(cx, op, lhs, rhs)
+        && cx.typeck_results().expr_ty(left).peel_refs().is_floating_point()
+        && in_keying_context(cx, expr.hir_id)
+    {
+        let op_str = if rel == Rel::Eq { "==" } else { "!=" };
+        span_lint_and_then(
+            cx,
+            FLOAT_KEY_EQUALITY,
+            expr.span,
+            &format!("comparing floats with `{op_str}` to tell keys apart"),
+            |diag| {
+                diag.note("`==` treats `-0.0` and `0.0` as the same key, and no key is equal to `NaN`, not even `NaN`");
+                if msrv.meets(msrvs::TOTAL_CMP) {
+                    // `total_cmp` takes the other float by reference.
+                    let right = peel_ref_deref(cx, right);
+                    let depth = ref_depth(cx, right);
+                    let mut right = Sugg::hir(cx, right, "..");
+                    if depth == 0 {
+                        right = right.addr();
+                    }
+                    for _ in 1..depth {
+                        right = right.deref();
+                    }
+                    let left = Sugg::hir(cx, peel_ref_deref(cx, left), "..").maybe_par();
+                    let method = if rel == Rel::Eq { "is_eq" } else { "is_ne" };
+                    diag.span_suggestion(
+                        expr.span,
+                        "compare them with `total_cmp` instead",
+                        format!("{left}.total_cmp({right}).{method}()"),
+                        // This changes which keys are equal, which is the point, but might not be
+                        // what every caller expects.
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+            },
+        );
+    }
+}
+
+/// Checks if `hir_id` is inside a closure that tells keys apart: one passed to a method like
+/// `dedup_by`, or one passed to a method like `any` to look for a key, when that happens inside a
+/// closure passed to a method like `filter`, or in the condition of an `if` that adds to the
+/// searched collection.
+fn in_keying_context(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    let Some((method, call)) = closure_method(cx, hir_id) else {
+        return false;
+    };
+    if DEDUP_METHODS.contains(&method.as_str()) {
+        return true;
+    }
+    if !SEARCH_METHODS.contains(&method.as_str()) {
+        return false;
+    }
+    if closure_method(cx, call.hir_id).is_some_and(|(method, _)| FILTER_METHODS.contains(&method.as_str())) {
+        return true;
+    }
+    let ExprKind::MethodCall(_, searched, ..) = call.kind else {
+        return false;
+    };
+    let searched = collection(searched);
+    let mut cond = call;
+    while let Some(parent) = get_parent_expr(cx, cond) {
+        match parent.kind {
+            ExprKind::Unary(UnOp::Not, _) | ExprKind::DropTemps(_) => cond = parent,
+            ExprKind::If(if_cond, then, _) if if_cond.hir_id == cond.hir_id => {
+                return for_each_expr(then, |e| {
+                    if let ExprKind::MethodCall(path, receiver, ..) = e.kind
+                        && INSERT_METHODS.contains(&path.ident.as_str())
+                        && eq_expr_value(cx, receiver, searched)
+                    {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .is_some();
+            },
+            _ => return false,
+        }
+    }
+    false
+}
+
+/// Returns the name of the method that the innermost closure around `hir_id` is passed to, and
+/// the method call.
+fn closure_method<'tcx>(cx: &LateContext<'tcx>, hir_id: HirId) -> Option<(Symbol, &'tcx Expr<'tcx>)> {
+    let hir = cx.tcx.hir();
+    for (id, node) in hir.parent_iter(hir_id) {
+        match node {
+            Node::Expr(closure) if matches!(closure.kind, ExprKind::Closure(_)) => {
+                return if let Some(Node::Expr(parent)) = hir.find_parent(id)
+                    && let ExprKind::MethodCall(path, _, args, _) = parent.kind
+                    && args.iter().any(|arg| arg.hir_id == id)
+                {
+                    Some((path.ident.name, parent))
+                } else {
+                    None
+                };
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => return None,
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Returns `v` if `expr` is `v.iter()`, `v.iter_mut()` or `v.into_iter()`, and `expr` otherwise.
+fn collection<'a>(expr: &'a Expr<'a>) -> &'a Expr<'a> {
+    match expr.kind {
+        ExprKind::MethodCall(path, receiver, [], _)
+            if matches!(path.ident.as_str(), "iter" | "iter_mut" | "into_iter") =>
+        {
+            receiver
+        },
+        _ => expr,
+    }
+}
+
+/// Returns the number of references that the type of `expr` has.
+fn ref_depth(cx: &LateContext<'_>, expr: &Expr<'_>) -> usize {
+    let mut ty = cx.typeck_results().expr_ty(expr);
+    let mut depth = 0;
+    while let ty::Ref(_, inner, _) = ty.kind() {
+        ty = *inner;
+        depth += 1;
+    }
+    depth
+}
+
+/// Returns `r` if `expr` is the dereference `*r` of a reference, and `expr` otherwise.
+fn peel_ref_deref<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> &'a Expr<'a> {
+    match expr.kind {
+        ExprKind::Unary(UnOp::Deref, inner) if cx.typeck_results().expr_ty(inner).is_ref() => inner,
+        _ => expr,
+    }
+}
@@ -0,0 +1,62 @@
+use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::comparisons::{unwrap_comparison, UnwrapComparison};
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::eq_expr_value;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::Sugg;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::GUARDED_UNWRAP_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+    msrv: &Msrv,
+) {
+    if op != BinOpKind::And {
+        return;
+    }
+    // In `a && x.is_some() && x.unwrap() > 0`, the guard is the right operand of `a && x.is_some()`.
+    let guard = match lhs.kind {
+        ExprKind::Binary(lhs_op, _, guard) if lhs_op.node == BinOpKind::And => guard,
+        _ => lhs,
+    };
+    if let ExprKind::Binary(cmp_op, cmp_lhs, cmp_rhs) = rhs.kind
+        && let Some(UnwrapComparison {
+            receiver,
+            is_option,
+            op: cmp,
+            bound,
+        }) = unwrap_comparison(cx, cmp_op.node, cmp_lhs, cmp_rhs)
+        && let ExprKind::MethodCall(path, guarded, [], _) = guard.kind
+        && path.ident.as_str() == if is_option { "is_some" } else { "is_ok" }
+        && eq_expr_value(cx, guarded, receiver)
+        && constant(cx, cx.typeck_results(), bound).is_some()
+        && guard.span.eq_ctxt(rhs.span)
+        && msrv.meets(msrvs::OPTION_IS_SOME_AND)
+    {
+        let method = if is_option { "is_some_and" } else { "is_ok_and" };
+        let mut app = Applicability::MachineApplicable;
+        let receiver = Sugg::hir_with_applicability(cx, receiver, "..", &mut app).maybe_par();
+        let bound = snippet_with_applicability(cx, bound.span, "..", &mut app);
+        span_lint_and_sugg(
+            cx,
+            GUARDED_UNWRAP_COMPARISONS,
+            if guard.hir_id == lhs.hir_id {
+                expr.span
+            } else {
+                guard.span.to(rhs.span)
+            },
+            &format!("comparing an unwrapped value after checking `{}`", path.ident),
+            &format!("use `{method}` instead"),
+            format!("{receiver}.{method}(|v| v {} {bound})", cmp.as_str()),
+            app,
+        );
+    }
+}
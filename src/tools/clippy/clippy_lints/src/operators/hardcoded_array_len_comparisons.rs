@@ -0,0 +1,115 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{path_to_local, path_to_local_id};
+use core::ops::ControlFlow;
+use rustc_ast::LitKind;
+use rustc_errors::Applicability;
+use rustc_hir::{ArrayLen, BinOpKind, Block, Expr, ExprKind, HirId, Local, Node, PatKind, StmtKind, TyKind};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+
+use super::HARDCODED_ARRAY_LEN_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((_, left, right)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    let (index, lit, len) = if let Some(len) = int_literal(right) {
+        (left, right, len)
+    } else if let Some(len) = int_literal(left) {
+        (right, left, len)
+    } else {
+        return;
+    };
+    if let Some(index) = path_to_local(index)
+        && let Some(array) = find_indexed_array(cx, expr, index, len)
+    {
+        span_lint_and_sugg(
+            cx,
+            HARDCODED_ARRAY_LEN_COMPARISONS,
+            lit.span,
+            &format!("comparing an index of `{array}` against a hardcoded copy of its length"),
+            "use the length of the array",
+            format!("{array}.len()"),
+            Applicability::MaybeIncorrect,
+        );
+    }
+}
+
+/// Returns the value of `expr` if it is an integer literal.
+fn int_literal(expr: &Expr<'_>) -> Option<u128> {
+    if let ExprKind::Lit(lit) = expr.kind
+        && let LitKind::Int(value, _) = lit.node
+    {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Looks for an array in the blocks around `expr` that is declared before it with a literal length
+/// of `len`, and that is indexed with the local `index` in the block it is declared in. Returns the
+/// name of the array.
+fn find_indexed_array<'tcx>(cx: &LateContext<'tcx>, expr: &Expr<'_>, index: HirId, len: u128) -> Option<Symbol> {
+    for (_, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        let block = match node {
+            Node::Block(block) => block,
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => return None,
+            _ => continue,
+        };
+        for stmt in block.stmts {
+            if let StmtKind::Local(local) = stmt.kind
+                && local.span.hi() <= expr.span.lo()
+                && let PatKind::Binding(_, array, name, None) = local.pat.kind
+                && declared_len(cx, local) == Some(len)
+                && is_indexed_with(block, array, index)
+            {
+                return Some(name.name);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the length of the array that `local` declares, if it is written as a literal in the
+/// type or in the repeat expression it is initialized with.
+fn declared_len(cx: &LateContext<'_>, local: &Local<'_>) -> Option<u128> {
+    let array_len = if let Some(ty) = local.ty
+        && let TyKind::Array(_, array_len) = &ty.kind
+    {
+        array_len
+    } else if let Some(init) = local.init
+        && let ExprKind::Repeat(_, array_len) = &init.kind
+    {
+        array_len
+    } else {
+        return None;
+    };
+    match array_len {
+        ArrayLen::Body(anon_const) => int_literal(cx.tcx.hir().body(anon_const.body).value),
+        ArrayLen::Infer(..) => None,
+    }
+}
+
+/// Returns whether `block` contains an expression that indexes the local `array` with the local
+/// `index`.
+fn is_indexed_with<'tcx>(block: &'tcx Block<'tcx>, array: HirId, index: HirId) -> bool {
+    for_each_expr(block, |e| {
+        if let ExprKind::Index(base, idx, _) = e.kind
+            && path_to_local_id(base, array)
+            && path_to_local_id(idx, index)
+        {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
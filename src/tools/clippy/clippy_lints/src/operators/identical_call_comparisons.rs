@@ -0,0 +1,120 @@
+use clippy_utils::comparisons::{normalize_comparison_peeled, Rel};
+use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::{eq_expr_value, SpanlessEq};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::{BinOpKind, Expr, ExprKind, Mutability};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, GenericArgKind, Ty};
+use rustc_span::sym;
+
+use super::IDENTICAL_CALL_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    // Comparisons of identical expressions without calls are linted by `eq_op`.
+    if let Some((rel, left, right)) = normalize_comparison_peeled(cx, op, lhs, rhs)
+        && !eq_expr_value(cx, left, right)
+        && SpanlessEq::new(cx).eq_expr(left, right)
+        && let Some(value) = constant_result(rel, cx.typeck_results().expr_ty(left).peel_refs())
+        && is_pure(cx, left)
+    {
+        span_lint_and_note(
+            cx,
+            IDENTICAL_CALL_COMPARISONS,
+            expr.span,
+            &format!("this comparison is always {value}"),
+            None,
+            "both sides are the same expression, which has no side effects",
+        );
+    }
+}
+
+/// Returns the result of comparing a value of type `ty` with itself, if it is known.
+pub(super) fn constant_result(rel: Rel, ty: Ty<'_>) -> Option<bool> {
+    if ty.is_integral() || ty.is_bool() || ty.is_char() {
+        Some(matches!(rel, Rel::Le | Rel::Eq))
+    } else if ty.is_floating_point() {
+        // Only `<` is known, all other comparisons depend on whether the value is NaN.
+        (rel == Rel::Lt).then_some(false)
+    } else {
+        None
+    }
+}
+
+/// Checks if evaluating `expr` twice gives the same result. This only allows calls of methods
+/// from `core` and `alloc` whose receiver, arguments and generic arguments contain no types
+/// from other crates, so no user code can run.
+fn is_pure(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let typeck = cx.typeck_results();
+    if typeck.is_method_call(expr) && !matches!(expr.kind, ExprKind::MethodCall(..)) {
+        // An overloaded operator.
+        return false;
+    }
+    match expr.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::Path(ref qpath) => matches!(
+            cx.qpath_res(qpath, expr.hir_id),
+            Res::Local(_) | Res::Def(DefKind::Const | DefKind::AssocConst, _)
+        ),
+        ExprKind::Field(inner, _)
+        | ExprKind::AddrOf(_, Mutability::Not, inner)
+        | ExprKind::Unary(_, inner)
+        | ExprKind::Cast(inner, _) => is_pure(cx, inner),
+        ExprKind::Binary(_, left, right) => is_pure(cx, left) && is_pure(cx, right),
+        ExprKind::MethodCall(_, receiver, args, _) => {
+            if let Some(method_id) = typeck.type_dependent_def_id(expr.hir_id)
+                && matches!(cx.tcx.crate_name(method_id.krate), sym::core | sym::alloc)
+                && !takes_mut_self(cx, method_id)
+                && typeck.node_args(expr.hir_id).types().all(|ty| is_pure_ty(cx, ty))
+            {
+                is_pure_ty(cx, typeck.expr_ty_adjusted(receiver))
+                    && is_pure(cx, receiver)
+                    && args
+                        .iter()
+                        .all(|arg| is_pure_ty(cx, typeck.expr_ty(arg)) && is_pure(cx, arg))
+            } else {
+                false
+            }
+        },
+        _ => false,
+    }
+}
+
+fn takes_mut_self(cx: &LateContext<'_>, method_id: DefId) -> bool {
+    let sig = cx.tcx.fn_sig(method_id).skip_binder().skip_binder();
+    matches!(
+        sig.inputs().first().map(|ty| ty.kind()),
+        Some(ty::Ref(_, _, Mutability::Mut))
+    )
+}
+
+/// Checks if `ty` only consists of primitives and types from `core` and `alloc` without interior
+/// mutability.
+fn is_pure_ty<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    ty.walk().all(|arg| match arg.unpack() {
+        GenericArgKind::Type(ty) => match ty.kind() {
+            ty::Adt(def, _) => {
+                matches!(cx.tcx.crate_name(def.did().krate), sym::core | sym::alloc)
+                    && ty.is_freeze(cx.tcx, cx.param_env)
+            },
+            ty::Bool
+            | ty::Char
+            | ty::Int(_)
+            | ty::Uint(_)
+            | ty::Float(_)
+            | ty::Str
+            | ty::Array(..)
+            | ty::Slice(_)
+            | ty::Tuple(_)
+            | ty::Ref(_, _, Mutability::Not) => true,
+            _ => false,
+        },
+        GenericArgKind::Lifetime(_) | GenericArgKind::Const(_) => true,
+    })
+}
@@ -0,0 +1,87 @@
+use clippy_utils::diagnostics::span_lint_and_note;
+use rustc_ast::LitKind;
+use rustc_hir::{BinOpKind, Expr, ExprKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, FloatTy};
+use rustc_span::Symbol;
+
+use super::INEXACT_FLOAT_LITERAL_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if matches!(op, BinOpKind::Eq | BinOpKind::Ne)
+        && let Some((lit, sym)) = float_literal(lhs).or_else(|| float_literal(rhs))
+        && let ty::Float(fty) = *cx.typeck_results().expr_ty(lit).kind()
+        && let Some(stored) = inexact_value(sym.as_str(), fty)
+    {
+        span_lint_and_note(
+            cx,
+            INEXACT_FLOAT_LITERAL_COMPARISONS,
+            expr.span,
+            "comparing a float for equality with a literal that it cannot represent exactly",
+            None,
+            &format!("`{sym}` is stored as `{stored}` in `{}`", fty.name_str()),
+        );
+    }
+}
+
+/// Returns the float literal expression and its symbol, peeling a negation.
+fn float_literal<'tcx>(expr: &'tcx Expr<'_>) -> Option<(&'tcx Expr<'tcx>, Symbol)> {
+    let expr = match expr.kind {
+        ExprKind::Unary(UnOp::Neg, inner) => inner,
+        _ => expr,
+    };
+    if let ExprKind::Lit(lit) = expr.kind
+        && let LitKind::Float(sym, _) = lit.node
+    {
+        Some((expr, sym))
+    } else {
+        None
+    }
+}
+
+/// If the decimal literal `lit` is not exactly representable in `fty`, returns the exact decimal
+/// value of the float it is rounded to.
+fn inexact_value(lit: &str, fty: FloatTy) -> Option<String> {
+    // Every `f32` is exactly representable as an `f64`.
+    let value: f64 = match fty {
+        FloatTy::F32 => lit.parse::<f32>().ok()?.into(),
+        FloatTy::F64 => lit.parse().ok()?,
+    };
+    if value.is_infinite() {
+        return None;
+    }
+    // Formatting with a precision is exact, and the smallest subnormal `f64` has 1074 fractional
+    // digits.
+    let exact = format!("{value:.1074}");
+    if normalize_decimal(lit) == normalize_decimal(&exact) {
+        None
+    } else {
+        Some(exact.trim_end_matches('0').trim_end_matches('.').to_string())
+    }
+}
+
+/// Splits a non-negative decimal number into its significant digits and the power of ten they
+/// are multiplied with, so that equal values have the same representation.
+fn normalize_decimal(s: &str) -> (String, i64) {
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().unwrap_or(0)),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{int}{frac}");
+    let digits = digits.trim_start_matches('0');
+    let significant = digits.trim_end_matches('0');
+    #[expect(clippy::cast_possible_wrap)]
+    let exponent = exponent - frac.len() as i64 + (digits.len() - significant.len()) as i64;
+    if significant.is_empty() {
+        (String::new(), 0)
+    } else {
+        (significant.to_string(), exponent)
+    }
+}
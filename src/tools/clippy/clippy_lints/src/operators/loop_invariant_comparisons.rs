@@ -0,0 +1,67 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::{get_enclosing_loop_or_multi_call_closure, path_def_id, path_to_local};
+use rustc_hir::def::DefKind;
+use rustc_hir::{BinOpKind, BindingAnnotation, Expr, ExprKind, Node, PatKind};
+use rustc_lint::LateContext;
+use rustc_span::Span;
+
+use super::LOOP_INVARIANT_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if normalize_comparison(op, lhs, rhs).is_some()
+        && let Some(enclosing) = get_enclosing_loop_or_multi_call_closure(cx, expr)
+        && let ExprKind::Loop(..) = enclosing.kind
+    {
+        for operand in [lhs, rhs] {
+            if is_invariant_call(cx, operand, enclosing.span) {
+                span_lint_and_then(
+                    cx,
+                    LOOP_INVARIANT_COMPARISONS,
+                    operand.span,
+                    "this call is evaluated again in every iteration of the loop",
+                    |diag| {
+                        diag.help("call it once before the loop and compare against the result instead");
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Returns whether `expr` is a call to a local `const fn` whose arguments do not change while the
+/// loop spanning `loop_span` runs, so the call always has the same result and no side effects.
+fn is_invariant_call(cx: &LateContext<'_>, expr: &Expr<'_>, loop_span: Span) -> bool {
+    if let ExprKind::Call(func, args) = expr.kind
+        && let Some(def_id) = path_def_id(cx, func)
+        && def_id.is_local()
+        && matches!(cx.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        && cx.tcx.is_const_fn(def_id)
+    {
+        args.iter().all(|arg| is_invariant_arg(cx, arg, loop_span))
+    } else {
+        false
+    }
+}
+
+/// Returns whether `arg` is a constant or an immutable binding from outside of the loop.
+fn is_invariant_arg(cx: &LateContext<'_>, arg: &Expr<'_>, loop_span: Span) -> bool {
+    if constant(cx, cx.typeck_results(), arg).is_some() {
+        return true;
+    }
+    if let Some(local) = path_to_local(arg)
+        && let Node::Pat(pat) = cx.tcx.hir().get(local)
+        && let PatKind::Binding(BindingAnnotation::NONE, ..) = pat.kind
+    {
+        !loop_span.contains(pat.span)
+    } else {
+        false
+    }
+}
@@ -0,0 +1,42 @@
+use clippy_utils::comparisons::{power_of_two_test, PowerOfTwoTest};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::sugg::Sugg;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::MANUAL_IS_POWER_OF_TWO;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    // In `a && x != 0 && x & (x - 1) == 0`, the first test is the right operand of `a && x != 0`.
+    let first = match lhs.kind {
+        ExprKind::Binary(lhs_op, _, first) if lhs_op.node == op => first,
+        _ => lhs,
+    };
+    if let Some(PowerOfTwoTest { value, is_negated }) = power_of_two_test(cx, op, first, rhs)
+        && first.span.eq_ctxt(rhs.span)
+    {
+        let mut app = Applicability::MachineApplicable;
+        let value = Sugg::hir_with_applicability(cx, value, "..", &mut app).maybe_par();
+        let not = if is_negated { "!" } else { "" };
+        span_lint_and_sugg(
+            cx,
+            MANUAL_IS_POWER_OF_TWO,
+            if first.hir_id == lhs.hir_id {
+                expr.span
+            } else {
+                first.span.to(rhs.span)
+            },
+            "manually testing whether an integer is a power of two",
+            "use `is_power_of_two`",
+            format!("{not}{value}.is_power_of_two()"),
+            app,
+        );
+    }
+}
@@ -1,28 +1,53 @@
 mod absurd_extreme_comparisons;
+mod absurd_float_sign_comparisons;
 mod assign_op_pattern;
+mod asymmetric_cast_comparisons;
 mod bit_mask;
+mod bitwise_joined_comparisons;
+mod checked_unwrap_comparisons;
 mod cmp_owned;
+mod comparison_constant_side;
 mod const_comparisons;
+mod deref_self_comparisons;
 mod double_comparison;
+mod double_negated_comparison;
+mod duration_conversion_comparisons;
 mod duration_subsec;
+mod enum_cast_comparisons;
+mod enum_cast_literal_comparisons;
 mod eq_op;
 mod erasing_op;
 mod float_cmp;
+mod float_comparison_in_comparator;
 mod float_equality_without_abs;
+mod float_key_equality;
+mod guarded_unwrap_comparisons;
+mod hardcoded_array_len_comparisons;
+mod identical_call_comparisons;
 mod identity_op;
+mod inexact_float_literal_comparisons;
 mod integer_division;
+mod loop_invariant_comparisons;
+mod manual_is_power_of_two;
 mod misrefactored_assign_op;
 mod modulo_arithmetic;
 mod modulo_one;
 mod needless_bitwise_bool;
 mod numeric_arithmetic;
 mod op_ref;
+mod option_ordering_comparisons;
+mod ordering_by_subtraction;
 mod ptr_eq;
+mod saturating_wrapping_comparisons;
 mod self_assignment;
+mod single_bit_equality;
+mod truncating_cast_comparisons;
 mod verbose_bit_mask;
 
 pub(crate) mod arithmetic_side_effects;
 
+use clippy_config::msrvs::Msrv;
+use clippy_config::types::ComparisonConstantSide;
 use rustc_hir::{Body, Expr, ExprKind, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
@@ -59,6 +84,33 @@
     "a comparison with a maximum or minimum value that is always true or false"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons of a float expression that can never be negative against a
+    /// bound that makes the comparison always false, such as `x.abs() < 0.0` or
+    /// `x.sqrt() <= -1.0`.
+    ///
+    /// ### Why is this bad?
+    /// Values like `x.abs()`, `x.sqrt()` or `x * x` are never negative, so the comparison
+    /// can never hold. This is usually a mistake, e.g. a check written for the wrong value.
+    ///
+    /// ### Known problems
+    /// Only the inherent float methods `abs`, `sqrt`, `exp`, `exp2`, `hypot` and `powi` with
+    /// an even literal exponent, and self-multiplications like `x * x` are recognized.
+    /// Comparisons in the opposite direction, like `x.abs() >= 0.0`, are not linted since
+    /// they are false for NaN.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = 1.0f64;
+    /// if x.abs() < 0.0 {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub ABSURD_FLOAT_SIGN_COMPARISONS,
+    correctness,
+    "a comparison of a never negative float with a bound that makes it always false"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks any kind of arithmetic operation of any type.
@@ -320,7 +372,8 @@
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for ineffective double comparisons against constants.
+    /// Checks for ineffective double comparisons against constants. With `&&`, the looser
+    /// comparison has no effect, and with `||`, the stricter one.
     ///
     /// ### Why is this bad?
     /// Only one of the comparisons has any effect on the result, the programmer
@@ -331,6 +384,7 @@
     /// ```no_run
     /// # let status_code = 200;
     /// if status_code <= 400 && status_code < 500 {}
+    /// if status_code > 500 || status_code > 400 {}
     /// ```
     #[clippy::version = "1.73.0"]
     pub REDUNDANT_COMPARISONS,
@@ -526,14 +580,20 @@
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for conversions to owned values just for the sake
-    /// of a comparison.
+    /// Checks for conversions to owned values, like `to_owned`, `to_string` or
+    /// `clone`, just for the sake of a comparison. If both sides of `==` or `!=` are converted
+    /// with the same `to_owned` or `to_string` method, it suggests comparing the original values.
     ///
     /// ### Why is this bad?
     /// The comparison can operate on a reference, so creating
     /// an owned value effectively throws it away directly afterwards, which is
     /// needlessly consuming code and heap space.
     ///
+    /// ### Known problems
+    /// Values that compare as equal can have different string representations and vice versa,
+    /// so the suggestion to compare values instead of their `to_string` results may change the
+    /// result.
+    ///
     /// ### Example
     /// ```no_run
     /// # let x = "foo";
@@ -708,6 +768,7 @@
     /// `ptr::eq` can be used to compare `&T` references
     /// (which coerce to `*const T` implicitly) by their address rather than
     /// comparing the values they point to.
+    /// Comparisons with `!=` can be written as `!ptr::eq(a, b)`.
     ///
     /// ### Example
     /// ```no_run
@@ -768,12 +829,725 @@
     "explicit self-assignment"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for equality comparisons between two values of the same enum that are
+    /// both cast to an integer first, e.g. `a as isize == b as isize`.
+    ///
+    /// ### Why is this bad?
+    /// Comparing the discriminants hides what is actually being compared. If the enum
+    /// implements `PartialEq`, the values can be compared directly; otherwise matching
+    /// on the expected variant states the intent more clearly.
+    ///
+    /// ### Known problems
+    /// If `PartialEq` is implemented by hand, comparing the values directly may not be
+    /// equivalent to comparing the discriminants.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[derive(PartialEq, Clone, Copy)]
+    /// enum Color {
+    ///     Red,
+    ///     Green,
+    /// }
+    ///
+    /// fn same(a: Color, b: Color) -> bool {
+    ///     a as isize == b as isize
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #[derive(PartialEq, Clone, Copy)]
+    /// # enum Color {
+    /// #     Red,
+    /// #     Green,
+    /// # }
+    /// fn same(a: Color, b: Color) -> bool {
+    ///     a == b
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub ENUM_CAST_COMPARISONS,
+    pedantic,
+    "comparing enum values by casting both of them to integers"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `==` and `!=` comparisons of floats with a decimal literal whose value can't be
+    /// represented exactly by the float type, such as `x == 0.1`.
+    ///
+    /// ### Why is this bad?
+    /// The literal is rounded to the nearest representable value, so the comparison is made
+    /// against a slightly different number than the one written. E.g. `0.1 + 0.2 == 0.3` is
+    /// `false` since neither side is exactly `0.3`.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = 0.1f32;
+    /// if x == 0.1 {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = 0.1f32;
+    /// if (x - 0.1).abs() < f32::EPSILON {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub INEXACT_FLOAT_LITERAL_COMPARISONS,
+    pedantic,
+    "comparing a float for equality with a literal that can't be represented exactly"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons of a saturating or wrapping integer operation with a positive
+    /// constant against its own receiver, like `x.saturating_add(1) >= x` or
+    /// `x.wrapping_add(1) > x`.
+    ///
+    /// ### Why is this bad?
+    /// Saturating operations never move the value in the wrong direction, so comparisons like
+    /// `x.saturating_add(1) >= x` are always true and `x.saturating_sub(1) > x` is always false.
+    /// Wrapping operations with a non-zero operand never give back the receiver, so
+    /// `x.wrapping_add(1) == x` is always false.
+    ///
+    /// Comparisons like `x.wrapping_add(1) > x` on the other hand look like they are always true,
+    /// but are false if the operation wraps around, which is often overlooked.
+    ///
+    /// ### Known problems
+    /// Only the inherent `saturating_add`, `saturating_sub`, `wrapping_add` and `wrapping_sub`
+    /// methods are checked. `checked_*` methods return an `Option`, which can't be compared with
+    /// the receiver.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = 1u8;
+    /// if x.wrapping_add(1) > x {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = 1u8;
+    /// if x.checked_add(1).is_some() {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub SATURATING_WRAPPING_COMPARISONS,
+    correctness,
+    "comparing the result of a saturating or wrapping operation with its receiver"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `<`, `<=`, `>` and `>=` comparisons where one side is a `None` or `Some(..)`
+    /// constructor.
+    ///
+    /// ### Why is this bad?
+    /// `Option` implements `Ord` with `None` being less than any `Some`, so e.g. `x < Some(5)`
+    /// is also true if `x` is `None`. Code relying on this is easily misread, and it is often
+    /// not what the author intended.
+    ///
+    /// ### Known problems
+    /// Relying on the ordering of `Option` can be intentional.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let limit = Some(5);
+    /// if limit < Some(10) {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let limit = Some(5);
+    /// if limit.map_or(true, |limit| limit < 10) {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub OPTION_ORDERING_COMPARISONS,
+    pedantic,
+    "ordering comparisons with `None` or `Some(..)`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons where both sides are the same expression containing method calls,
+    /// like `v.iter().count() < v.iter().count()`, and the calls are known to have no side
+    /// effects.
+    ///
+    /// ### Why is this bad?
+    /// Comparing a value with itself always gives the same result, e.g. `<` is always false and
+    /// `<=` is always true for integers. This is likely a mistake, such as a copy-paste error.
+    ///
+    /// ### Known problems
+    /// To be sure that both sides evaluate to the same value, only methods from `core` and
+    /// `alloc` that don't take `&mut self` are allowed, and all types involved must be from
+    /// these crates and be free of interior mutability. Comparisons of identical expressions
+    /// without calls are linted by `eq_op` instead.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let v = vec![1];
+    /// # let w = vec![2];
+    /// if v.iter().count() < v.iter().count() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let v = vec![1];
+    /// # let w = vec![2];
+    /// if v.iter().count() < w.iter().count() {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub IDENTICAL_CALL_COMPARISONS,
+    suspicious,
+    "comparing identical expressions containing method calls without side effects"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `<`, `<=`, `>` and `>=` comparisons of floats inside closures passed to
+    /// methods like `sort_by` or `max_by`, and inside `Ord::cmp` and `PartialOrd::partial_cmp`
+    /// implementations.
+    ///
+    /// ### Why is this bad?
+    /// Comparison operators are false if either float is NaN, so orderings built from them are
+    /// inconsistent if NaN is involved, which can give arbitrary results when sorting. The
+    /// `total_cmp` method gives a total order that also handles NaN.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::cmp::Ordering;
+    /// let mut v = vec![1.0, 0.5];
+    /// v.sort_by(|a, b| if a < b { Ordering::Less } else { Ordering::Greater });
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// let mut v = vec![1.0, 0.5];
+    /// v.sort_by(|a: &f64, b| a.total_cmp(b));
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub FLOAT_COMPARISON_IN_COMPARATOR,
+    pedantic,
+    "comparing floats with comparison operators when ordering them"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `if` conditions that compare the difference of two values with zero, like
+    /// `a - b < 0` or `a - b > T::zero()`, to decide which `Ordering` to return.
+    ///
+    /// ### Why is this bad?
+    /// The subtraction can overflow at the extremes of the type, which panics or wraps around for
+    /// integers and gives NaN for infinite floats, so the resulting ordering is wrong. Comparing
+    /// the values directly has no such problem.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::cmp::Ordering;
+    /// fn compare(a: i32, b: i32) -> Ordering {
+    ///     if a - b < 0 {
+    ///         Ordering::Less
+    ///     } else if a - b > 0 {
+    ///         Ordering::Greater
+    ///     } else {
+    ///         Ordering::Equal
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::cmp::Ordering;
+    /// fn compare(a: i32, b: i32) -> Ordering {
+    ///     a.cmp(&b)
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub ORDERING_BY_SUBTRACTION,
+    correctness,
+    "ordering values by comparing their difference with zero"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons wrapped in more than one `!`, like `!!(a < b)` or `!(!(a == b))`.
+    ///
+    /// ### Why is this bad?
+    /// Negating a comparison twice gives back the comparison itself, so the negations only make
+    /// the condition harder to read.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// if !!(a < b) {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let (a, b) = (1, 2);
+    /// if a < b {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub DOUBLE_NEGATED_COMPARISON,
+    complexity,
+    "a comparison that is negated twice"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons with a constant on the side that the `comparison-constant-side`
+    /// configuration doesn't allow. By default, constants have to be on the right, like `x == 5`.
+    /// With `comparison-constant-side = "Left"`, they have to be on the left instead, like
+    /// `5 == x` ("Yoda conditions").
+    ///
+    /// ### Why is this bad?
+    /// Mixing both styles makes comparisons harder to read.
+    ///
+    /// ### Known problems
+    /// Comparisons of operands with different types are not linted, since swapping them would
+    /// need a different `PartialEq` or `PartialOrd` impl.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = 1;
+    /// if 5 == x {}
+    /// if 5 < x {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = 1;
+    /// if x == 5 {}
+    /// if x > 5 {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub COMPARISON_CONSTANT_SIDE,
+    restriction,
+    "a comparison with the constant on the wrong side"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons where both sides read the same place, but one of them goes through
+    /// auto-deref and the other dereferences explicitly, like `p.x < (*p).x`.
+    ///
+    /// ### Why is this bad?
+    /// Comparing a value with itself always gives the same result, e.g. `<` is always false and
+    /// `<=` is always true for integers. This is likely a mistake, such as a copy-paste error.
+    ///
+    /// ### Known problems
+    /// Only places behind a single reference are recognized, and references that point to the
+    /// same value but are stored in different bindings are not. Comparisons of identical
+    /// expressions, like `*p < *p`, are linted by `eq_op` instead.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # struct S { x: u32 }
+    /// fn f(p: &S, q: &S) -> bool {
+    ///     p.x < (*p).x
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # struct S { x: u32 }
+    /// fn f(p: &S, q: &S) -> bool {
+    ///     p.x < q.x
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub DEREF_SELF_COMPARISONS,
+    correctness,
+    "comparing a value with itself through the same reference"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons where only one operand is cast to another integer type, and the
+    /// cast can change the value of that operand, like `x as i32 < y` with `x: u64`.
+    ///
+    /// ### Why is this bad?
+    /// Values that don't fit into the target type wrap around or are truncated before they are
+    /// compared, so the comparison can give a different result than comparing the original
+    /// values would.
+    ///
+    /// ### Known problems
+    /// The lint only reasons about the types. Comparisons where the casted value is known to be
+    /// in range, e.g. because it was checked before, are linted too. Comparisons with constants
+    /// are not linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn f(len: u64, limit: i32) -> bool {
+    ///     (len as i32) < limit
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn f(len: u64, limit: i32) -> bool {
+    ///     i32::try_from(len).is_ok_and(|len| len < limit)
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub ASYMMETRIC_CAST_COMPARISONS,
+    correctness,
+    "comparing a value cast to a narrower type with an uncast value"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `==` and `!=` comparisons of a value with a single bit computed by a shift, like
+    /// `flags == 1 << k`.
+    ///
+    /// ### Why is this bad?
+    /// If the value is a set of flags, comparing it for equality with a single bit is false as
+    /// soon as any other flag is set. Testing whether the bit is set was likely intended.
+    ///
+    /// ### Known problems
+    /// This is a heuristic. Comparing with a power of two is correct if the value is not a set
+    /// of flags.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (flags, k) = (1u32, 0);
+    /// if flags == 1 << k {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let (flags, k) = (1u32, 0);
+    /// if flags & (1 << k) != 0 {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub SINGLE_BIT_EQUALITY,
+    pedantic,
+    "comparing a value for equality with a single bit"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons of an unwrapped `Option` or `Result` with a constant that are
+    /// guarded by an `is_some` or `is_ok` check of the same value, like
+    /// `x.is_some() && x.unwrap() > 5`.
+    ///
+    /// ### Why is this bad?
+    /// `is_some_and` and `is_ok_and` express the same condition in one call, without an `unwrap`
+    /// that is only correct because of the check before it.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = Some(1);
+    /// if x.is_some() && x.unwrap() > 5 {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = Some(1);
+    /// if x.is_some_and(|v| v > 5) {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub GUARDED_UNWRAP_COMPARISONS,
+    style,
+    "comparing an unwrapped value after checking that it is `Some` or `Ok`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons of two `Duration`s that are both converted with the same `as_*`
+    /// method first, like `a.as_nanos() < b.as_nanos()`.
+    ///
+    /// ### Why is this bad?
+    /// `Duration` implements the comparison operators itself, so the conversions are not needed.
+    /// All conversions except `as_nanos` also lose precision, so durations that differ only in
+    /// the truncated or rounded part compare as equal, which is rarely what was meant.
+    ///
+    /// ### Known problems
+    /// If the loss of precision is intended, comparing the `Duration`s directly changes the
+    /// result.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # let (a, b) = (Duration::ZERO, Duration::MAX);
+    /// if a.as_nanos() < b.as_nanos() {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # let (a, b) = (Duration::ZERO, Duration::MAX);
+    /// if a < b {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub DURATION_CONVERSION_COMPARISONS,
+    complexity,
+    "comparing `Duration`s after converting both of them with the same `as_*` method"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for power of two tests of unsigned integers written with bit operations, like
+    /// `x != 0 && x & (x - 1) == 0`.
+    ///
+    /// ### Why is this bad?
+    /// `is_power_of_two` does the same and is easier to read. The bit trick is easy to get
+    /// slightly wrong, and without the `x != 0` part it also accepts zero.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let x = 8u32;
+    /// if x != 0 && x & (x - 1) == 0 {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let x = 8u32;
+    /// if x.is_power_of_two() {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_IS_POWER_OF_TWO,
+    complexity,
+    "testing whether an integer is a power of two with bit operations"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for ordering comparisons of the unwrapped result of a `checked_*` integer operation,
+    /// like `a.checked_add(b).unwrap() > c`.
+    ///
+    /// ### Why is this bad?
+    /// The point of the `checked_*` methods is to handle overflow (or division by zero) without
+    /// panicking. Unwrapping the result right away panics on overflow again, just with a less
+    /// helpful message than handling the `None` would give. If overflow cannot happen or should
+    /// not matter, `saturating_*` or `wrapping_*` arithmetic states that directly.
+    ///
+    /// ### Known problems
+    /// Unwrapping is sometimes used on purpose, to panic on overflow even in release builds.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (a, b, c) = (1u32, 2u32, 3u32);
+    /// if a.checked_add(b).unwrap() > c {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let (a, b, c) = (1u32, 2u32, 3u32);
+    /// if a.checked_add(b).map_or(true, |sum| sum > c) {}
+    /// // or, if the sum should stop at the maximum
+    /// if a.saturating_add(b) > c {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub CHECKED_UNWRAP_COMPARISONS,
+    pedantic,
+    "comparing the unwrapped result of a `checked_*` integer operation"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for two comparisons joined with the bitwise `&` or `|` operator, like
+    /// `(a < b) & (c < d)`.
+    ///
+    /// ### Why is this bad?
+    /// On `bool`s the bitwise operators always evaluate both sides, unlike `&&` and `||`. Joining
+    /// comparisons is nearly always meant as the logical operation, and the parentheses that the
+    /// bitwise form needs suggest that `&&` or `||` was intended. If the second comparison has
+    /// side effects, they happen even when its result does not matter.
+    ///
+    /// ### Known problems
+    /// The bitwise form is sometimes used on purpose, to avoid a branch or to always run the
+    /// side effects.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let (a, b, c, d) = (1, 2, 3, 4);
+    /// if (a < b) & (c < d) {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let (a, b, c, d) = (1, 2, 3, 4);
+    /// if a < b && c < d {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub BITWISE_JOINED_COMPARISONS,
+    suspicious,
+    "comparisons joined with a bitwise operator instead of a lazy one"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for equality comparisons between a field-less enum value that is cast to an
+    /// integer and an integer literal, e.g. `color as u8 == 2`.
+    ///
+    /// ### Why is this bad?
+    /// The numeric discriminant is an implementation detail of the enum, and the literal does not
+    /// say which variant is meant. Reordering the variants or changing their discriminants
+    /// silently changes the meaning of the comparison. Matching against the variant states the
+    /// intent and keeps working.
+    ///
+    /// ### Known problems
+    /// The discriminants are sometimes part of an interface, e.g. the values of a protocol field
+    /// that a `#[repr(u8)]` enum gives names to. Comparing them to numbers is not wrong then.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #[derive(Clone, Copy)]
+    /// # enum Color {
+    /// #     Red,
+    /// #     Green,
+    /// #     Blue,
+    /// # }
+    /// # let color = Color::Blue;
+    /// if color as u8 == 2 {}
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # #[derive(Clone, Copy)]
+    /// # enum Color {
+    /// #     Red,
+    /// #     Green,
+    /// #     Blue,
+    /// # }
+    /// # let color = Color::Blue;
+    /// if matches!(color, Color::Blue) {}
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub ENUM_CAST_LITERAL_COMPARISONS,
+    pedantic,
+    "comparing the discriminant of an enum value to an integer literal"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons inside of a loop where one side is a call to a `const fn` whose
+    /// arguments do not change while the loop runs, e.g. `x > compute_threshold()`.
+    ///
+    /// ### Why is this bad?
+    /// The call has the same result in every iteration, but it is evaluated again each time the
+    /// comparison runs. Calling it once before the loop avoids that work and makes it clear that
+    /// the value does not change.
+    ///
+    /// ### Known problems
+    /// To be sure that the call has no side effects, this only checks calls of `const fn`s of
+    /// the current crate whose arguments are constants or immutable bindings from outside of the
+    /// loop. The optimizer may already hoist or fold such calls.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # const fn compute_threshold() -> u32 { 10 }
+    /// # let scores = vec![1, 20, 3];
+    /// for score in &scores {
+    ///     if *score > compute_threshold() {}
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # const fn compute_threshold() -> u32 { 10 }
+    /// # let scores = vec![1, 20, 3];
+    /// let threshold = compute_threshold();
+    /// for score in &scores {
+    ///     if *score > threshold {}
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub LOOP_INVARIANT_COMPARISONS,
+    pedantic,
+    "comparing against a call that has the same result in every iteration of a loop"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparisons of an index against an integer literal that is also the literal
+    /// length of an array that the index is used with, e.g. `idx < 256` next to
+    /// `let buf = [0u8; 256];` and `buf[idx]`.
+    ///
+    /// ### Why is this bad?
+    /// The length is written down twice. If the array's length changes, the comparison silently
+    /// keeps checking against the old one.
+    ///
+    /// ### Known problems
+    /// This is a heuristic: it only looks at arrays that are declared with a literal length in a
+    /// surrounding block before the comparison, and that are indexed with the same local.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let idx = 3;
+    /// let mut buf = [0u8; 256];
+    /// if idx < 256 {
+    ///     buf[idx] = 1;
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let idx = 3;
+    /// let mut buf = [0u8; 256];
+    /// if idx < buf.len() {
+    ///     buf[idx] = 1;
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub HARDCODED_ARRAY_LEN_COMPARISONS,
+    pedantic,
+    "comparing an index against a hardcoded copy of an array's length"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `==` and `!=` comparisons of floats that tell keys apart: in closures passed to
+    /// `dedup_by`, and in closures passed to methods like `any` or `position` when that is used to
+    /// filter out duplicates or to check for a key before adding it.
+    ///
+    /// ### Why is this bad?
+    /// `==` is not a good equality for keys. It treats `-0.0` and `0.0` as the same key, and `NaN`
+    /// is not equal to anything, not even itself, so every `NaN` is a new key. `total_cmp` orders
+    /// all floats, so using it for equality tells keys apart consistently.
+    ///
+    /// ### Known problems
+    /// Some code relies on `-0.0` and `0.0` being the same key.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// let mut v = vec![0.0, -0.0, f64::NAN, f64::NAN];
+    /// v.dedup_by(|a, b| a == b);
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// let mut v = vec![0.0, -0.0, f64::NAN, f64::NAN];
+    /// v.dedup_by(|a, b| a.total_cmp(b).is_eq());
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub FLOAT_KEY_EQUALITY,
+    correctness,
+    "comparing floats with `==` to tell keys apart"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `==` and `!=` comparisons where one operand is cast to an integer type with
+    /// fewer bits, such that the comparison is always false, like `x as u8 as u32 == 300`, or
+    /// where the truncation is easy to miss, like `x as u8 as u32 == y as u32`.
+    ///
+    /// ### Why is this bad?
+    /// Truncating casts drop the high bits of the value. A constant that does not fit into the
+    /// narrower type can never be equal to the truncated value, and comparing a truncated value
+    /// with an untruncated one only compares the low bits.
+    ///
+    /// ### Known problems
+    /// Comparisons where only one operand is cast at all are left to
+    /// `asymmetric_cast_comparisons`. Comparisons with constants that fit are not linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn f(x: u64, y: u8) -> bool {
+    ///     x as u8 as u32 == 300 || x as u8 as u32 == y as u32
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn f(x: u64, y: u8) -> bool {
+    ///     x == 300 || x == u64::from(y)
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub TRUNCATING_CAST_COMPARISONS,
+    correctness,
+    "comparing a value that is cast to a narrower integer type in a way that truncates it"
+}
+
 pub struct Operators {
     arithmetic_context: numeric_arithmetic::Context,
     verbose_bit_mask_threshold: u64,
+    comparison_constant_side: ComparisonConstantSide,
+    msrv: Msrv,
 }
 impl_lint_pass!(Operators => [
     ABSURD_EXTREME_COMPARISONS,
+    ABSURD_FLOAT_SIGN_COMPARISONS,
     ARITHMETIC_SIDE_EFFECTS,
     FLOAT_ARITHMETIC,
     ASSIGN_OP_PATTERN,
@@ -799,12 +1573,36 @@ pub struct Operators {
     NEEDLESS_BITWISE_BOOL,
     PTR_EQ,
     SELF_ASSIGNMENT,
+    ENUM_CAST_COMPARISONS,
+    INEXACT_FLOAT_LITERAL_COMPARISONS,
+    SATURATING_WRAPPING_COMPARISONS,
+    OPTION_ORDERING_COMPARISONS,
+    IDENTICAL_CALL_COMPARISONS,
+    FLOAT_COMPARISON_IN_COMPARATOR,
+    ORDERING_BY_SUBTRACTION,
+    DOUBLE_NEGATED_COMPARISON,
+    COMPARISON_CONSTANT_SIDE,
+    DEREF_SELF_COMPARISONS,
+    ASYMMETRIC_CAST_COMPARISONS,
+    SINGLE_BIT_EQUALITY,
+    GUARDED_UNWRAP_COMPARISONS,
+    DURATION_CONVERSION_COMPARISONS,
+    MANUAL_IS_POWER_OF_TWO,
+    CHECKED_UNWRAP_COMPARISONS,
+    BITWISE_JOINED_COMPARISONS,
+    ENUM_CAST_LITERAL_COMPARISONS,
+    LOOP_INVARIANT_COMPARISONS,
+    HARDCODED_ARRAY_LEN_COMPARISONS,
+    FLOAT_KEY_EQUALITY,
+    TRUNCATING_CAST_COMPARISONS,
 ]);
 impl Operators {
-    pub fn new(verbose_bit_mask_threshold: u64) -> Self {
+    pub fn new(verbose_bit_mask_threshold: u64, comparison_constant_side: ComparisonConstantSide, msrv: Msrv) -> Self {
         Self {
             arithmetic_context: numeric_arithmetic::Context::default(),
             verbose_bit_mask_threshold,
+            comparison_constant_side,
+            msrv,
         }
     }
 }
@@ -815,6 +1613,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
             ExprKind::Binary(op, lhs, rhs) => {
                 if !e.span.from_expansion() {
                     absurd_extreme_comparisons::check(cx, e, op.node, lhs, rhs);
+                    absurd_float_sign_comparisons::check(cx, e, op.node, lhs, rhs);
                     if !(macro_with_not_op(lhs) || macro_with_not_op(rhs)) {
                         eq_op::check(cx, e, op.node, lhs, rhs);
                         op_ref::check(cx, e, op.node, lhs, rhs);
@@ -823,6 +1622,27 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
                     identity_op::check(cx, e, op.node, lhs, rhs);
                     needless_bitwise_bool::check(cx, e, op.node, lhs, rhs);
                     ptr_eq::check(cx, e, op.node, lhs, rhs);
+                    enum_cast_comparisons::check(cx, e, op.node, lhs, rhs);
+                    inexact_float_literal_comparisons::check(cx, e, op.node, lhs, rhs);
+                    saturating_wrapping_comparisons::check(cx, e, op.node, lhs, rhs);
+                    option_ordering_comparisons::check(cx, e, op.node, lhs, rhs);
+                    identical_call_comparisons::check(cx, e, op.node, lhs, rhs);
+                    float_comparison_in_comparator::check(cx, e, op.node, lhs, rhs);
+                    ordering_by_subtraction::check(cx, e, op.node, lhs, rhs);
+                    comparison_constant_side::check(cx, e, op.node, lhs, rhs, self.comparison_constant_side);
+                    deref_self_comparisons::check(cx, e, op.node, lhs, rhs);
+                    asymmetric_cast_comparisons::check(cx, e, op.node, lhs, rhs);
+                    single_bit_equality::check(cx, e, op.node, lhs, rhs);
+                    guarded_unwrap_comparisons::check(cx, e, op.node, lhs, rhs, &self.msrv);
+                    duration_conversion_comparisons::check(cx, e, op.node, lhs, rhs);
+                    manual_is_power_of_two::check(cx, e, op.node, lhs, rhs);
+                    checked_unwrap_comparisons::check(cx, e, op.node, lhs, rhs);
+                    bitwise_joined_comparisons::check(cx, e, op.node, lhs, rhs);
+                    enum_cast_literal_comparisons::check(cx, e, op.node, lhs, rhs);
+                    loop_invariant_comparisons::check(cx, e, op.node, lhs, rhs);
+                    hardcoded_array_len_comparisons::check(cx, e, op.node, lhs, rhs);
+                    float_key_equality::check(cx, e, op.node, lhs, rhs, &self.msrv);
+                    truncating_cast_comparisons::check(cx, e, op.node, lhs, rhs);
                 }
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
                 bit_mask::check(cx, e, op.node, lhs, rhs);
@@ -850,6 +1670,9 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
                 if op == UnOp::Neg {
                     self.arithmetic_context.check_negate(cx, e, arg);
                 }
+                if op == UnOp::Not && !e.span.from_expansion() {
+                    double_negated_comparison::check(cx, e, arg);
+                }
             },
             _ => (),
         }
@@ -866,6 +1689,8 @@ fn check_body(&mut self, cx: &LateContext<'tcx>, b: &'tcx Body<'_>) {
     fn check_body_post(&mut self, cx: &LateContext<'tcx>, b: &'tcx Body<'_>) {
         self.arithmetic_context.body_post(cx, b);
     }
+
+    extract_msrv_attr!(LateContext);
 }
 
 fn macro_with_not_op(e: &Expr<'_>) -> bool {
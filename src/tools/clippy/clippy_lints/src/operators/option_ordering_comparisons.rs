@@ -0,0 +1,36 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::{is_res_lang_ctor, path_res};
+use rustc_hir::{BinOpKind, Expr, ExprKind, LangItem};
+use rustc_lint::LateContext;
+
+use super::OPTION_ORDERING_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if let Some((Rel::Lt | Rel::Le, left, right)) = normalize_comparison(op, lhs, rhs)
+        && (is_option_ctor(cx, left) || is_option_ctor(cx, right))
+    {
+        span_lint_and_help(
+            cx,
+            OPTION_ORDERING_COMPARISONS,
+            expr.span,
+            "ordering comparison with an `Option` constructor",
+            None,
+            "`None` is less than any `Some`, consider matching on the `Option` to make the intent explicit",
+        );
+    }
+}
+
+/// Checks if `expr` is `None` or `Some(..)`.
+fn is_option_ctor(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Call(callee, [_]) => is_res_lang_ctor(cx, path_res(cx, callee), LangItem::OptionSome),
+        _ => is_res_lang_ctor(cx, path_res(cx, expr), LangItem::OptionNone),
+    }
+}
@@ -0,0 +1,62 @@
+use clippy_utils::comparisons::{difference_sign, ordering_is_less, DifferenceSign};
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::source::snippet;
+use clippy_utils::{eq_expr_value, get_parent_expr};
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::ORDERING_BY_SUBTRACTION;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if let Some(sign) = difference_sign(cx, op, lhs, rhs)
+        && matches!(sign.op, BinOpKind::Lt | BinOpKind::Le | BinOpKind::Gt | BinOpKind::Ge)
+        && let Some(if_expr) = if_with_condition(cx, expr)
+        && let ExprKind::If(_, then, _) = if_expr.kind
+        && ordering_is_less(cx, then).is_some()
+        && !is_else_if_on_same_difference(cx, if_expr, &sign)
+    {
+        let minuend = snippet(cx, sign.minuend.span, "..");
+        let subtrahend = snippet(cx, sign.subtrahend.span, "..");
+        span_lint_and_help(
+            cx,
+            ORDERING_BY_SUBTRACTION,
+            expr.span,
+            "ordering values by the sign of their difference",
+            None,
+            &format!("`{minuend} - {subtrahend}` can overflow, compare the values directly instead"),
+        );
+    }
+}
+
+/// Returns the `if` expression that has `cond` as its condition.
+fn if_with_condition<'tcx>(cx: &LateContext<'tcx>, cond: &Expr<'_>) -> Option<&'tcx Expr<'tcx>> {
+    // The condition of an `if` is wrapped in `DropTemps`.
+    let drop_temps = get_parent_expr(cx, cond)?;
+    let if_expr = get_parent_expr(cx, drop_temps)?;
+    match if_expr.kind {
+        ExprKind::If(if_cond, ..) if if_cond.hir_id == drop_temps.hir_id => Some(if_expr),
+        _ => None,
+    }
+}
+
+/// Checks if `if_expr` is the `else if` of an `if` that checks the sign of the same difference, so
+/// that the whole chain is linted only once.
+fn is_else_if_on_same_difference(cx: &LateContext<'_>, if_expr: &Expr<'_>, sign: &DifferenceSign<'_>) -> bool {
+    if let Some(parent) = get_parent_expr(cx, if_expr)
+        && let ExprKind::If(cond, _, Some(els)) = parent.kind
+        && els.hir_id == if_expr.hir_id
+        && let ExprKind::Binary(op, lhs, rhs) = cond.peel_drop_temps().kind
+        && let Some(parent_sign) = difference_sign(cx, op.node, lhs, rhs)
+    {
+        eq_expr_value(cx, sign.minuend, parent_sign.minuend)
+            && eq_expr_value(cx, sign.subtrahend, parent_sign.subtrahend)
+    } else {
+        false
+    }
+}
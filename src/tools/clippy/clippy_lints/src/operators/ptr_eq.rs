@@ -1,8 +1,9 @@
-use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::source::snippet_opt;
 use rustc_errors::Applicability;
 use rustc_hir::{BinOpKind, Expr, ExprKind};
 use rustc_lint::LateContext;
+use rustc_middle::ty::Ty;
 
 use super::PTR_EQ;
 
@@ -15,7 +16,7 @@ pub(super) fn check<'tcx>(
     left: &'tcx Expr<'_>,
     right: &'tcx Expr<'_>,
 ) {
-    if BinOpKind::Eq == op {
+    if matches!(op, BinOpKind::Eq | BinOpKind::Ne) {
         let (left, right) = match (expr_as_cast_to_usize(cx, left), expr_as_cast_to_usize(cx, right)) {
             (Some(lhs), Some(rhs)) => (lhs, rhs),
             _ => (left, right),
@@ -26,19 +27,40 @@ pub(super) fn check<'tcx>(
             && let Some(left_snip) = snippet_opt(cx, left_var.span)
             && let Some(right_snip) = snippet_opt(cx, right_var.span)
         {
-            span_lint_and_sugg(
-                cx,
-                PTR_EQ,
-                expr.span,
-                LINT_MSG,
-                "try",
-                format!("std::ptr::eq({left_snip}, {right_snip})"),
-                Applicability::MachineApplicable,
-            );
+            let negation = if op == BinOpKind::Ne { "!" } else { "" };
+            span_lint_and_then(cx, PTR_EQ, expr.span, LINT_MSG, |diag| {
+                diag.span_suggestion(
+                    expr.span,
+                    "try",
+                    format!("{negation}std::ptr::eq({left_snip}, {right_snip})"),
+                    Applicability::MachineApplicable,
+                );
+                if pointer_kind(cx, cx.typeck_results().expr_ty(left)) == Some(PointerKind::Fat) {
+                    diag.note("these are fat pointers, so their metadata (a length or vtable) is compared as well");
+                }
+            });
         }
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PointerKind {
+    /// A pointer that only consists of an address.
+    Thin,
+    /// A pointer to an unsized type, which also carries a length or vtable.
+    Fat,
+}
+
+/// Returns the kind of pointer `ty` is, or `None` if it is not a pointer.
+fn pointer_kind<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> Option<PointerKind> {
+    let pointee = ty.builtin_deref(true)?.ty;
+    if pointee.is_sized(cx.tcx, cx.param_env) {
+        Some(PointerKind::Thin)
+    } else {
+        Some(PointerKind::Fat)
+    }
+}
+
 // If the given expression is a cast to a usize, return the lhs of the cast
 // E.g., `foo as *const _ as usize` returns `foo as *const _`.
 fn expr_as_cast_to_usize<'tcx>(cx: &LateContext<'tcx>, cast_expr: &'tcx Expr<'_>) -> Option<&'tcx Expr<'tcx>> {
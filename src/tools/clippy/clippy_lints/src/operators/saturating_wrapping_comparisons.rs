@@ -0,0 +1,122 @@
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::source::snippet;
+use clippy_utils::{eq_expr_value, sext};
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::SATURATING_WRAPPING_COMPARISONS;
+
+/// What is known about `x.method(c) <op> x` for a positive constant `c`.
+#[derive(Clone, Copy)]
+enum Outcome {
+    /// The comparison always evaluates to the given value.
+    Always(bool),
+    /// The comparison looks like it is always true, but is false if the operation wraps around.
+    FalseOnWrap,
+}
+
+/// The comparisons of `x.method(c)` with `x`, oriented as `x.method(c) <op> x`, that are either
+/// provably constant or only fail on wraparound. `c` has to be a positive constant.
+const TABLE: &[(&str, BinOpKind, Outcome)] = &[
+    ("saturating_add", BinOpKind::Ge, Outcome::Always(true)),
+    ("saturating_add", BinOpKind::Lt, Outcome::Always(false)),
+    ("saturating_sub", BinOpKind::Le, Outcome::Always(true)),
+    ("saturating_sub", BinOpKind::Gt, Outcome::Always(false)),
+    ("wrapping_add", BinOpKind::Eq, Outcome::Always(false)),
+    ("wrapping_add", BinOpKind::Ne, Outcome::Always(true)),
+    ("wrapping_add", BinOpKind::Gt, Outcome::FalseOnWrap),
+    ("wrapping_add", BinOpKind::Ge, Outcome::FalseOnWrap),
+    ("wrapping_sub", BinOpKind::Eq, Outcome::Always(false)),
+    ("wrapping_sub", BinOpKind::Ne, Outcome::Always(true)),
+    ("wrapping_sub", BinOpKind::Lt, Outcome::FalseOnWrap),
+    ("wrapping_sub", BinOpKind::Le, Outcome::FalseOnWrap),
+];
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((rel, left, right)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    // Orient the comparison as `x.method(c) <op> x`.
+    let (call, op) = if is_arithmetic_on(cx, left, right) {
+        let op = match rel {
+            Rel::Lt => BinOpKind::Lt,
+            Rel::Le => BinOpKind::Le,
+            Rel::Eq => BinOpKind::Eq,
+            Rel::Ne => BinOpKind::Ne,
+        };
+        (left, op)
+    } else if is_arithmetic_on(cx, right, left) {
+        let op = match rel {
+            Rel::Lt => BinOpKind::Gt,
+            Rel::Le => BinOpKind::Ge,
+            Rel::Eq => BinOpKind::Eq,
+            Rel::Ne => BinOpKind::Ne,
+        };
+        (right, op)
+    } else {
+        return;
+    };
+    let ExprKind::MethodCall(path, receiver, [arg], _) = call.kind else {
+        return;
+    };
+    let method = path.ident.as_str();
+    if let Some(&(_, _, outcome)) = TABLE
+        .iter()
+        .find(|&&(name, table_op, _)| name == method && table_op == op)
+        && is_positive_constant(cx, arg)
+    {
+        let call = snippet(cx, call.span, "..");
+        let receiver = snippet(cx, receiver.span, "..");
+        let (msg, note) = match outcome {
+            Outcome::Always(value) => (
+                format!("this comparison is always {value}"),
+                match method {
+                    "saturating_add" => format!("`{call}` is never less than `{receiver}`"),
+                    "saturating_sub" => format!("`{call}` is never greater than `{receiver}`"),
+                    _ => format!("`{call}` is never equal to `{receiver}`"),
+                },
+            ),
+            Outcome::FalseOnWrap => (
+                "this comparison is false if the operation wraps around".to_string(),
+                format!(
+                    "`{call}` wraps around on overflow, use `{}` to detect that",
+                    method.replace("wrapping", "checked")
+                ),
+            ),
+        };
+        span_lint_and_note(cx, SATURATING_WRAPPING_COMPARISONS, expr.span, &msg, None, &note);
+    }
+}
+
+/// Checks if `call` is a call of an inherent integer method on a receiver equal to `operand`.
+fn is_arithmetic_on(cx: &LateContext<'_>, call: &Expr<'_>, operand: &Expr<'_>) -> bool {
+    if let ExprKind::MethodCall(_, receiver, [_], _) = call.kind
+        && let Some(method_id) = cx.typeck_results().type_dependent_def_id(call.hir_id)
+        && let Some(impl_id) = cx.tcx.impl_of_method(method_id)
+        && cx.tcx.type_of(impl_id).instantiate_identity().is_integral()
+    {
+        eq_expr_value(cx, receiver, operand)
+    } else {
+        false
+    }
+}
+
+fn is_positive_constant(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    match (
+        constant(cx, cx.typeck_results(), expr),
+        cx.typeck_results().expr_ty(expr).kind(),
+    ) {
+        (Some(Constant::Int(n)), ty::Int(ity)) => sext(cx.tcx, n, *ity) > 0,
+        (Some(Constant::Int(n)), ty::Uint(_)) => n > 0,
+        _ => false,
+    }
+}
@@ -0,0 +1,34 @@
+use clippy_utils::comparisons::{single_bit_comparison, SingleBitComparison};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::sugg::Sugg;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr};
+use rustc_lint::LateContext;
+
+use super::SINGLE_BIT_EQUALITY;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    if let Some(SingleBitComparison { is_eq, value, bit }) = single_bit_comparison(cx, op, lhs, rhs) {
+        // Testing the bit has a different result if other bits are set, so this is only a guess.
+        let mut app = Applicability::MaybeIncorrect;
+        let value = Sugg::hir_with_applicability(cx, value, "..", &mut app).maybe_par();
+        let bit = snippet_with_applicability(cx, bit.span, "..", &mut app);
+        let op = if is_eq { "!=" } else { "==" };
+        span_lint_and_sugg(
+            cx,
+            SINGLE_BIT_EQUALITY,
+            expr.span,
+            "comparing a value for equality with a single bit",
+            "if this is meant to test the bit, use a bit mask",
+            format!("{value} & ({bit}) {op} 0"),
+            app,
+        );
+    }
+}
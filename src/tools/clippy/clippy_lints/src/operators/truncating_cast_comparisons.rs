@@ -0,0 +1,150 @@
+use super::asymmetric_cast_comparisons::{int_range, IntRange};
+use clippy_utils::comparisons::{normalize_comparison, Rel};
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_note;
+use clippy_utils::source::snippet;
+use clippy_utils::{int_bits, sext};
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty, UintTy};
+
+use super::TRUNCATING_CAST_COMPARISONS;
+
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'_>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'_>,
+    rhs: &'tcx Expr<'_>,
+) {
+    let Some((rel @ (Rel::Eq | Rel::Ne), left, right)) = normalize_comparison(op, lhs, rhs) else {
+        return;
+    };
+    let Some((truncation, other)) = find_truncation(cx, left)
+        .map(|truncation| (truncation, right))
+        .or_else(|| find_truncation(cx, right).map(|truncation| (truncation, left)))
+    else {
+        return;
+    };
+    let typeck = cx.typeck_results();
+    if constant(cx, typeck, truncation.inner).is_some() {
+        return;
+    }
+    let inner = snippet(cx, truncation.inner.span, "..");
+    let (to, bits) = (truncation.to, truncation.bits);
+    if let Some(value) = constant_value(cx, other) {
+        // Comparing with a constant that fits is usually deliberate, e.g. `x as u8 == b'a'`.
+        if !value.fits(&truncation.range) {
+            span_lint_and_note(
+                cx,
+                TRUNCATING_CAST_COMPARISONS,
+                expr.span,
+                &format!("this comparison is always {}", rel == Rel::Ne),
+                None,
+                &format!("`{inner}` is truncated to `{to}` first, which cannot hold `{value}`"),
+            );
+        }
+    } else if (truncation.widened || matches!(other.kind, ExprKind::Cast(..)))
+        // Comparing two values that are truncated the same way is usually deliberate, and
+        // comparing a single cast with an uncast value is `ASYMMETRIC_CAST_COMPARISONS`.
+        && find_truncation(cx, other).is_none()
+    {
+        span_lint_and_note(
+            cx,
+            TRUNCATING_CAST_COMPARISONS,
+            expr.span,
+            &format!("this comparison only compares the low {bits} bits of `{inner}`"),
+            None,
+            &format!("`{inner}` is truncated to `{to}` before it is compared"),
+        );
+    }
+}
+
+/// A cast that drops the high bits of a value, possibly followed by casts that keep all values.
+struct Truncation<'tcx> {
+    /// The value that is truncated.
+    inner: &'tcx Expr<'tcx>,
+    /// The type it is truncated to.
+    to: Ty<'tcx>,
+    /// The values of `to`.
+    range: IntRange,
+    /// The number of bits of `to`.
+    bits: u64,
+    /// Whether the truncated value is cast to a wider type again.
+    widened: bool,
+}
+
+/// Returns the truncation if `expr` is a cast to an integer type with fewer bits, possibly
+/// followed by casts that keep all values, like `x as u8 as u32` with `x: u64`.
+fn find_truncation<'tcx>(cx: &LateContext<'tcx>, mut expr: &'tcx Expr<'tcx>) -> Option<Truncation<'tcx>> {
+    let typeck = cx.typeck_results();
+    let mut widened = false;
+    while let ExprKind::Cast(inner, _) = expr.kind
+        && !expr.span.from_expansion()
+    {
+        let (from, to) = (typeck.expr_ty(inner), typeck.expr_ty(expr));
+        let to_bits = bits(cx, to)?;
+        if to_bits < bits(cx, from)? {
+            return Some(Truncation {
+                inner,
+                to,
+                range: int_range(cx, to)?,
+                bits: to_bits,
+                widened,
+            });
+        }
+        let (from_range, to_range) = (int_range(cx, from)?, int_range(cx, to)?);
+        if from_range.min < to_range.min || from_range.max > to_range.max {
+            return None;
+        }
+        expr = inner;
+        widened = true;
+    }
+    None
+}
+
+/// Returns the number of bits of an integer type.
+fn bits(cx: &LateContext<'_>, ty: Ty<'_>) -> Option<u64> {
+    match *ty.kind() {
+        ty::Int(ity) => Some(int_bits(cx.tcx, ity)),
+        ty::Uint(UintTy::Usize) => Some(cx.tcx.data_layout.pointer_size.bits()),
+        ty::Uint(uty) => uty.bit_width(),
+        _ => None,
+    }
+}
+
+/// The value of an integer constant.
+enum IntValue {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl IntValue {
+    fn fits(&self, range: &IntRange) -> bool {
+        match *self {
+            IntValue::Signed(value) => value >= range.min && (value < 0 || value.unsigned_abs() <= range.max),
+            IntValue::Unsigned(value) => value <= range.max,
+        }
+    }
+}
+
+impl std::fmt::Display for IntValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntValue::Signed(value) => value.fmt(f),
+            IntValue::Unsigned(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Returns the value of `expr` if it is an integer constant.
+fn constant_value(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<IntValue> {
+    let Some(Constant::Int(value)) = constant(cx, cx.typeck_results(), expr) else {
+        return None;
+    };
+    match *cx.typeck_results().expr_ty(expr).kind() {
+        ty::Int(ity) => Some(IntValue::Signed(sext(cx.tcx, value, ity))),
+        ty::Uint(_) => Some(IntValue::Unsigned(value)),
+        _ => None,
+    }
+}
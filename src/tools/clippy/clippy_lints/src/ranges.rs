@@ -1,12 +1,13 @@
 use clippy_config::msrvs::{self, Msrv};
+use clippy_utils::comparisons::two_sided_bounds;
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::source::{snippet, snippet_opt, snippet_with_applicability};
 use clippy_utils::sugg::Sugg;
 use clippy_utils::{get_parent_expr, higher, in_constant, is_integer_const, path_to_local};
-use rustc_ast::ast::RangeLimits;
+use rustc_ast::ast::{LitKind, RangeLimits};
 use rustc_errors::Applicability;
-use rustc_hir::{BinOpKind, Expr, ExprKind, HirId};
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
@@ -159,6 +160,37 @@
     "manually reimplementing {`Range`, `RangeInclusive`}`::contains`"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for comparison chains like `x >= 'a' && x <= 'z'` on a `char` or integer
+    /// where both bounds are literals, which could be written as `matches!(x, 'a'..='z')`.
+    ///
+    /// ### Why is this bad?
+    /// A range pattern states the accepted values in one place and can't accidentally
+    /// mix up the comparison operators.
+    ///
+    /// ### Known problems
+    /// Exclusive range patterns are unstable, so a chain with an exclusive upper bound is
+    /// only linted if the `exclusive_range_pattern` feature is enabled. There is no pattern
+    /// for an exclusive lower bound, so such chains are never linted. In const contexts, chains
+    /// are only linted if the MSRV allows `match` there.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # let c = 'x';
+    /// let is_lower = c >= 'a' && c <= 'z';
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// # let c = 'x';
+    /// let is_lower = matches!(c, 'a'..='z');
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub MANUAL_RANGE_MATCHES,
+    pedantic,
+    "comparing a value against two literal bounds instead of matching a range pattern"
+}
+
 pub struct Ranges {
     msrv: Msrv,
 }
@@ -175,6 +207,7 @@ pub fn new(msrv: Msrv) -> Self {
     RANGE_MINUS_ONE,
     REVERSED_EMPTY_RANGES,
     MANUAL_RANGE_CONTAINS,
+    MANUAL_RANGE_MATCHES,
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Ranges {
@@ -183,6 +216,7 @@ fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
             if self.msrv.meets(msrvs::RANGE_CONTAINS) {
                 check_possible_range_contains(cx, op.node, l, r, expr, expr.span);
             }
+            check_manual_range_matches(cx, op.node, l, r, expr, &self.msrv);
         }
 
         check_exclusive_range_plus_one(cx, expr);
@@ -293,6 +327,55 @@ fn check_possible_range_contains(
     }
 }
 
+fn check_manual_range_matches<'tcx>(
+    cx: &LateContext<'tcx>,
+    op: BinOpKind,
+    left: &'tcx Expr<'_>,
+    right: &'tcx Expr<'_>,
+    expr: &Expr<'_>,
+    msrv: &Msrv,
+) {
+    if !expr.span.from_expansion()
+        && msrv.meets(msrvs::MATCHES_MACRO)
+        // `matches!` expands to a `match`, which needs a newer Rust version in const contexts.
+        && (!in_constant(cx, expr.hir_id) || msrv.meets(msrvs::CONST_IF_MATCH))
+        && let Some(bounds) = two_sided_bounds(cx, op, left, right)
+        // There is no pattern for an exclusive lower bound.
+        && bounds.lower_inclusive
+        && (bounds.upper_inclusive || cx.tcx.features().exclusive_range_pattern)
+        && is_pattern_literal(bounds.lower)
+        && is_pattern_literal(bounds.upper)
+        && let ty = cx.typeck_results().expr_ty(bounds.value)
+        && (ty.is_char() || ty.is_integral())
+    {
+        let mut applicability = Applicability::MachineApplicable;
+        let value = snippet_with_applicability(cx, bounds.value.span, "_", &mut applicability);
+        let lower = snippet_with_applicability(cx, bounds.lower.span, "_", &mut applicability);
+        let upper = snippet_with_applicability(cx, bounds.upper.span, "_", &mut applicability);
+        let range_op = if bounds.upper_inclusive { "..=" } else { ".." };
+        span_lint_and_sugg(
+            cx,
+            MANUAL_RANGE_MATCHES,
+            expr.span,
+            "manual range pattern check",
+            "use",
+            format!("matches!({value}, {lower}{range_op}{upper})"),
+            applicability,
+        );
+    }
+}
+
+/// Checks if `expr` is a literal that can be used as the bound of a range pattern.
+fn is_pattern_literal(expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Lit(lit) => matches!(lit.node, LitKind::Int(..) | LitKind::Char(_)),
+        ExprKind::Unary(UnOp::Neg, inner) => {
+            matches!(inner.kind, ExprKind::Lit(lit) if matches!(lit.node, LitKind::Int(..)))
+        },
+        _ => false,
+    }
+}
+
 struct RangeBounds<'a, 'tcx> {
     val: Constant<'tcx>,
     expr: &'a Expr<'a>,
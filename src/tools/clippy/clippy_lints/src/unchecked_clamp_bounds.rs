@@ -0,0 +1,97 @@
+use clippy_utils::comparisons::normalize_comparison;
+use clippy_utils::consts::constant;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{eq_expr_value, is_trait_method};
+use core::ops::ControlFlow;
+use rustc_hir::{Body, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::sym;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls of `clamp` with bounds that are not constant, where nothing earlier in
+    /// the function compares the bounds with each other.
+    ///
+    /// ### Why is this bad?
+    /// `clamp` panics if the lower bound is greater than the upper one (or, for floats, if either
+    /// of them is `NaN`). Without a check like `assert!(lo <= hi)`, it is easy to overlook that
+    /// the bounds can end up out of order.
+    ///
+    /// ### Known problems
+    /// Any earlier comparison of the two bounds in the same function counts as a check, no
+    /// matter what happens with its result. Bounds that are in order for other reasons, e.g.
+    /// because of how they are computed, are still linted.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// fn fit(x: u32, lo: u32, hi: u32) -> u32 {
+    ///     x.clamp(lo, hi)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// fn fit(x: u32, lo: u32, hi: u32) -> u32 {
+    ///     assert!(lo <= hi, "invalid bounds");
+    ///     x.clamp(lo, hi)
+    /// }
+    /// ```
+    #[clippy::version = "1.76.0"]
+    pub UNCHECKED_CLAMP_BOUNDS,
+    restriction,
+    "calling `clamp` with bounds that are not checked to be in order"
+}
+
+declare_lint_pass!(UncheckedClampBounds => [UNCHECKED_CLAMP_BOUNDS]);
+
+impl<'tcx> LateLintPass<'tcx> for UncheckedClampBounds {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if let ExprKind::MethodCall(path, receiver, [lo, hi], _) = expr.kind
+            && path.ident.as_str() == "clamp"
+            && !expr.span.from_expansion()
+            && (is_trait_method(cx, expr, sym::Ord) || cx.typeck_results().expr_ty(receiver).is_floating_point())
+            && (constant(cx, cx.typeck_results(), lo).is_none() || constant(cx, cx.typeck_results(), hi).is_none())
+            && !eq_expr_value(cx, lo, hi)
+            && let Some(body_id) = cx.enclosing_body
+            && !is_compared_before(cx, cx.tcx.hir().body(body_id), expr, lo, hi)
+        {
+            span_lint_and_then(
+                cx,
+                UNCHECKED_CLAMP_BOUNDS,
+                expr.span,
+                "calling `clamp` with bounds that are not checked to be in order",
+                |diag| {
+                    let lo = snippet(cx, lo.span, "..");
+                    let hi = snippet(cx, hi.span, "..");
+                    diag.note(format!("this panics if `{lo}` is greater than `{hi}`"));
+                    diag.help(format!("check the bounds first, e.g. with `assert!({lo} <= {hi})`"));
+                },
+            );
+        }
+    }
+}
+
+/// Returns whether `body` contains a comparison of `lo` with `hi` that comes before `call`.
+fn is_compared_before<'tcx>(
+    cx: &LateContext<'tcx>,
+    body: &'tcx Body<'tcx>,
+    call: &Expr<'_>,
+    lo: &Expr<'_>,
+    hi: &Expr<'_>,
+) -> bool {
+    for_each_expr(body.value, |e| {
+        if let ExprKind::Binary(op, left, right) = e.kind
+            && e.span.hi() <= call.span.lo()
+            && normalize_comparison(op.node, left, right).is_some()
+            && ((eq_expr_value(cx, left, lo) && eq_expr_value(cx, right, hi))
+                || (eq_expr_value(cx, left, hi) && eq_expr_value(cx, right, lo)))
+        {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .is_some()
+}
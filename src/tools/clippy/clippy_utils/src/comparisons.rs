@@ -2,7 +2,21 @@
 
 #![deny(clippy::missing_docs_in_private_items)]
 
-use rustc_hir::{BinOpKind, Expr};
+use crate::consts::{constant, Constant};
+use crate::ty::is_type_diagnostic_item;
+use crate::visitors::{for_each_expr, is_local_used, Descend};
+use crate::{eq_expr_value, is_res_lang_ctor, is_trait_method, path_res, path_to_local, path_to_local_id, peel_blocks};
+use core::ops::ControlFlow;
+use rustc_ast::ast::LitKind;
+use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
+use rustc_hir::{
+    BinOpKind, Body, BorrowKind, Expr, ExprKind, HirId, Impl, ImplItemKind, LangItem, Pat, PatKind, PathSegment, QPath,
+    UnOp,
+};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_middle::ty::adjustment::Adjust;
+use rustc_span::{sym, Span, Symbol};
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 /// Represents a normalized comparison operator.
@@ -34,3 +48,876 @@ pub fn normalize_comparison<'a>(
         _ => None,
     }
 }
+
+/// Like `normalize_comparison`, but also removes borrows and dereferences of references that
+/// both operands start with, so `&a < &b` and `*a < *b` give `a` and `b`. Comparing references
+/// compares the values behind them, so the result is the same. Peeling stops as soon as the
+/// operands differ, e.g. in `&*a < b`, and at dereferences of raw pointers and overloaded
+/// dereferences. Note that the returned operands may still be references.
+pub fn normalize_comparison_peeled<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<(Rel, &'a Expr<'a>, &'a Expr<'a>)> {
+    let typeck = cx.typeck_results();
+    let (rel, mut lhs, mut rhs) = normalize_comparison(op, lhs, rhs)?;
+    loop {
+        match (lhs.kind, rhs.kind) {
+            (ExprKind::AddrOf(BorrowKind::Ref, l_mut, l_inner), ExprKind::AddrOf(BorrowKind::Ref, r_mut, r_inner))
+                if l_mut == r_mut =>
+            {
+                (lhs, rhs) = (l_inner, r_inner);
+            },
+            (ExprKind::Unary(UnOp::Deref, l_inner), ExprKind::Unary(UnOp::Deref, r_inner))
+                if typeck.expr_ty(l_inner).is_ref() && typeck.expr_ty(r_inner).is_ref() =>
+            {
+                (lhs, rhs) = (l_inner, r_inner);
+            },
+            _ => return Some((rel, lhs, rhs)),
+        }
+    }
+}
+
+/// Checks if the expression is an ordering comparison of two floats (or references to floats),
+/// such as `a < b` or `a > b`, and returns it normalized like `normalize_comparison` does.
+pub fn float_ordering_comparison<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<(Rel, &'a Expr<'a>, &'a Expr<'a>)> {
+    let is_float = |expr: &Expr<'_>| cx.typeck_results().expr_ty(expr).peel_refs().is_floating_point();
+    match normalize_comparison(op, lhs, rhs)? {
+        (rel @ (Rel::Lt | Rel::Le), lhs, rhs) if is_float(lhs) && is_float(rhs) => Some((rel, lhs, rhs)),
+        _ => None,
+    }
+}
+
+/// The bounds of a two-sided comparison chain, e.g. `lo <= x && x < hi`.
+#[derive(Debug, Clone, Copy)]
+pub struct TwoSidedBounds<'a> {
+    /// The expression that is compared against both bounds.
+    pub value: &'a Expr<'a>,
+    /// The lower bound.
+    pub lower: &'a Expr<'a>,
+    /// Whether `value` may be equal to the lower bound.
+    pub lower_inclusive: bool,
+    /// The upper bound.
+    pub upper: &'a Expr<'a>,
+    /// Whether `value` may be equal to the upper bound.
+    pub upper_inclusive: bool,
+}
+
+/// Extracts the bounds of a conjunction of two ordering comparisons on the same side-effect free
+/// value, such as `x >= lo && x < hi`. Either comparison can come first and each can have the
+/// value on either side.
+pub fn two_sided_bounds<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<TwoSidedBounds<'a>> {
+    /// Normalizes `expr` into `lhs < rhs` or `lhs <= rhs`, returning whether it is inclusive.
+    fn ordering<'a>(expr: &'a Expr<'a>) -> Option<(bool, &'a Expr<'a>, &'a Expr<'a>)> {
+        if let ExprKind::Binary(op, lhs, rhs) = expr.kind {
+            match normalize_comparison(op.node, lhs, rhs)? {
+                (Rel::Lt, lhs, rhs) => Some((false, lhs, rhs)),
+                (Rel::Le, lhs, rhs) => Some((true, lhs, rhs)),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    if op != BinOpKind::And {
+        return None;
+    }
+    let (first_inclusive, first_lhs, first_rhs) = ordering(lhs)?;
+    let (second_inclusive, second_lhs, second_rhs) = ordering(rhs)?;
+    if eq_expr_value(cx, first_lhs, second_rhs) {
+        // `value < upper && lower < value`
+        Some(TwoSidedBounds {
+            value: first_lhs,
+            lower: second_lhs,
+            lower_inclusive: second_inclusive,
+            upper: first_rhs,
+            upper_inclusive: first_inclusive,
+        })
+    } else if eq_expr_value(cx, first_rhs, second_lhs) {
+        // `lower < value && value < upper`
+        Some(TwoSidedBounds {
+            value: first_rhs,
+            lower: first_lhs,
+            lower_inclusive: first_inclusive,
+            upper: second_rhs,
+            upper_inclusive: second_inclusive,
+        })
+    } else {
+        None
+    }
+}
+
+/// The parity of an integer.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Parity {
+    /// Divisible by two.
+    Even,
+    /// Not divisible by two.
+    Odd,
+}
+
+/// What a comparison of `x & 1` against a constant tests.
+#[derive(Debug, Clone, Copy)]
+pub enum ParityComparison<'a> {
+    /// The comparison holds exactly when `value` has the given parity, e.g. `(value & 1) == 0`.
+    Tests {
+        /// The value whose lowest bit is tested.
+        value: &'a Expr<'a>,
+        /// The parity the comparison holds for.
+        parity: Parity,
+    },
+    /// The comparison always evaluates to the given value since `x & 1` can only be `0` or `1`,
+    /// e.g. `(x & 1) == 2`.
+    Always(bool),
+}
+
+/// Checks if the expression is an equality comparison of an integer masked with `1` against a
+/// constant, such as `(x & 1) == 0`, `1 != (1 & x)` or `(x & 1) == 2`, and returns what it tests.
+pub fn parity_comparison<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<ParityComparison<'a>> {
+    /// Returns the value of `expr` if it is an integer constant.
+    fn int_constant(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<u128> {
+        match constant(cx, cx.typeck_results(), expr)? {
+            Constant::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns `x` if `expr` is `x & 1` or `1 & x`.
+    fn masked_with_one<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+        if let ExprKind::Binary(op, left, right) = expr.kind
+            && op.node == BinOpKind::BitAnd
+        {
+            if int_constant(cx, right) == Some(1) {
+                Some(left)
+            } else if int_constant(cx, left) == Some(1) {
+                Some(right)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    let (rel, lhs, rhs) = normalize_comparison(op, lhs, rhs)?;
+    if !matches!(rel, Rel::Eq | Rel::Ne) {
+        return None;
+    }
+    let (value, cmp_value) = if let Some(value) = masked_with_one(cx, lhs) {
+        (value, int_constant(cx, rhs)?)
+    } else {
+        (masked_with_one(cx, rhs)?, int_constant(cx, lhs)?)
+    };
+    let is_eq = rel == Rel::Eq;
+    Some(match cmp_value {
+        0 | 1 => ParityComparison::Tests {
+            value,
+            // `== 0` and `!= 1` test for even values
+            parity: if is_eq == (cmp_value == 0) {
+                Parity::Even
+            } else {
+                Parity::Odd
+            },
+        },
+        _ => ParityComparison::Always(!is_eq),
+    })
+}
+
+/// An equality comparison of a value with a single bit, e.g. `x == 1 << k`.
+#[derive(Debug, Clone, Copy)]
+pub struct SingleBitComparison<'a> {
+    /// Whether the comparison is `==` rather than `!=`.
+    pub is_eq: bool,
+    /// The value compared with the bit.
+    pub value: &'a Expr<'a>,
+    /// The `1 << k` expression.
+    pub bit: &'a Expr<'a>,
+}
+
+/// Checks if the expression is an `==` or `!=` comparison of a non-constant integer with `1 << k`,
+/// with the shift on either side.
+pub fn single_bit_comparison<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<SingleBitComparison<'a>> {
+    /// Checks if `expr` is `1 << k`.
+    fn is_single_bit(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+        if let ExprKind::Binary(op, one, _) = expr.kind
+            && op.node == BinOpKind::Shl
+        {
+            matches!(constant(cx, cx.typeck_results(), one), Some(Constant::Int(1)))
+        } else {
+            false
+        }
+    }
+
+    let (rel, lhs, rhs) = normalize_comparison(op, lhs, rhs)?;
+    if !matches!(rel, Rel::Eq | Rel::Ne) || !cx.typeck_results().expr_ty(lhs).is_integral() {
+        return None;
+    }
+    let (value, bit) = if is_single_bit(cx, rhs) {
+        (lhs, rhs)
+    } else if is_single_bit(cx, lhs) {
+        (rhs, lhs)
+    } else {
+        return None;
+    };
+    if constant(cx, cx.typeck_results(), value).is_some() || is_single_bit(cx, value) {
+        return None;
+    }
+    Some(SingleBitComparison {
+        is_eq: rel == Rel::Eq,
+        value,
+        bit,
+    })
+}
+
+/// A power of two test written out with bit operations, e.g. `x != 0 && x & (x - 1) == 0`.
+#[derive(Debug, Clone, Copy)]
+pub struct PowerOfTwoTest<'a> {
+    /// The value that is tested.
+    pub value: &'a Expr<'a>,
+    /// Whether this tests that `value` is *not* a power of two, as in
+    /// `x == 0 || x & (x - 1) != 0`.
+    pub is_negated: bool,
+}
+
+/// Checks if `lhs && rhs` or `lhs || rhs` tests whether an unsigned integer is a power of two,
+/// i.e. if it is `x != 0 && x & (x - 1) == 0` or `x == 0 || x & (x - 1) != 0`, in any order and
+/// with the operands of `!=`, `==` and `&` in any order. `x != 0` may also be written as `x > 0`.
+pub fn power_of_two_test<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<PowerOfTwoTest<'a>> {
+    /// Returns the value compared with zero and whether the comparison checks that it is zero,
+    /// if `expr` is such a comparison.
+    fn zero_comparison<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<(&'a Expr<'a>, bool)> {
+        let ExprKind::Binary(op, lhs, rhs) = expr.kind else {
+            return None;
+        };
+        let is_zero = |expr: &Expr<'_>| matches!(constant(cx, cx.typeck_results(), expr), Some(Constant::Int(0)));
+        match normalize_comparison(op.node, lhs, rhs)? {
+            (rel @ (Rel::Eq | Rel::Ne), value, zero) if is_zero(zero) => Some((value, rel == Rel::Eq)),
+            (rel @ (Rel::Eq | Rel::Ne), zero, value) if is_zero(zero) => Some((value, rel == Rel::Eq)),
+            (Rel::Lt, zero, value) if is_zero(zero) => Some((value, false)),
+            _ => None,
+        }
+    }
+
+    /// Returns `x` if `expr` is `x & (x - 1)` or `(x - 1) & x`.
+    fn clear_lowest_bit<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+        let ExprKind::Binary(op, left, right) = expr.kind else {
+            return None;
+        };
+        if op.node != BinOpKind::BitAnd {
+            return None;
+        }
+        let minus_one = |x: &Expr<'_>, expr: &Expr<'_>| {
+            if let ExprKind::Binary(op, value, one) = expr.kind
+                && op.node == BinOpKind::Sub
+                && let Some(Constant::Int(1)) = constant(cx, cx.typeck_results(), one)
+            {
+                eq_expr_value(cx, x, value)
+            } else {
+                false
+            }
+        };
+        if minus_one(left, right) {
+            Some(left)
+        } else if minus_one(right, left) {
+            Some(right)
+        } else {
+            None
+        }
+    }
+
+    let is_negated = match op {
+        BinOpKind::And => false,
+        BinOpKind::Or => true,
+        _ => return None,
+    };
+    let (zero_test, mask_test) =
+        if zero_comparison(cx, lhs).is_some_and(|(value, _)| clear_lowest_bit(cx, value).is_none()) {
+            (lhs, rhs)
+        } else {
+            (rhs, lhs)
+        };
+    let (value, value_is_zero) = zero_comparison(cx, zero_test)?;
+    let (masked, masked_is_zero) = zero_comparison(cx, mask_test)?;
+    let masked = clear_lowest_bit(cx, masked)?;
+    // `x != 0 && x & (x - 1) == 0` or `x == 0 || x & (x - 1) != 0`
+    if value_is_zero == is_negated
+        && masked_is_zero != is_negated
+        && eq_expr_value(cx, value, masked)
+        && matches!(cx.typeck_results().expr_ty(value).kind(), ty::Uint(_))
+        && constant(cx, cx.typeck_results(), value).is_none()
+    {
+        Some(PowerOfTwoTest { value, is_negated })
+    } else {
+        None
+    }
+}
+
+/// A comparison of an unwrapped `Option` or `Result` with some bound, e.g. `opt.unwrap() > 0`.
+#[derive(Debug, Clone, Copy)]
+pub struct UnwrapComparison<'a> {
+    /// The `Option` or `Result` that is unwrapped.
+    pub receiver: &'a Expr<'a>,
+    /// Whether `receiver` is an `Option` rather than a `Result`.
+    pub is_option: bool,
+    /// The relation of the unwrapped value to `bound`, as in `receiver.unwrap() <op> bound`.
+    pub op: BinOpKind,
+    /// The value the unwrapped value is compared with.
+    pub bound: &'a Expr<'a>,
+}
+
+/// Checks if one side of the comparison is an `.unwrap()` or `.expect(..)` call on an `Option` or
+/// a `Result`, which may panic. The comparison is oriented so that the unwrapped value is on the
+/// left, i.e. `0 < opt.unwrap()` is returned as `opt.unwrap() > 0`.
+pub fn unwrap_comparison<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<UnwrapComparison<'a>> {
+    /// Returns the receiver of `expr` and whether it is an `Option` if `expr` is an unwrap.
+    fn unwrapped<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<(&'a Expr<'a>, bool)> {
+        if let ExprKind::MethodCall(path, receiver, args, _) = expr.kind
+            && matches!((path.ident.as_str(), args), ("unwrap", []) | ("expect", [_]))
+        {
+            let ty = cx.typeck_results().expr_ty(receiver).peel_refs();
+            if is_type_diagnostic_item(cx, ty, sym::Option) {
+                Some((receiver, true))
+            } else if is_type_diagnostic_item(cx, ty, sym::Result) {
+                Some((receiver, false))
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    if !op.is_comparison() {
+        return None;
+    }
+    let (receiver, is_option, op, bound) = if let Some((receiver, is_option)) = unwrapped(cx, lhs) {
+        (receiver, is_option, op, rhs)
+    } else {
+        let (receiver, is_option) = unwrapped(cx, rhs)?;
+        let op = match op {
+            BinOpKind::Lt => BinOpKind::Gt,
+            BinOpKind::Le => BinOpKind::Ge,
+            BinOpKind::Gt => BinOpKind::Lt,
+            BinOpKind::Ge => BinOpKind::Le,
+            _ => op,
+        };
+        (receiver, is_option, op, lhs)
+    };
+    Some(UnwrapComparison {
+        receiver,
+        is_option,
+        op,
+        bound,
+    })
+}
+
+/// An equality comparison of a closure parameter with a value that does not depend on it, e.g.
+/// the body of `|x| *x == target`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParamEquality<'a> {
+    /// Whether the comparison is `==` rather than `!=`.
+    pub is_eq: bool,
+    /// The side of the comparison that is the parameter, possibly dereferenced.
+    pub param: &'a Expr<'a>,
+    /// The side of the comparison that does not use the parameter.
+    pub other: &'a Expr<'a>,
+}
+
+/// Checks if the body of a closure with a single parameter is nothing but an `==` or `!=`
+/// comparison between that parameter (possibly dereferenced or bound with `&x`) and a value that
+/// does not use it. Any other body, even one that contains such a comparison, is rejected.
+pub fn param_equality<'a>(cx: &LateContext<'_>, body: &'a Body<'a>) -> Option<ParamEquality<'a>> {
+    let [param] = body.params else {
+        return None;
+    };
+    let (PatKind::Binding(_, id, _, None)
+    | PatKind::Ref(
+        Pat {
+            kind: PatKind::Binding(_, id, _, None),
+            ..
+        },
+        _,
+    )) = param.pat.kind
+    else {
+        return None;
+    };
+    let ExprKind::Binary(op, lhs, rhs) = peel_blocks(body.value).kind else {
+        return None;
+    };
+    let is_eq = match op.node {
+        BinOpKind::Eq => true,
+        BinOpKind::Ne => false,
+        _ => return None,
+    };
+    let is_param = |expr: &Expr<'_>| match expr.kind {
+        ExprKind::Unary(UnOp::Deref, inner) => path_to_local_id(inner, id),
+        _ => path_to_local_id(expr, id),
+    };
+    let (param, other) = if is_param(lhs) {
+        (lhs, rhs)
+    } else if is_param(rhs) {
+        (rhs, lhs)
+    } else {
+        return None;
+    };
+    (!is_local_used(cx, other, id)).then_some(ParamEquality { is_eq, param, other })
+}
+
+/// The `Iterator` method of a `SearchEquality`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMethod {
+    /// `Iterator::position`
+    Position,
+    /// `Iterator::find`
+    Find,
+}
+
+/// A search for an item that is equal to a value, e.g. `iter.position(|x| *x == target)`.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchEquality<'a> {
+    /// The method doing the search.
+    pub method: SearchMethod,
+    /// The searched iterator.
+    pub recv: &'a Expr<'a>,
+    /// The comparison in the closure, which is always `==`.
+    pub eq: ParamEquality<'a>,
+}
+
+/// Checks if `expr` is a call to `Iterator::position` or `Iterator::find` with a closure that
+/// compares the item with a value using `==`, as `param_equality` checks it.
+pub fn search_equality<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<SearchEquality<'a>> {
+    let ExprKind::MethodCall(path, recv, [arg], _) = expr.kind else {
+        return None;
+    };
+    let method = match path.ident.as_str() {
+        "position" => SearchMethod::Position,
+        "find" => SearchMethod::Find,
+        _ => return None,
+    };
+    if !is_trait_method(cx, expr, sym::Iterator) {
+        return None;
+    }
+    let ExprKind::Closure(closure) = arg.kind else {
+        return None;
+    };
+    let eq = param_equality(cx, cx.tcx.hir().body(closure.body))?;
+    eq.is_eq.then_some(SearchEquality { method, recv, eq })
+}
+
+/// Returns whether `expr` evaluates to `Ordering::Less`, if it evaluates to `Ordering::Less`
+/// or `Ordering::Greater`, possibly wrapped in `Some`.
+pub fn ordering_is_less(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<bool> {
+    let mut expr = peel_blocks(expr);
+    if let ExprKind::Call(callee, [arg]) = expr.kind
+        && is_res_lang_ctor(cx, path_res(cx, callee), LangItem::OptionSome)
+    {
+        expr = peel_blocks(arg);
+    }
+    if let Res::Def(DefKind::Ctor(CtorOf::Variant, _), ctor_id) = path_res(cx, expr)
+        && let variant_id = cx.tcx.parent(ctor_id)
+        && cx.tcx.is_diagnostic_item(sym::Ordering, cx.tcx.parent(variant_id))
+    {
+        match cx.tcx.item_name(variant_id).as_str() {
+            "Less" => Some(true),
+            "Greater" => Some(false),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// An ordering comparison between the same field of `self` and `other` in a method of an `Ord` or
+/// `PartialOrd` impl, e.g. `self.x < other.x`.
+#[derive(Debug, Clone, Copy)]
+pub struct OrdFieldComparison {
+    /// The name of the compared field.
+    pub field: Symbol,
+    /// Whether the comparison normalizes to `self.field < other.field` (or `<=`) rather than
+    /// `other.field < self.field`.
+    pub self_is_less: bool,
+    /// Whether the comparison being true is used to mean that `self` is less than `other`, as
+    /// opposed to greater.
+    pub means_self_is_less: bool,
+    /// The span of the comparison.
+    pub span: Span,
+}
+
+impl OrdFieldComparison {
+    /// Checks if the direction of the comparison matches what it is used for.
+    pub fn is_consistent(&self) -> bool {
+        self.self_is_less == self.means_self_is_less
+    }
+}
+
+/// Collects the ordering comparisons between fields of `self` and `other` in the `cmp`,
+/// `partial_cmp`, `lt`, `le`, `gt` and `ge` methods of an impl, in the order they appear.
+///
+/// In `lt` and `le` a comparison being true is taken to mean that `self` is less than `other`,
+/// and in `gt` and `ge` that it is greater. In `cmp` and `partial_cmp`, only comparisons that are
+/// the condition of an `if` whose branch evaluates to `Ordering::Less` or `Ordering::Greater`
+/// (possibly wrapped in `Some`) are collected. Comparisons below a `!` are skipped.
+pub fn ord_field_comparisons(cx: &LateContext<'_>, impl_: &Impl<'_>) -> Vec<OrdFieldComparison> {
+    /// Returns the comparison if `expr` orders the same field of `self_id` and `other_id`.
+    fn field_comparison(
+        expr: &Expr<'_>,
+        self_id: HirId,
+        other_id: HirId,
+        means_self_is_less: bool,
+    ) -> Option<OrdFieldComparison> {
+        let ExprKind::Binary(op, lhs, rhs) = expr.kind else {
+            return None;
+        };
+        let (Rel::Lt | Rel::Le, lhs, rhs) = normalize_comparison(op.node, lhs, rhs)? else {
+            return None;
+        };
+        let (ExprKind::Field(lhs_base, lhs_field), ExprKind::Field(rhs_base, rhs_field)) = (lhs.kind, rhs.kind) else {
+            return None;
+        };
+        let self_is_less = if path_to_local_id(lhs_base, self_id) && path_to_local_id(rhs_base, other_id) {
+            true
+        } else if path_to_local_id(lhs_base, other_id) && path_to_local_id(rhs_base, self_id) {
+            false
+        } else {
+            return None;
+        };
+        (lhs_field.name == rhs_field.name).then_some(OrdFieldComparison {
+            field: lhs_field.name,
+            self_is_less,
+            means_self_is_less,
+            span: expr.span,
+        })
+    }
+
+    let mut comparisons = Vec::new();
+    for item in impl_.items {
+        let means_self_is_less = match item.ident.name.as_str() {
+            "lt" | "le" => Some(true),
+            "gt" | "ge" => Some(false),
+            "cmp" | "partial_cmp" => None,
+            _ => continue,
+        };
+        let ImplItemKind::Fn(_, body_id) = cx.tcx.hir().impl_item(item.id).kind else {
+            continue;
+        };
+        let body = cx.tcx.hir().body(body_id);
+        let [self_param, other_param] = body.params else {
+            continue;
+        };
+        let (PatKind::Binding(_, self_id, ..), PatKind::Binding(_, other_id, ..)) =
+            (self_param.pat.kind, other_param.pat.kind)
+        else {
+            continue;
+        };
+        for_each_expr(body.value, |expr| {
+            let comparison = match (expr.kind, means_self_is_less) {
+                (ExprKind::Unary(UnOp::Not, _), _) => return ControlFlow::Continue(Descend::No),
+                (ExprKind::If(cond, then, _), None) => ordering_is_less(cx, then)
+                    .and_then(|is_less| field_comparison(cond.peel_drop_temps(), self_id, other_id, is_less)),
+                (_, Some(is_less)) => field_comparison(expr, self_id, other_id, is_less),
+                _ => None,
+            };
+            comparisons.extend(comparison);
+            ControlFlow::<()>::Continue(Descend::Yes)
+        });
+    }
+    comparisons
+}
+
+/// Collects the fields of the two parameters of a comparator closure that are compared with each
+/// other in its body, in the order they are first compared, e.g. `x` and `y` in
+/// `|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y))`.
+///
+/// Only comparisons of the same field of both parameters are considered, either with a comparison
+/// operator or with `cmp`, `partial_cmp` or `total_cmp`. Returns `None` if the parameters are used
+/// in any other way, since the comparator might then compare more than those fields, or if no
+/// fields are compared at all.
+pub fn comparator_fields(body: &Body<'_>) -> Option<Vec<Symbol>> {
+    /// Returns the field name if `lhs` and `rhs` project the same field out of `a_id` and `b_id`.
+    fn same_field(lhs: &Expr<'_>, rhs: &Expr<'_>, a_id: HirId, b_id: HirId) -> Option<Symbol> {
+        let (ExprKind::Field(lhs_base, lhs_field), ExprKind::Field(rhs_base, rhs_field)) = (lhs.kind, rhs.kind) else {
+            return None;
+        };
+        let params_match = (path_to_local_id(lhs_base, a_id) && path_to_local_id(rhs_base, b_id))
+            || (path_to_local_id(lhs_base, b_id) && path_to_local_id(rhs_base, a_id));
+        (params_match && lhs_field.name == rhs_field.name).then_some(lhs_field.name)
+    }
+
+    let [a_param, b_param] = body.params else {
+        return None;
+    };
+    let (PatKind::Binding(_, a_id, ..), PatKind::Binding(_, b_id, ..)) = (a_param.pat.kind, b_param.pat.kind) else {
+        return None;
+    };
+    let mut fields = Vec::new();
+    let other_use = for_each_expr(body.value, |expr| {
+        let field = match expr.kind {
+            ExprKind::Binary(op, lhs, rhs) => {
+                normalize_comparison(op.node, lhs, rhs).and_then(|(_, lhs, rhs)| same_field(lhs, rhs, a_id, b_id))
+            },
+            ExprKind::MethodCall(path, receiver, [arg], _)
+                if matches!(path.ident.as_str(), "cmp" | "partial_cmp" | "total_cmp") =>
+            {
+                let arg = match arg.kind {
+                    ExprKind::AddrOf(_, _, inner) => inner,
+                    _ => arg,
+                };
+                same_field(receiver, arg, a_id, b_id)
+            },
+            _ => None,
+        };
+        if let Some(field) = field {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+            ControlFlow::Continue(Descend::No)
+        } else if path_to_local_id(expr, a_id) || path_to_local_id(expr, b_id) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(Descend::Yes)
+        }
+    });
+    (other_use.is_none() && !fields.is_empty()).then_some(fields)
+}
+
+/// A comparison of a difference with zero, e.g. `a - b < 0`.
+#[derive(Debug, Clone, Copy)]
+pub struct DifferenceSign<'a> {
+    /// The left operand of the subtraction.
+    pub minuend: &'a Expr<'a>,
+    /// The right operand of the subtraction.
+    pub subtrahend: &'a Expr<'a>,
+    /// The relation of the difference to zero, as in `minuend - subtrahend <op> 0`.
+    pub op: BinOpKind,
+}
+
+/// Checks if the comparison compares a subtraction with zero, where zero is either a constant or
+/// a call of a function or method named `zero` without arguments, like `T::zero()` in generic
+/// code. The comparison is oriented so that the difference is on the left.
+pub fn difference_sign<'a>(
+    cx: &LateContext<'_>,
+    op: BinOpKind,
+    lhs: &'a Expr<'a>,
+    rhs: &'a Expr<'a>,
+) -> Option<DifferenceSign<'a>> {
+    /// Checks if `expr` is zero.
+    fn is_zero(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+        match expr.kind {
+            ExprKind::Call(callee, [])
+                if let ExprKind::Path(qpath) = callee.kind
+                    && let Some(segment) = qpath_last_segment(&qpath) =>
+            {
+                segment.ident.name.as_str() == "zero"
+            },
+            ExprKind::MethodCall(path, _, [], _) => path.ident.name.as_str() == "zero",
+            _ => match constant(cx, cx.typeck_results(), expr) {
+                Some(Constant::Int(n)) => n == 0,
+                Some(Constant::F32(f)) => f == 0.0,
+                Some(Constant::F64(f)) => f == 0.0,
+                _ => false,
+            },
+        }
+    }
+
+    /// Returns the last segment of a path.
+    fn qpath_last_segment<'a>(qpath: &QPath<'a>) -> Option<&'a PathSegment<'a>> {
+        match *qpath {
+            QPath::Resolved(_, path) => path.segments.last(),
+            QPath::TypeRelative(_, segment) => Some(segment),
+            QPath::LangItem(..) => None,
+        }
+    }
+
+    let (rel, left, right) = normalize_comparison(op, lhs, rhs)?;
+    let (difference, op) = if is_zero(cx, right) {
+        let op = match rel {
+            Rel::Lt => BinOpKind::Lt,
+            Rel::Le => BinOpKind::Le,
+            Rel::Eq => BinOpKind::Eq,
+            Rel::Ne => BinOpKind::Ne,
+        };
+        (left, op)
+    } else if is_zero(cx, left) {
+        let op = match rel {
+            Rel::Lt => BinOpKind::Gt,
+            Rel::Le => BinOpKind::Ge,
+            Rel::Eq => BinOpKind::Eq,
+            Rel::Ne => BinOpKind::Ne,
+        };
+        (right, op)
+    } else {
+        return None;
+    };
+    if let ExprKind::Binary(sub, minuend, subtrahend) = difference.kind
+        && sub.node == BinOpKind::Sub
+    {
+        Some(DifferenceSign {
+            minuend,
+            subtrahend,
+            op,
+        })
+    } else {
+        None
+    }
+}
+
+/// A step in reading a place, see `deref_place`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceProjection {
+    /// Dereferencing a reference, either explicitly or through auto-deref.
+    Deref,
+    /// Accessing a field.
+    Field(Symbol),
+}
+
+/// Returns the local that `expr` reads from and the projections applied to it, from the local
+/// outwards, if `expr` only consists of a local, field accesses and dereferences of references.
+/// Auto-derefs of field access receivers are included, so `p.x` and `(*p).x` give the same result
+/// for `p: &S`. Returns `None` if there are overloaded derefs or raw pointers.
+pub fn deref_place(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<(HirId, Vec<PlaceProjection>)> {
+    let typeck = cx.typeck_results();
+    let mut projections = Vec::new();
+    let mut expr = expr;
+    loop {
+        match expr.kind {
+            ExprKind::Unary(UnOp::Deref, inner) if typeck.expr_ty(inner).is_ref() => {
+                projections.push(PlaceProjection::Deref);
+                expr = inner;
+            },
+            ExprKind::Field(base, name) => {
+                projections.push(PlaceProjection::Field(name.name));
+                let mut ty = typeck.expr_ty(base);
+                for adjustment in typeck.expr_adjustments(base) {
+                    if !(matches!(adjustment.kind, Adjust::Deref(None)) && ty.is_ref()) {
+                        return None;
+                    }
+                    projections.push(PlaceProjection::Deref);
+                    ty = adjustment.target;
+                }
+                expr = base;
+            },
+            _ => {
+                let local = path_to_local(expr)?;
+                projections.reverse();
+                return Some((local, projections));
+            },
+        }
+    }
+}
+
+/// Checks if `expr` is a comparison cast to an integer type, like `(a < b) as usize`, and returns
+/// the comparison.
+pub fn comparison_cast_to_int<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+    if let ExprKind::Cast(inner, _) = expr.kind
+        && let ExprKind::Binary(op, _, _) = inner.kind
+        && op.node.is_comparison()
+        && cx.typeck_results().expr_ty(expr).is_integral()
+    {
+        Some(inner)
+    } else {
+        None
+    }
+}
+
+/// An `==` comparison of a place with a value that can be used as a pattern, e.g.
+/// `self.state == State::Idle` or `2 == n`.
+#[derive(Debug, Clone)]
+pub struct PatternEquality<'a> {
+    /// The side of the comparison that is the place.
+    pub value: &'a Expr<'a>,
+    /// The place, as `deref_place` returns it.
+    pub place: (HirId, Vec<PlaceProjection>),
+    /// The side of the comparison that can be used as a pattern.
+    pub pat: &'a Expr<'a>,
+}
+
+/// Checks if `cond` is one or more `==` comparisons joined with `&&`, where each compares a place
+/// (see `deref_place`) with a literal or a unit variant of the same type, and returns the
+/// comparisons in order. Returns `None` if any operand of `&&` is anything else, e.g. a `!=`
+/// comparison or a comparison of two places, so that mixed conditions are rejected as a whole.
+pub fn pattern_equalities<'a>(cx: &LateContext<'_>, cond: &'a Expr<'a>) -> Option<Vec<PatternEquality<'a>>> {
+    let mut equalities = Vec::new();
+    push_pattern_equalities(cx, cond, &mut equalities)?;
+    Some(equalities)
+}
+
+/// Pushes the comparisons of `expr` onto `equalities`, as `pattern_equalities` describes them.
+fn push_pattern_equalities<'a>(
+    cx: &LateContext<'_>,
+    expr: &'a Expr<'a>,
+    equalities: &mut Vec<PatternEquality<'a>>,
+) -> Option<()> {
+    if expr.span.from_expansion() {
+        return None;
+    }
+    match expr.kind {
+        ExprKind::DropTemps(inner) => push_pattern_equalities(cx, inner, equalities),
+        ExprKind::Binary(op, lhs, rhs) if op.node == BinOpKind::And => {
+            push_pattern_equalities(cx, lhs, equalities)?;
+            push_pattern_equalities(cx, rhs, equalities)
+        },
+        ExprKind::Binary(op, lhs, rhs) => {
+            let (Rel::Eq, left, right) = normalize_comparison(op.node, lhs, rhs)? else {
+                return None;
+            };
+            let (value, pat) = if is_pattern_like(cx, right) {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            let typeck = cx.typeck_results();
+            if !is_pattern_like(cx, pat) || typeck.expr_ty(value) != typeck.expr_ty(pat) {
+                return None;
+            }
+            let place = deref_place(cx, value)?;
+            equalities.push(PatternEquality { value, place, pat });
+            Some(())
+        },
+        _ => None,
+    }
+}
+
+/// Checks if `expr` can be written as a pattern: a literal other than a float or byte string, a
+/// negated integer literal, or a path to a unit variant or a unit struct.
+fn is_pattern_like(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    match expr.kind {
+        ExprKind::Lit(lit) => !matches!(lit.node, LitKind::Float(..) | LitKind::ByteStr(..) | LitKind::CStr(..)),
+        ExprKind::Unary(UnOp::Neg, inner) => {
+            matches!(inner.kind, ExprKind::Lit(lit) if matches!(lit.node, LitKind::Int(..)))
+        },
+        ExprKind::Path(_) => matches!(path_res(cx, expr), Res::Def(DefKind::Ctor(_, CtorKind::Const), _)),
+        _ => false,
+    }
+}
@@ -0,0 +1,18 @@
+#![warn(clippy::comparison_as_index)]
+
+fn main() {
+    let (a, b) = (1, 2);
+    let v = vec![0u8; 4];
+
+    let _ = v[(a < b) as usize];
+    //~^ ERROR: using a comparison cast to an integer as an index
+    let _ = v[2 + (a < b) as usize];
+    //~^ ERROR: using a comparison cast to an integer as an index
+    let _ = v[(a > 0) as usize * 2 + (b > 0) as usize];
+    //~^ ERROR: using a comparison cast to an integer as an index
+    //~| ERROR: using a comparison cast to an integer as an index
+
+    // Don't lint, the arithmetic is not in an index
+    let i = 2 + (a < b) as usize;
+    let _ = v[i];
+}
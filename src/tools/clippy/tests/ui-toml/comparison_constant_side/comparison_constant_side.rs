@@ -0,0 +1,15 @@
+#![warn(clippy::comparison_constant_side)]
+
+const LIMIT: u32 = 10;
+
+fn main() {
+    let x = 5u32;
+    let _ = x == 5;
+    //~^ ERROR: constant on the right side of a comparison
+    let _ = x < LIMIT;
+    //~^ ERROR: constant on the right side of a comparison
+
+    // Don't lint
+    let _ = 5 == x;
+    let _ = LIMIT > x;
+}
@@ -0,0 +1,22 @@
+#![warn(clippy::manual_tuple_match)]
+//@no-rustfix
+
+fn log(_: u8) {}
+
+fn main() {
+    let (a, b) = (1, 'x');
+
+    if a == 1 && b == 'x' {
+        //~^ ERROR: this `if` chain compares the same values in every branch
+        log(1);
+    } else if a == 2 && b == 'y' {
+        log(2);
+    }
+
+    // Don't lint, only one branch
+    if a == 1 && b == 'x' {
+        log(3);
+    } else {
+        log(4);
+    }
+}
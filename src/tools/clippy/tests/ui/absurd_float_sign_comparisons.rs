@@ -0,0 +1,39 @@
+#![warn(clippy::absurd_float_sign_comparisons)]
+#![allow(clippy::float_cmp, clippy::nonminimal_bool)]
+
+struct Signed(f64);
+
+impl Signed {
+    fn abs(&self) -> f64 {
+        self.0
+    }
+}
+
+fn main() {
+    let x = 4.0f64;
+    let y = 2.0f32;
+
+    let _ = x.abs() < 0.0;
+    //~^ ERROR: this comparison is always false
+    let _ = x * x < 0.0;
+    //~^ ERROR: this comparison is always false
+    let _ = 0.0 > x.abs();
+    //~^ ERROR: this comparison is always false
+    let _ = x.sqrt() <= -1.0;
+    //~^ ERROR: this comparison is always false
+    let _ = x.powi(2) < 0.0;
+    //~^ ERROR: this comparison is always false
+    let _ = y.hypot(1.0) < -0.5;
+    //~^ ERROR: this comparison is always false
+
+    // ok, can be true for `0.0`
+    let _ = x.abs() <= 0.0;
+    // ok, not a float method
+    let _ = Signed(x).abs() < 0.0;
+    // ok, false for NaN
+    let _ = x.abs() >= 0.0;
+    // ok, odd powers can be negative
+    let _ = x.powi(3) < 0.0;
+    // ok, not a constant bound
+    let _ = x.abs() < x;
+}
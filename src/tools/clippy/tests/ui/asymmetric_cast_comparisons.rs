@@ -0,0 +1,26 @@
+#![warn(clippy::asymmetric_cast_comparisons)]
+#![allow(clippy::cast_lossless)]
+
+fn main() {
+    let a: u64 = 1;
+    let b: i32 = 2;
+    let c: i8 = 3;
+    let d: u32 = 4;
+
+    let _ = (a as i32) < b;
+    //~^ ERROR: only one side of this comparison is cast, which can change its result
+    let _ = d > c as u32;
+    //~^ ERROR: only one side of this comparison is cast, which can change its result
+    let _ = (b as u8) != (d as u8);
+    let _ = (c as i64) >= (a as i64);
+
+    // Don't lint, the casts preserve all values
+    let _ = (c as i32) < b;
+    let _ = d as u64 == a;
+    let _ = (d as i64) < (b as i64);
+
+    // Don't lint, comparisons with constants
+    let _ = a as u8 == b'a';
+    let _ = (c as u8) < 200;
+    let _ = (u64::MAX as i32) < b;
+}
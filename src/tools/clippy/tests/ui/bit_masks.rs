@@ -18,6 +18,10 @@ fn main() {
     //~| NOTE: `#[deny(clippy::erasing_op)]` on by default
     x & 1 == 1; //ok, distinguishes bit 0
     x & 1 == 0; //ok, compared with zero
+    x & 1 == 2;
+    //~^ ERROR: incompatible bit mask: `_ & 1` can never be equal to `2`
+    2 != 1 & x;
+    //~^ ERROR: incompatible bit mask: `_ & 1` can never be equal to `2`
     x & 2 == 1;
     //~^ ERROR: incompatible bit mask: `_ & 2` can never be equal to `1`
     x | 0 == 0; //ok, equals x == 0 (maybe warn?)
@@ -0,0 +1,23 @@
+#![warn(clippy::bitwise_joined_comparisons)]
+
+fn next(n: &mut i32) -> i32 {
+    *n += 1;
+    *n
+}
+
+fn main() {
+    let (a, b, c, d) = (1, 2, 3, 4);
+    let (x, y) = (true, false);
+    let mut n = 0;
+
+    // Lint
+    let _ = (a < b) & (c < d);
+    let _ = (a == b) | (c >= d);
+    let _ = (a != b) & (next(&mut n) > d);
+
+    // Don't lint
+    let _ = a < b && c < d;
+    let _ = (a < b) & y;
+    let _ = x | (c < d);
+    let _ = (a & b) < (c | d);
+}
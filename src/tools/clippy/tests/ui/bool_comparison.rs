@@ -1,6 +1,6 @@
 #![allow(clippy::needless_if)]
 #![warn(clippy::bool_comparison)]
-#![allow(clippy::non_canonical_partial_ord_impl)]
+#![allow(clippy::non_canonical_partial_ord_impl, clippy::neg_cmp_op_on_partial_ord)]
 
 fn main() {
     let x = true;
@@ -165,3 +165,13 @@ fn issue3973() {
     if is_debug == m!(func) {}
     if m!(func) == is_debug {}
 }
+
+fn nested_comparisons(a: i32, b: i32, f: f64, g: f64) {
+    if (a < b) == true {}
+    if (a < b) == false {}
+    if (a == b) != true {}
+    // `!(f < g)` is not the same as `f >= g` if one of them is NaN
+    if (f < g) == false {}
+    if (f >= g) != true {}
+    if (f < g) != false {}
+}
@@ -0,0 +1,19 @@
+#![warn(clippy::checked_unwrap_comparisons)]
+#![allow(clippy::unnecessary_literal_unwrap)]
+
+fn main() {
+    let (a, b, c) = (1u32, 2u32, 3u32);
+    let x = 5i64;
+
+    // Lint
+    let _ = a.checked_add(b).unwrap() > c;
+    let _ = c <= a.checked_mul(b).expect("overflow");
+    let _ = x.checked_pow(2).unwrap() < 100;
+    let _ = x.checked_div(2).unwrap() >= 1;
+
+    // Don't lint
+    let _ = a.checked_add(b).unwrap() == c;
+    let _ = a.checked_add(b).map_or(true, |sum| sum > c);
+    let _ = a.saturating_add(b) > c;
+    let _ = Some(a).unwrap() > c;
+}
@@ -0,0 +1,17 @@
+#![warn(clippy::cmp_owned)]
+
+fn check(a: &str, b: &str, x: i32, y: i32, s: &[u8], t: &[u8]) {
+    let _ = a.to_string() == b.to_string();
+    //~^ ERROR: this creates owned instances just for comparison
+    let _ = x.to_string() != y.to_string();
+    //~^ ERROR: this creates owned instances just for comparison
+    let _ = a.to_owned() == b.to_owned();
+    //~^ ERROR: this creates owned instances just for comparison
+    let _ = s.to_owned() != t.to_owned();
+    //~^ ERROR: this creates owned instances just for comparison
+
+    // Don't lint, `i32` and `str` cannot be compared
+    let _ = x.to_string() == a.to_string();
+}
+
+fn main() {}
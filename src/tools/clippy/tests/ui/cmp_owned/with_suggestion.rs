@@ -22,6 +22,11 @@ fn with_to_string(x: &str) {
     "abc".chars().filter(|c| c.to_owned() != 'X');
 
     "abc".chars().filter(|c| *c != 'X');
+    let s = String::from("a");
+    let t = String::from("b");
+    s.clone() == t;
+    let r = &s;
+    t != r.clone();
 }
 
 struct Foo;
@@ -0,0 +1,21 @@
+#![warn(clippy::comparison_as_index)]
+
+fn main() {
+    let arr = ["no", "yes"];
+    let (a, b) = (1, 2);
+    let v = vec![0u8; 4];
+
+    let _ = arr[(a < b) as usize];
+    //~^ ERROR: using a comparison cast to an integer as an index
+    let _ = v[(a == b) as usize];
+    //~^ ERROR: using a comparison cast to an integer as an index
+
+    // Don't lint, the comparison is part of arithmetic
+    let _ = v[2 + (a < b) as usize];
+    let _ = v[2 * (a >= b) as usize];
+
+    // Don't lint, not a comparison
+    let c = true;
+    let _ = arr[c as usize];
+    let _ = arr[(a - 1) as usize];
+}
@@ -0,0 +1,29 @@
+#![warn(clippy::comparison_constant_side)]
+#![allow(clippy::nonminimal_bool)]
+
+const LIMIT: u32 = 10;
+
+fn get() -> u32 {
+    3
+}
+
+fn main() {
+    let x = 5u32;
+    let _ = 5 == x;
+    //~^ ERROR: constant on the left side of a comparison
+    let _ = LIMIT != x;
+    //~^ ERROR: constant on the left side of a comparison
+    let _ = 5 < x;
+    //~^ ERROR: constant on the left side of a comparison
+    let _ = LIMIT >= x * 2;
+    //~^ ERROR: constant on the left side of a comparison
+    let _ = 3 == get();
+    //~^ ERROR: constant on the left side of a comparison
+
+    // Don't lint
+    let _ = x == 5;
+    let _ = x < LIMIT;
+    let _ = 5 == LIMIT;
+    let s = String::new();
+    let _ = "" == s;
+}
@@ -51,6 +51,11 @@ fn main() {
     status_code < 500 && status_code > 500;
     //~^ ERROR: boolean expression will never evaluate to 'true'
     //~| NOTE: `status_code` cannot simultaneously be greater than and less than `500`
+    status_code >= 500 && status_code < 500;
+    //~^ ERROR: boolean expression will never evaluate to 'true'
+    //~| NOTE: `status_code` cannot simultaneously be greater than and less than `500`
+    // Only true for `500`
+    500 <= status_code && status_code <= 500;
 
     // More complex expressions
     status_code < { 400 } && status_code > { 500 };
@@ -147,4 +152,14 @@ fn main() {
     area < std::f32::consts::E && area > std::f32::consts::PI;
     //~^ ERROR: boolean expression will never evaluate to 'true'
     //~| NOTE: since `std::f32::consts::E` < `std::f32::consts::PI`, the expression evalua
+
+    // With `||`, the stricter comparison has no effect
+    status_code > 500 || status_code > 400;
+    //~^ ERROR: left-hand side of `||` operator has no effect
+    status_code >= 400 || status_code > 500;
+    //~^ ERROR: right-hand side of `||` operator has no effect
+    400 > status_code || status_code < 500;
+    //~^ ERROR: left-hand side of `||` operator has no effect
+    // Correct
+    status_code < 400 || status_code > 500;
 }
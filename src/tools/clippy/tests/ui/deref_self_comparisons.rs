@@ -0,0 +1,44 @@
+#![warn(clippy::deref_self_comparisons)]
+#![allow(clippy::explicit_auto_deref)]
+
+struct S {
+    x: u32,
+    f: f64,
+    inner: Inner,
+}
+
+struct Inner(u8);
+
+fn main() {
+    let s = S {
+        x: 1,
+        f: 1.0,
+        inner: Inner(2),
+    };
+    let p = &s;
+    let _ = p.x < (*p).x;
+    //~^ ERROR: this comparison is always false
+    let _ = (*p).x == p.x;
+    //~^ ERROR: this comparison is always true
+    let _ = p.inner.0 >= (*p).inner.0;
+    //~^ ERROR: this comparison is always true
+    let _ = p.f > (*p).f;
+    //~^ ERROR: this comparison is always false
+
+    // Don't lint
+    let q = &s;
+    let _ = p.x < q.x;
+    let _ = p.x < (*p).inner.0 as u32;
+    // The result depends on whether the value is NaN.
+    let _ = p.f == (*p).f;
+    // More than one layer of references.
+    let pp = &p;
+    let _ = pp.x < (**pp).x;
+    // Overloaded deref.
+    let b = Box::new(S {
+        x: 1,
+        f: 1.0,
+        inner: Inner(2),
+    });
+    let _ = b.x < (*b).x;
+}
@@ -0,0 +1,31 @@
+#![warn(clippy::double_negated_comparison)]
+#![allow(clippy::nonminimal_bool)]
+
+macro_rules! not_not {
+    ($e:expr) => {
+        !!$e
+    };
+}
+
+fn main() {
+    let (a, b, c) = (1, 2, true);
+    if !!(a < b) {}
+    //~^ ERROR: redundant double negation of a comparison
+    let _ = !(!(a == b));
+    //~^ ERROR: redundant double negation of a comparison
+    let _ = c && !!(a >= b);
+    //~^ ERROR: redundant double negation of a comparison
+    let _ = c == !!(a > b);
+    //~^ ERROR: redundant double negation of a comparison
+    let _ = !!(a <= b) as u8;
+    //~^ ERROR: redundant double negation of a comparison
+    let (x, y) = (1.0, f64::NAN);
+    let _ = !!!(x < y);
+    //~^ ERROR: redundant double negation of a comparison
+
+    // Don't lint
+    let _ = !(a < b);
+    let _ = !!c;
+    let _ = !!(a <= b).then_some(1).is_some();
+    let _ = not_not!(a < b);
+}
@@ -0,0 +1,38 @@
+#![warn(clippy::duration_conversion_comparisons)]
+
+use std::time::Duration;
+
+struct Timeout;
+
+impl Timeout {
+    fn as_secs(&self) -> u64 {
+        5
+    }
+}
+
+fn check(a: Duration, b: Duration, r: &Duration, s: &Duration, t: Timeout) {
+    let _ = a.as_nanos() < b.as_nanos();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_nanos`
+    let _ = a.as_micros() != b.as_micros();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_micros`
+    let _ = a.as_millis() >= b.as_millis();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_millis`
+    let _ = a.as_secs() == b.as_secs();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_secs`
+    let _ = a.as_secs_f32() > b.as_secs_f32();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_secs_f32`
+    let _ = a.as_secs_f64() <= b.as_secs_f64();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_secs_f64`
+    let _ = r.as_nanos() > a.as_nanos();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_nanos`
+    let _ = r.as_nanos() == s.as_nanos();
+    //~^ ERROR: comparing `Duration`s after converting both with `as_nanos`
+
+    // Don't lint
+    let _ = a.as_secs() < b.as_millis() as u64;
+    let _ = a.as_secs() < 5;
+    let _ = a.subsec_nanos() < b.subsec_nanos();
+    let _ = t.as_secs() < a.as_secs();
+}
+
+fn main() {}
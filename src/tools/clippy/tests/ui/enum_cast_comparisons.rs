@@ -0,0 +1,34 @@
+#![warn(clippy::enum_cast_comparisons)]
+#![allow(dead_code)]
+
+#[derive(PartialEq, Clone, Copy)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Clone, Copy)]
+enum Opaque {
+    A,
+    B,
+}
+
+fn main() {
+    let (a, b) = (Color::Red, Color::Green);
+    let _ = a as isize == b as isize;
+    //~^ ERROR: comparing enum values by casting them to integers
+    let _ = a as u8 != b as u8;
+    //~^ ERROR: comparing enum values by casting them to integers
+
+    let (c, d) = (Opaque::A, Opaque::B);
+    let _ = c as isize == d as isize;
+    //~^ ERROR: comparing enum values by casting them to integers
+
+    // Orderings are not equality comparisons
+    let _ = (a as isize) < (b as isize);
+    // Different enums
+    let _ = a as isize == c as isize;
+    // Only one side is an enum
+    let _ = a as isize == 1;
+}
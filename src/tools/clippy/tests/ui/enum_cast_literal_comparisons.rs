@@ -0,0 +1,42 @@
+#![warn(clippy::enum_cast_literal_comparisons)]
+#![allow(dead_code)]
+
+#[derive(Clone, Copy)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Clone, Copy)]
+#[repr(i8)]
+enum Signed {
+    Minus = -1,
+    Zero,
+}
+
+fn main() {
+    let color = Color::Green;
+    let _ = color as u8 == 2;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+    let _ = color as u8 != 1;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+    let _ = 0 == color as i32;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+    let _ = color as u8 == 7;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+
+    let signed = Signed::Minus;
+    let _ = signed as i8 == -1;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+    let _ = signed as u8 != 255;
+    //~^ ERROR: comparing the discriminant of an enum value to an integer literal
+
+    // Orderings are not equality comparisons
+    let _ = color as u8 > 1;
+    // Not a literal
+    let two = 2;
+    let _ = color as u8 == two;
+    // Not an enum
+    let _ = two as i8 == 2;
+}
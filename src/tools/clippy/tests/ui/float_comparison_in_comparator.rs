@@ -0,0 +1,38 @@
+#![warn(clippy::float_comparison_in_comparator)]
+#![allow(clippy::comparison_chain)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq)]
+struct Meters(f64);
+
+impl PartialOrd for Meters {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.0 < other.0 {
+            //~^ ERROR: comparing floats with a comparison operator when ordering them
+            Some(Ordering::Less)
+        } else if self.0 > other.0 {
+            //~^ ERROR: comparing floats with a comparison operator when ordering them
+            Some(Ordering::Greater)
+        } else {
+            Some(Ordering::Equal)
+        }
+    }
+}
+
+fn main() {
+    let mut v = vec![1.0f32, 0.5];
+    v.sort_by(|a, b| if a < b { Ordering::Less } else { Ordering::Greater });
+    //~^ ERROR: comparing floats with a comparison operator when ordering them
+    let _ = v
+        .iter()
+        .max_by(|a, b| if **a >= **b { Ordering::Greater } else { Ordering::Less });
+    //~^^ ERROR: comparing floats with a comparison operator when ordering them
+
+    // ok, not ordering anything
+    let _ = v.iter().filter(|x| **x < 1.0).count();
+    let _ = v[0] < v[1];
+    // ok, not floats
+    let mut w = vec![1, 2];
+    w.sort_by(|a, b| if a < b { Ordering::Less } else { Ordering::Greater });
+}
@@ -0,0 +1,48 @@
+#![warn(clippy::float_key_equality)]
+#![allow(clippy::float_cmp)]
+
+fn dedup(v: &mut Vec<f64>, w: &mut Vec<f32>) {
+    v.dedup_by(|a, b| a == b);
+    //~^ ERROR: comparing floats with `==` to tell keys apart
+    w.dedup_by(|a, b| *a != *b);
+    //~^ ERROR: comparing floats with `!=` to tell keys apart
+}
+
+fn filter(values: &[f64], seen: &[f64], removed: &[f64]) -> Vec<f64> {
+    let unique = values.iter().filter(|x| !seen.iter().any(|y| y == *x));
+    //~^ ERROR: comparing floats with `==` to tell keys apart
+    let mut kept: Vec<f64> = unique.copied().collect();
+    kept.retain(|v| removed.iter().all(|r| r != v));
+    //~^ ERROR: comparing floats with `!=` to tell keys apart
+    kept
+}
+
+fn entry(pairs: &mut Vec<(f64, u32)>, key: f64) {
+    if !pairs.iter().any(|(k, _)| *k == key) {
+        //~^ ERROR: comparing floats with `==` to tell keys apart
+        pairs.push((key, 1));
+    }
+}
+
+#[clippy::msrv = "1.61"]
+fn msrv_1_61(v: &mut Vec<f64>) {
+    v.dedup_by(|a, b| a == b);
+    //~^ ERROR: comparing floats with `==` to tell keys apart
+}
+
+fn no_lint(v: &mut Vec<f64>, ints: &mut Vec<i32>, pairs: &[(f64, u32)], key: f64) {
+    ints.dedup_by(|a, b| a == b);
+    v.sort_by(|a, b| a.total_cmp(b));
+    // Looking for a key is fine on its own.
+    let _ = pairs.iter().any(|(k, _)| *k == key);
+    if pairs.iter().any(|(k, _)| *k == key) {
+        println!("found");
+    }
+    // The key is added somewhere else.
+    let mut other = Vec::new();
+    if !pairs.iter().any(|(k, _)| *k == key) {
+        other.push(key);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+#![warn(clippy::guarded_unwrap_comparisons)]
+
+const LIMIT: u32 = 10;
+
+fn check(x: Option<u32>, y: Option<u32>, r: Result<u32, ()>, flag: bool) {
+    let _ = x.is_some() && x.unwrap() > 5;
+    //~^ ERROR: comparing an unwrapped value after checking `is_some`
+    let _ = x.is_some() && LIMIT <= x.unwrap();
+    //~^ ERROR: comparing an unwrapped value after checking `is_some`
+    let _ = r.is_ok() && r.unwrap() == 3;
+    //~^ ERROR: comparing an unwrapped value after checking `is_ok`
+    let _ = flag && x.is_some() && x.expect("checked") != 0;
+    //~^ ERROR: comparing an unwrapped value after checking `is_some`
+
+    // Don't lint
+    let _ = x.is_some() && y.unwrap() > 5;
+    let _ = x.is_some() && x.unwrap() > y.unwrap();
+    let _ = r.is_err() && r.unwrap() > 5;
+    let _ = x.is_none() || x.unwrap() > 5;
+    let mut it = [1u32].into_iter();
+    let _ = it.next().is_some() && it.next().unwrap() > 0;
+}
+
+fn main() {}
+
+#[clippy::msrv = "1.69"]
+fn msrv_1_69(x: Option<u32>) -> bool {
+    x.is_some() && x.unwrap() > 5
+}
+
+#[clippy::msrv = "1.70"]
+fn msrv_1_70(x: Option<u32>) -> bool {
+    x.is_some() && x.unwrap() > 5
+    //~^ ERROR: comparing an unwrapped value after checking `is_some`
+}
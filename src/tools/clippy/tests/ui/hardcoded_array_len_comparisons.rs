@@ -0,0 +1,33 @@
+#![warn(clippy::hardcoded_array_len_comparisons)]
+
+fn main() {
+    let mut buf = [0u8; 256];
+    let idx = 3;
+    if idx < 256 {
+        //~^ ERROR: comparing an index of `buf` against a hardcoded copy of its length
+        buf[idx] = 1;
+    }
+    if 256 > idx {
+        //~^ ERROR: comparing an index of `buf` against a hardcoded copy of its length
+        buf[idx] = 2;
+    }
+    let table: [u16; 16] = [7; 16];
+    let slot = 5;
+    let _ = slot >= 16 || table[slot] == 7;
+    //~^ ERROR: comparing an index of `table` against a hardcoded copy of its length
+
+    // A different literal
+    if idx < 255 {
+        buf[idx] = 3;
+    }
+    // The array is not indexed with the compared local
+    let other = 4;
+    let _ = other < 256;
+    // The length is not written as a literal
+    const LEN: usize = 8;
+    let mut small = [0u8; LEN];
+    let pos = 1;
+    if pos < 8 {
+        small[pos] = 1;
+    }
+}
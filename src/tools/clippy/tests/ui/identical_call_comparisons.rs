@@ -0,0 +1,59 @@
+#![warn(clippy::identical_call_comparisons)]
+#![allow(clippy::op_ref, clippy::borrow_deref_ref)]
+
+use std::cell::Cell;
+
+struct Wrapper(Vec<u8>);
+
+impl Wrapper {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let s = "abc";
+    let f = 1.5f32;
+
+    let _ = v.iter().count() < v.iter().count();
+    //~^ ERROR: this comparison is always false
+    let _ = v.len() <= v.len();
+    //~^ ERROR: this comparison is always true
+    let _ = s.len() + 1 == s.len() + 1;
+    //~^ ERROR: this comparison is always true
+    let _ = s.is_empty() != s.is_empty();
+    //~^ ERROR: this comparison is always false
+    let _ = v.len() > v.len();
+    //~^ ERROR: this comparison is always false
+    let _ = f.to_bits() >= f.to_bits();
+    //~^ ERROR: this comparison is always true
+    let _ = v.first().copied().unwrap_or(0) < v.first().copied().unwrap_or(0);
+    //~^ ERROR: this comparison is always false
+    let _ = &v.len() <= &v.len();
+    //~^ ERROR: this comparison is always true
+    let _ = *v.iter().max().unwrap() < *v.iter().max().unwrap();
+    //~^ ERROR: this comparison is always false
+    let _ = unsafe { *v.as_ptr() < *v.as_ptr() };
+    //~^ ERROR: this comparison is always false
+
+    // ok, different receivers
+    let w = vec![4];
+    let _ = v.len() < w.len();
+    // ok, the methods take `&mut self`
+    let mut it = v.iter();
+    let _ = it.next().is_some() == it.next().is_some();
+    // ok, interior mutability
+    let c = Cell::new(1);
+    let _ = c.get() < c.get();
+    // ok, user code
+    let u = Wrapper(vec![]);
+    let _ = u.len() < u.len();
+    // ok, closures
+    let _ = v.iter().map(|x| x + 1).count() < v.iter().map(|x| x + 1).count();
+    // ok, the result can be NaN
+    let g = [f];
+    let _ = g.first().copied().unwrap_or(0.0) <= g.first().copied().unwrap_or(0.0);
+    // ok, only one side is borrowed again
+    let _ = &*v.iter().max().unwrap() < v.iter().max().unwrap();
+}
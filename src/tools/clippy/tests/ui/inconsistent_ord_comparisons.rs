@@ -0,0 +1,85 @@
+#![warn(clippy::inconsistent_ord_comparisons)]
+#![allow(clippy::comparison_chain, clippy::if_same_then_else, clippy::nonminimal_bool)]
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq)]
+struct A {
+    x: i32,
+    y: i32,
+}
+
+impl PartialOrd for A {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.x < other.x {
+            Some(Ordering::Less)
+        } else if self.x > other.x {
+            Some(Ordering::Greater)
+        } else {
+            self.y.partial_cmp(&other.y)
+        }
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        self.x < other.x || (self.x == other.x && self.y < other.y)
+    }
+
+    fn gt(&self, other: &Self) -> bool {
+        self.x < other.x || (self.x == other.x && self.y > other.y)
+        //~^ ERROR: field `x` is compared in the opposite direction elsewhere in this impl
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct B {
+    x: i32,
+}
+
+impl Ord for B {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.x < other.x {
+            Ordering::Less
+        } else if other.x < self.x {
+            Ordering::Less
+            //~^^ ERROR: field `x` is compared in the opposite direction elsewhere in this impl
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+impl PartialOrd for B {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Don't lint: `x` is ordered in reverse, but consistently so.
+#[derive(PartialEq)]
+struct Reversed {
+    x: i32,
+}
+
+impl PartialOrd for Reversed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.x.partial_cmp(&self.x)
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        self.x > other.x
+    }
+
+    fn le(&self, other: &Self) -> bool {
+        self.x >= other.x
+    }
+
+    fn gt(&self, other: &Self) -> bool {
+        other.x > self.x
+    }
+
+    fn ge(&self, other: &Self) -> bool {
+        !(self.x > other.x)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![warn(clippy::inexact_float_literal_comparisons)]
+
+fn main() {
+    let x = 1.0f32;
+    let y = 1.0f64;
+
+    let _ = x == 0.1;
+    //~^ ERROR: comparing a float for equality with a literal that it cannot represent exactly
+    let _ = 0.1 != y;
+    //~^ ERROR: comparing a float for equality with a literal that it cannot represent exactly
+    let _ = x == -0.3;
+    //~^ ERROR: comparing a float for equality with a literal that it cannot represent exactly
+    let _ = x == 16_777_217.0;
+    //~^ ERROR: comparing a float for equality with a literal that it cannot represent exactly
+
+    // ok, exactly representable
+    let _ = x == 0.5;
+    let _ = y == 0.0;
+    let _ = y != 1e10;
+    let _ = x == 16_777_216.0;
+    // ok, not an equality comparison
+    let _ = x < 0.1;
+}
@@ -0,0 +1,53 @@
+#![warn(clippy::loop_invariant_comparisons)]
+
+const fn threshold() -> u32 {
+    10
+}
+
+const fn scaled(factor: u32) -> u32 {
+    factor * 10
+}
+
+fn not_const() -> u32 {
+    10
+}
+
+fn main() {
+    let scores = vec![1, 20, 3];
+    for score in &scores {
+        let _ = *score > threshold();
+        //~^ ERROR: this call is evaluated again in every iteration of the loop
+    }
+    let factor = 2;
+    let mut i = 0;
+    while i < scaled(factor) {
+        //~^ ERROR: this call is evaluated again in every iteration of the loop
+        i += 1;
+    }
+    loop {
+        if scaled(3) == i {
+            //~^ ERROR: this call is evaluated again in every iteration of the loop
+            break;
+        }
+        i += 1;
+    }
+
+    // Depends on the loop variable
+    for score in &scores {
+        let _ = *score > scaled(*score);
+    }
+    // The argument may change
+    let mut step = 1;
+    for score in &scores {
+        let _ = *score > scaled(step);
+        step += 1;
+    }
+    // Not a `const fn`
+    for score in &scores {
+        let _ = *score > not_const();
+    }
+    // Not in a loop
+    let _ = scores[0] > threshold();
+    // In a closure that is called more than once
+    let _ = scores.iter().any(|&score| score > threshold());
+}
@@ -351,6 +351,11 @@ fn no_lint() {
         let x30 = f64::max(CONST_F64_MIN, f64::max(CONST_F64_MAX, input));
         let x31 = f64::min(CONST_F64_MAX, f64::min(CONST_F64_MIN, input));
         let x32 = f64::min(CONST_F64_MAX, f64::min(CONST_F64_MIN, CONST_F64_MAX));
+        // `clamp` would panic here, since the constant bounds are out of order.
+        let input: i32 = cmp_min_max(1);
+        let x33 = input.max(CONST_MAX).min(CONST_MIN);
+        let x34 = input.min(CONST_MIN).max(CONST_MAX);
+        let x35 = cmp_min(cmp_max(input, CONST_MAX), CONST_MIN);
     }
 }
 
@@ -415,3 +420,33 @@ const fn _const() {
         x = min;
     }
 }
+
+fn sequence_form(input: i32, min: i32, max: i32) {
+    let mut x = input;
+    if x >= max {
+        //~^ ERROR: clamp-like pattern without using clamp function
+        //~| NOTE: clamp will panic if max < min
+        x = max;
+    }
+    if min >= x {
+        x = min;
+    }
+
+    // The assignments do not go to the compared value.
+    let mut y = 0;
+    if x > max {
+        y = max;
+    }
+    if x < min {
+        y = min;
+    }
+
+    // The lower bound is evaluated on the value after the upper bound was applied.
+    let mut z = input;
+    if z > max {
+        z = max;
+    }
+    if z < max - z {
+        z = max - z;
+    }
+}
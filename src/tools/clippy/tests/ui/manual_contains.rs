@@ -0,0 +1,33 @@
+#![warn(clippy::manual_contains)]
+#![allow(clippy::useless_vec)]
+
+fn main() {
+    let values = vec![1, 2, 3];
+    let array = [1, 2, 3];
+    let target = 2;
+
+    let _ = values.iter().any(|x| *x == target);
+    //~^ ERROR: using `any` to check whether a slice contains a value
+    let _ = values.iter().any(|x| x == &target);
+    //~^ ERROR: using `any` to check whether a slice contains a value
+    let _ = values.iter().any(|&x| target == x);
+    //~^ ERROR: using `any` to check whether a slice contains a value
+    let _ = array.iter().any(|x| *x == target + 1);
+    //~^ ERROR: using `any` to check whether a slice contains a value
+    let _ = values.iter().all(|x| *x != target);
+    //~^ ERROR: using `all` to check whether a slice contains a value
+
+    // Don't lint
+    let _ = values.iter().any(|x| *x != target);
+    let _ = values.iter().all(|x| *x == target);
+    let _ = values.iter().any(|x| *x == *x + target);
+    let _ = values.iter().any(|x| *x == target || *x == 0);
+    let _ = values.iter().any(|x| {
+        println!("{x}");
+        *x == target
+    });
+    let _ = values.iter().any(|x| *x > target);
+    let _ = values.iter().map(|x| x * 2).any(|x| x == target);
+    let strings = vec![String::from("a")];
+    let _ = strings.iter().any(|s| s == "a");
+}
@@ -0,0 +1,29 @@
+#![warn(clippy::manual_is_power_of_two)]
+
+fn check(x: u32, y: usize, t: (u64, u8), i: i32, flag: bool) {
+    let _ = x != 0 && x & (x - 1) == 0;
+    //~^ ERROR: manually testing whether an integer is a power of two
+    let _ = (x - 1) & x == 0 && 0 != x;
+    //~^ ERROR: manually testing whether an integer is a power of two
+    let _ = x > 0 && (x & (x - 1)) == 0;
+    //~^ ERROR: manually testing whether an integer is a power of two
+    let _ = x == 0 || x & (x - 1) != 0;
+    //~^ ERROR: manually testing whether an integer is a power of two
+    let _ = flag && y != 0 && y & (y - 1) == 0;
+    //~^ ERROR: manually testing whether an integer is a power of two
+    let _ = t.0 != 0 && t.0 & (t.0 - 1) == 0;
+    //~^ ERROR: manually testing whether an integer is a power of two
+
+    // Don't lint
+    let _ = i != 0 && i & (i - 1) == 0;
+    let _ = x != 0 && u32::from(t.1) & (u32::from(t.1) - 1) == 0;
+    let _ = x != 0 && x & (x - 2) == 0;
+    let _ = x != 0 && x & (x + 1) == 0;
+    let _ = x > 1 && x & (x - 1) == 0;
+    let _ = x == 0 && x & (x - 1) == 0;
+    let _ = x != 0 || x & (x - 1) == 0;
+    let _ = x != 0 && x & (x - 1) != 0;
+    let _ = x != 0 && x | (x - 1) == 0;
+}
+
+fn main() {}
@@ -0,0 +1,95 @@
+#![feature(is_sorted)]
+#![warn(clippy::manual_is_sorted)]
+#![allow(clippy::let_and_return, clippy::needless_range_loop)]
+
+fn ascending(v: Vec<u32>) -> bool {
+    let mut sorted = true;
+    //~^ ERROR: this loop checks whether `v` is sorted
+    for i in 0..v.len() - 1 {
+        sorted &= v[i] <= v[i + 1];
+    }
+    sorted
+}
+
+fn ascending_break(v: &[u32]) -> bool {
+    let mut sorted = true;
+    //~^ ERROR: this loop checks whether `v` is sorted
+    for i in 1..v.len() {
+        if v[i - 1] > v[i] {
+            sorted = false;
+            break;
+        }
+    }
+    sorted
+}
+
+fn descending(v: [u32; 4]) -> bool {
+    let mut sorted = true;
+    //~^ ERROR: this loop checks whether `v` is sorted
+    for i in 0..v.len() - 1 {
+        sorted = sorted && v[i] >= v[i + 1];
+    }
+    sorted
+}
+
+fn descending_if(v: &[u32]) -> bool {
+    let mut sorted = true;
+    //~^ ERROR: this loop checks whether `v` is sorted
+    for i in 1..v.len() {
+        if v[i - 1] < v[i] {
+            sorted = false;
+        }
+    }
+    sorted
+}
+
+fn still_mutated(v: &[u32], empty: bool) -> bool {
+    let mut sorted = true;
+    //~^ ERROR: this loop checks whether `v` is sorted
+    for i in 1..v.len() {
+        sorted &= v[i - 1] <= v[i];
+    }
+    if empty {
+        sorted = false;
+    }
+    sorted
+}
+
+// Don't lint
+fn dont_lint(v: &[u32], w: &[u32], f: &[f64]) {
+    // `!(a > b)` is true for `NaN`, but `is_sorted` is not.
+    let mut sorted = true;
+    for i in 1..f.len() {
+        if f[i - 1] > f[i] {
+            sorted = false;
+        }
+    }
+
+    // Strictly sorted.
+    let mut sorted = true;
+    for i in 1..v.len() {
+        sorted &= v[i - 1] < v[i];
+    }
+
+    // The accumulator is not declared right before the loop.
+    let mut sorted = true;
+    let _ = sorted;
+    for i in 1..v.len() {
+        sorted &= v[i - 1] <= v[i];
+    }
+
+    // Different slices.
+    let mut sorted = true;
+    for i in 1..v.len() {
+        sorted &= v[i - 1] <= w[i];
+    }
+
+    // The body does more than that.
+    let mut sorted = true;
+    for i in 1..v.len() {
+        sorted &= v[i - 1] <= v[i];
+        println!("{i}");
+    }
+}
+
+fn main() {}
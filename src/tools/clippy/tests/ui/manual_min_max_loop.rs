@@ -0,0 +1,107 @@
+//@no-rustfix
+#![warn(clippy::manual_min_max_loop)]
+
+struct Player {
+    score: u32,
+    level: u32,
+}
+
+fn plain(v: Vec<i32>) {
+    let mut best = 0;
+    for &x in &v {
+        //~^ ERROR: manual implementation of `Iterator::max`
+        if x > best {
+            best = x;
+        }
+    }
+
+    let mut best = i32::MAX;
+    for x in v {
+        //~^ ERROR: manual implementation of `Iterator::min`
+        if x < best {
+            best = x
+        }
+    }
+}
+
+fn projected(players: &[Player], words: Vec<&str>) {
+    let mut best = &players[0];
+    for p in players {
+        //~^ ERROR: manual implementation of `Iterator::max_by_key`
+        if p.score > best.score {
+            best = p;
+        }
+    }
+
+    let mut best = &players[0];
+    for p in players {
+        //~^ ERROR: manual implementation of `Iterator::max_by_key`
+        if p.score >= best.score {
+            best = p;
+        }
+    }
+
+    let mut shortest = words[0];
+    for &w in &words {
+        //~^ ERROR: manual implementation of `Iterator::min_by_key`
+        if w.len() < shortest.len() {
+            shortest = w;
+        }
+    }
+}
+
+// Don't lint
+fn dont_lint(v: &[i32], players: &[Player], limit: i32, floats: &[f64]) {
+    // The loop does more than that.
+    let mut best = 0;
+    let mut count = 0;
+    for &x in v {
+        if x > best {
+            best = x;
+            count += 1;
+        }
+    }
+
+    let mut best = 0;
+    for &x in v {
+        if x > best {
+            best = x;
+        } else {
+            best -= 1;
+        }
+    }
+
+    // Different projections.
+    let mut best = &players[0];
+    for p in players {
+        if p.score > best.level {
+            best = p;
+        }
+    }
+
+    // Not the item itself.
+    let mut best = 0;
+    for &x in v {
+        if x > best {
+            best = x + 1;
+        }
+    }
+
+    // Not compared with the accumulator.
+    let mut best = 0;
+    for &x in v {
+        if x > limit {
+            best = x;
+        }
+    }
+
+    // Not totally ordered, `max` isn't available.
+    let mut best = 0.0;
+    for &x in floats {
+        if x > best {
+            best = x;
+        }
+    }
+}
+
+fn main() {}
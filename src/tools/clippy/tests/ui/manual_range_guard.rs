@@ -0,0 +1,42 @@
+#![warn(clippy::manual_range_guard)]
+#![allow(clippy::manual_range_contains)]
+
+fn main() {
+    let n = 5i32;
+    match n {
+        n if n >= 0 && n <= 9 => println!("digit {n}"),
+        //~^ ERROR: this match guard can be written as a range pattern
+        n if n > 9 && n < 100 => {},
+        //~^ ERROR: this match guard can be written as a range pattern
+        n if -10 <= n && 0 > n => {},
+        //~^ ERROR: this match guard can be written as a range pattern
+        _ => {},
+    }
+
+    let c = 'x';
+    match c {
+        c if c >= 'a' && c < 'z' => println!("{c}"),
+        //~^ ERROR: this match guard can be written as a range pattern
+        _ => {},
+    }
+
+    let b = 3u8;
+    match b {
+        // Don't lint, the bounds are not literals
+        x if x >= b && x <= 9 => {},
+        // Don't lint, the guard compares a different value
+        _x if b >= 1 && b <= 9 => {},
+        // Don't lint, the range is empty
+        x if x > 5 && x < 6 => {},
+        // Don't lint, the pattern is not a plain binding
+        ref x if *x >= 1 && *x <= 9 => {},
+        _ => {},
+    }
+
+    // Don't lint, not an integer or `char`
+    let f = 1.0f64;
+    match f {
+        x if x >= 0.0 && x <= 1.0 => {},
+        _ => {},
+    }
+}
@@ -0,0 +1,48 @@
+#![warn(clippy::manual_range_matches)]
+#![allow(clippy::manual_range_contains, clippy::nonminimal_bool)]
+
+fn main() {
+    let c = 'x';
+    let _ = c >= 'a' && c <= 'z';
+    //~^ ERROR: manual range pattern check
+    let _ = c <= 'z' && c >= 'a';
+    //~^ ERROR: manual range pattern check
+    let _ = 'a' <= c && 'z' >= c;
+    //~^ ERROR: manual range pattern check
+
+    let n = 5u32;
+    let _ = n >= 1 && n <= 9;
+    //~^ ERROR: manual range pattern check
+    let i = -3i32;
+    let _ = i >= -10 && 10 >= i;
+    //~^ ERROR: manual range pattern check
+
+    // Exclusive range patterns are unstable
+    let _ = n >= 1 && n < 10;
+    // There is no pattern for an exclusive lower bound
+    let _ = n > 0 && n <= 9;
+    // Floats can't be matched against range patterns
+    let f = 1.5f64;
+    let _ = f >= 1.0 && f <= 2.0;
+    // Bounds must be literals
+    let m = 9;
+    let _ = n >= 1 && n <= m;
+    // Different values
+    let _ = n >= 1 && m <= 9;
+}
+
+// `match` can be used in const fns since Rust 1.46.
+const fn is_digit(n: u32) -> bool {
+    n >= 1 && n <= 9
+    //~^ ERROR: manual range pattern check
+}
+
+#[clippy::msrv = "1.45"]
+const fn is_digit_before_const_match(n: u32) -> bool {
+    n >= 1 && n <= 9
+}
+
+#[clippy::msrv = "1.41"]
+fn is_digit_before_matches(n: u32) -> bool {
+    n >= 1 && n <= 9
+}
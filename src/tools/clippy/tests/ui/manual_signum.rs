@@ -0,0 +1,90 @@
+#![warn(clippy::manual_signum)]
+#![allow(clippy::comparison_chain)]
+
+fn main() {
+    let x = -3i32;
+    let y = 7i64;
+    let f = 2.5f64;
+
+    // Lint
+    let _ = if x > 0 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    };
+    let _ = if 0 > x {
+        -1
+    } else if 0 < x {
+        1
+    } else {
+        0
+    };
+    let _ = if x == 0 {
+        0
+    } else if x > 0 {
+        1
+    } else {
+        -1
+    };
+    let _ = if y < 0 {
+        -1
+    } else if 0 == y {
+        0
+    } else {
+        1
+    };
+
+    // Don't lint
+    // The results do not match the signs.
+    let _ = if x > 0 {
+        1
+    } else if x < 0 {
+        0
+    } else {
+        -1
+    };
+    // Different values are compared.
+    let _ = if x > 0 {
+        1
+    } else if y < 0 {
+        -1
+    } else {
+        0
+    };
+    // Not compared with zero.
+    let _ = if x > 1 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    };
+    // `f64::signum` does not return zero.
+    let _ = if f > 0.0 {
+        1.0
+    } else if f < 0.0 {
+        -1.0
+    } else {
+        0.0
+    };
+    // The result has a different type than the value.
+    let _ = if x > 0 {
+        1i64
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    };
+    // The end of a longer chain is not linted.
+    let _ = if x > 10 {
+        10
+    } else if x > 0 {
+        1
+    } else if x < 0 {
+        -1
+    } else {
+        0
+    };
+}
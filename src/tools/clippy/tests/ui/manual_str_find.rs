@@ -0,0 +1,23 @@
+#![warn(clippy::manual_str_find)]
+
+fn lint(line: &str, owned: String) {
+    let _ = line.bytes().position(|b| b == b'=');
+    //~^ ERROR: searching the bytes of a string for an ASCII character
+    let _ = owned.as_bytes().iter().position(|b| *b == b':');
+    //~^ ERROR: searching the bytes of a string for an ASCII character
+    let _ = line.as_bytes().iter().position(|&b| b'\'' == b);
+    //~^ ERROR: searching the bytes of a string for an ASCII character
+}
+
+fn no_lint(line: &str, bytes: &[u8], target: u8) {
+    // Not ASCII, so `find` would search for a different character.
+    let _ = line.bytes().position(|b| b == b'\xff');
+    // Not a literal.
+    let _ = line.bytes().position(|b| b == target);
+    let _ = line.bytes().position(|b| b != b'=');
+    let _ = line.bytes().find(|&b| b == b'=');
+    let _ = line.chars().position(|c| c == '=');
+    let _ = bytes.iter().position(|&b| b == b'=');
+}
+
+fn main() {}
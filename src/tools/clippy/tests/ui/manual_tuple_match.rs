@@ -0,0 +1,119 @@
+#![warn(clippy::manual_tuple_match)]
+//@no-rustfix
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    Running,
+    Done,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Event {
+    Start,
+    Stop,
+}
+
+#[derive(PartialEq)]
+enum Mode {
+    Read,
+    Write,
+}
+
+struct Machine {
+    mode: Mode,
+    code: i32,
+}
+
+fn transitions(mut state: State, event: Event) -> State {
+    if state == State::Idle && event == Event::Start {
+        //~^ ERROR: this `if` chain compares the same values in every branch
+        state = State::Running;
+    } else if state == State::Running && event == Event::Stop {
+        state = State::Done;
+    } else if state == State::Done && event == Event::Start {
+        state = State::Running;
+    }
+    state
+}
+
+fn swapped_and_reversed(m: &Machine) -> u8 {
+    if m.mode == Mode::Read && m.code == 0 {
+        //~^ ERROR: this `if` chain compares the same values in every branch
+        1
+    } else if -1 == m.code && Mode::Write == m.mode {
+        2
+    } else if m.code == 1 && m.mode == Mode::Read {
+        3
+    } else {
+        0
+    }
+}
+
+fn log(_: u8) {}
+
+fn not_linted(state: State, event: Event, flag: bool) {
+    // Don't lint, only two branches
+    if state == State::Idle && event == Event::Start {
+        log(1);
+    } else if state == State::Running && event == Event::Stop {
+        log(2);
+    }
+
+    // Don't lint, one branch uses `!=`
+    if state == State::Idle && event == Event::Start {
+        log(3);
+    } else if state == State::Running && event != Event::Stop {
+        log(4);
+    } else if state == State::Done && event == Event::Start {
+        log(5);
+    }
+
+    // Don't lint, one branch tests something else as well
+    if state == State::Idle && event == Event::Start {
+        log(6);
+    } else if state == State::Running && event == Event::Stop && flag {
+        log(7);
+    } else if state == State::Done && event == Event::Start {
+        log(8);
+    }
+
+    // Don't lint, one branch doesn't test `event`
+    if state == State::Idle && event == Event::Start {
+        log(9);
+    } else if state == State::Running {
+        log(10);
+    } else if state == State::Done && event == Event::Start {
+        log(11);
+    }
+
+    // Don't lint, one branch is an `if let`
+    let opt = Some(state);
+    if state == State::Idle && event == Event::Start {
+        log(12);
+    } else if let Some(State::Running) = opt {
+        log(13);
+    } else if state == State::Done && event == Event::Start {
+        log(14);
+    }
+
+    // Don't lint, only one value is compared
+    if state == State::Idle {
+        log(15);
+    } else if state == State::Running {
+        log(16);
+    } else if state == State::Done {
+        log(17);
+    }
+
+    // Don't lint, `state` is compared twice
+    if state == State::Idle && state == State::Running {
+        log(18);
+    } else if state == State::Running && event == Event::Stop {
+        log(19);
+    } else if state == State::Done && event == Event::Start {
+        log(20);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,76 @@
+#![warn(clippy::off_by_one_slice_window)]
+#![allow(clippy::needless_range_loop, clippy::while_len_comparison)]
+
+struct Samples {
+    items: Vec<u32>,
+}
+
+impl Samples {
+    fn total(&self) -> u32 {
+        let mut total = 0;
+        for i in 1..self.items.len() {
+            total += self.items[1 + i];
+            //~^ ERROR: this index is out of bounds in the last iteration of the loop
+        }
+        total
+    }
+}
+
+fn lint(v: &[i32], w: Vec<u8>) {
+    let mut deltas = Vec::new();
+    for i in 0..v.len() {
+        deltas.push(v[i + 1] - v[i]);
+        //~^ ERROR: this index is out of bounds in the last iteration of the loop
+    }
+
+    let mut i = 0;
+    let mut sum = 0;
+    while i < w.len() {
+        sum += w[i] + w[i + 2];
+        //~^ ERROR: this index is out of bounds in the last iteration of the loop
+        i += 1;
+    }
+}
+
+fn no_lint(v: &[i32]) {
+    let mut deltas = Vec::new();
+    // Guarded against the last iteration.
+    for i in 0..v.len() {
+        if i + 1 < v.len() {
+            deltas.push(v[i + 1] - v[i]);
+        }
+    }
+    for i in 0..v.len() {
+        match i {
+            0 => {},
+            _ => deltas.push(v[i - 1] + v[i + 1]),
+        }
+    }
+    // The bound is already correct.
+    for i in 0..v.len() - 1 {
+        deltas.push(v[i + 1] - v[i]);
+    }
+    for i in 1..v.len() {
+        deltas.push(v[i] - v[i - 1]);
+    }
+    // A different slice.
+    let w = vec![0; 10];
+    for i in 0..v.len() {
+        deltas.push(w[i + 1]);
+    }
+    // The slice grows in the loop.
+    let mut u = vec![1];
+    for i in 0..u.len() {
+        u.push(u[i] + 1);
+        deltas.push(u[i + 1]);
+    }
+    // The counter is moved back.
+    let mut i = 0;
+    while i < v.len() {
+        i -= 1;
+        deltas.push(v[i + 1]);
+        i += 2;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![warn(clippy::option_ordering_comparisons)]
+#![allow(clippy::partialeq_to_none)]
+
+fn main() {
+    let x = Some(5);
+    let y = None;
+    let n = 3;
+
+    let _ = x < Some(10);
+    //~^ ERROR: ordering comparison with an `Option` constructor
+    let _ = None < x;
+    //~^ ERROR: ordering comparison with an `Option` constructor
+    let _ = x >= Some(n);
+    //~^ ERROR: ordering comparison with an `Option` constructor
+    let _ = Some(1) <= y;
+    //~^ ERROR: ordering comparison with an `Option` constructor
+
+    // ok, equality comparisons
+    let _ = x == None;
+    let _ = x != Some(10);
+    // ok, no constructor
+    let _ = x < y;
+}
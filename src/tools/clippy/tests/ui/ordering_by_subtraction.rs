@@ -0,0 +1,79 @@
+#![warn(clippy::ordering_by_subtraction)]
+#![allow(clippy::comparison_chain)]
+
+use std::cmp::Ordering;
+use std::ops::Sub;
+
+trait Zero {
+    fn zero() -> Self;
+}
+
+fn ints(a: i32, b: i32) -> Ordering {
+    if a - b < 0 {
+        //~^ ERROR: ordering values by the sign of their difference
+        Ordering::Less
+    } else if a - b > 0 {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+fn generic<T: Sub<Output = T> + PartialOrd + Zero + Copy>(a: T, b: T) -> Option<Ordering> {
+    if T::zero() < a - b {
+        //~^ ERROR: ordering values by the sign of their difference
+        Some(Ordering::Greater)
+    } else if T::zero() > a - b {
+        Some(Ordering::Less)
+    } else {
+        Some(Ordering::Equal)
+    }
+}
+
+fn floats(a: f64, b: f64) -> Ordering {
+    if 0.0 > a - b {
+        //~^ ERROR: ordering values by the sign of their difference
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+fn different_differences(a: i32, b: i32, c: i32, d: i32) -> Ordering {
+    if a - b < 0 {
+        //~^ ERROR: ordering values by the sign of their difference
+        Ordering::Less
+    } else if c - d > 0 {
+        //~^ ERROR: ordering values by the sign of their difference
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+// Don't lint
+fn not_ordering(a: i32, b: i32) -> i32 {
+    if a - b < 0 {
+        1
+    } else {
+        2
+    }
+}
+
+fn equality(a: i32, b: i32) -> Ordering {
+    if a - b == 0 {
+        Ordering::Equal
+    } else {
+        Ordering::Less
+    }
+}
+
+fn nonzero(a: i32, b: i32) -> Ordering {
+    if a - b < 1 {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+fn main() {}
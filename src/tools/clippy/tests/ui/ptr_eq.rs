@@ -18,6 +18,10 @@ fn main() {
 
     let _ = a as *const _ as usize == b as *const _ as usize;
     let _ = a as *const _ == b as *const _;
+    let _ = a as *const _ != b as *const _;
+    let x: &[i32] = a;
+    let y: &[i32] = b;
+    let _ = x as *const [i32] == y as *const [i32];
     let _ = a.as_ptr() == b as *const _;
     let _ = a.as_ptr() == b.as_ptr();
 
@@ -0,0 +1,62 @@
+#![warn(clippy::saturating_wrapping_comparisons)]
+
+struct Counter(u32);
+
+impl Counter {
+    fn wrapping_add(&self, n: u32) -> u32 {
+        self.0 + n
+    }
+}
+
+fn main() {
+    let x = 1u32;
+    let y = -1i64;
+
+    let _ = x.saturating_add(1) >= x;
+    //~^ ERROR: this comparison is always true
+    let _ = x.saturating_add(1) < x;
+    //~^ ERROR: this comparison is always false
+    let _ = x.saturating_sub(2) <= x;
+    //~^ ERROR: this comparison is always true
+    let _ = x.saturating_sub(2) > x;
+    //~^ ERROR: this comparison is always false
+    let _ = x.wrapping_add(1) == x;
+    //~^ ERROR: this comparison is always false
+    let _ = x.wrapping_add(1) != x;
+    //~^ ERROR: this comparison is always true
+    let _ = x.wrapping_add(1) > x;
+    //~^ ERROR: this comparison is false if the operation wraps around
+    let _ = x.wrapping_add(1) >= x;
+    //~^ ERROR: this comparison is false if the operation wraps around
+    let _ = x.wrapping_sub(1) == x;
+    //~^ ERROR: this comparison is always false
+    let _ = x.wrapping_sub(1) != x;
+    //~^ ERROR: this comparison is always true
+    let _ = x.wrapping_sub(1) < x;
+    //~^ ERROR: this comparison is false if the operation wraps around
+    let _ = x.wrapping_sub(1) <= x;
+    //~^ ERROR: this comparison is false if the operation wraps around
+
+    // operands swapped
+    let _ = x <= x.saturating_add(1);
+    //~^ ERROR: this comparison is always true
+    let _ = x < x.wrapping_add(1);
+    //~^ ERROR: this comparison is false if the operation wraps around
+    // signed
+    let _ = y.saturating_add(3) < y;
+    //~^ ERROR: this comparison is always false
+
+    // ok, can be true or false
+    let _ = x.saturating_add(1) > x;
+    let _ = x.saturating_sub(1) < x;
+    let _ = x.wrapping_add(1) < x;
+    // ok, not a positive constant
+    let _ = x.saturating_add(0) > x;
+    let _ = y.saturating_add(-1) >= y;
+    let _ = x.wrapping_add(x) == x;
+    // ok, not the receiver
+    let _ = x.saturating_add(1) >= 5;
+    // ok, not an integer method
+    let c = Counter(x);
+    let _ = c.wrapping_add(1) == c.0;
+}
@@ -0,0 +1,20 @@
+#![warn(clippy::single_bit_equality)]
+
+fn main() {
+    let flags = 5u32;
+    let k = 2;
+
+    let _ = flags == 1 << k;
+    //~^ ERROR: comparing a value for equality with a single bit
+    let _ = 1 << k != flags;
+    //~^ ERROR: comparing a value for equality with a single bit
+    let _ = flags + 1 == 1 << 3;
+    //~^ ERROR: comparing a value for equality with a single bit
+
+    // Don't lint
+    let _ = flags & (1 << k) != 0;
+    let _ = flags == 2 << k;
+    let _ = flags < 1 << k;
+    let _ = 4 == 1 << k;
+    let _ = 1 << flags == 1 << k;
+}
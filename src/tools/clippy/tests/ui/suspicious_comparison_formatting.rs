@@ -0,0 +1,30 @@
+#![warn(clippy::suspicious_comparison_formatting)]
+#![allow(clippy::needless_if, clippy::suspicious_unary_op_formatting)]
+
+macro_rules! greater_than_negated {
+    ($a:expr, $b:expr) => {
+        $a >-$b
+    };
+}
+
+#[rustfmt::skip]
+fn main() {
+    let a = 42;
+    let b = 30;
+
+    if a >-b {}
+    //~^ ERROR: `>-` looks like a single operator, but this compares with a negated value
+    if a>-30 {}
+    //~^ ERROR: `>-` looks like a single operator, but this compares with a negated value
+    if a >=-b {}
+    //~^ ERROR: `>=-` looks like a single operator, but this compares with a negated value
+    if a <=-30 {}
+    //~^ ERROR: `<=-` looks like a single operator, but this compares with a negated value
+
+    // Don't lint
+    if a > -b {}
+    if a >- b {}
+    if a == -b {}
+    if a != -b {}
+    if greater_than_negated!(a, b) {}
+}
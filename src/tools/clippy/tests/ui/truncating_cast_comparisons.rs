@@ -0,0 +1,34 @@
+#![warn(clippy::truncating_cast_comparisons)]
+#![allow(clippy::asymmetric_cast_comparisons, clippy::cast_lossless)]
+
+const LIMIT: u16 = 256;
+
+fn main() {
+    let big: u32 = 300;
+    let small: u8 = 44;
+    let wide: i64 = -1;
+
+    // The comparison is always false or always true
+    let _ = big as u8 as u32 == 300;
+    //~^ ERROR: this comparison is always false
+    let _ = (wide as i8) as i32 != 200;
+    //~^ ERROR: this comparison is always true
+    let _ = LIMIT == big as u8 as u16;
+    //~^ ERROR: this comparison is always false
+
+    // Only the low bits are compared
+    let _ = (big as u8) as u32 == small as u32;
+    //~^ ERROR: this comparison only compares the low 8 bits of `big`
+    let _ = u64::from(small) != wide as u16 as u64;
+    //~^ ERROR: this comparison only compares the low 16 bits of `wide`
+    let _ = (big as u16) == (small as u16);
+    //~^ ERROR: this comparison only compares the low 16 bits of `big`
+
+    // Don't lint
+    let _ = big as u8 == b'a';
+    let _ = big as u8 as u32 == 44;
+    let _ = (big as u8) == small;
+    let _ = (big as u8) as u32 == (wide as u8) as u32;
+    let _ = big as u64 == 300;
+    let _ = (big as u8 as u32) < 300;
+}
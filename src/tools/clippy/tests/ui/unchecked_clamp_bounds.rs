@@ -0,0 +1,48 @@
+#![warn(clippy::unchecked_clamp_bounds)]
+
+fn unchecked(x: u32, lo: u32, hi: u32) -> u32 {
+    x.clamp(lo, hi)
+    //~^ ERROR: calling `clamp` with bounds that are not checked to be in order
+}
+
+fn unchecked_float(x: f64, lo: f64) -> f64 {
+    x.clamp(lo, 1.0)
+    //~^ ERROR: calling `clamp` with bounds that are not checked to be in order
+}
+
+fn compared_after(x: i32, lo: i32, hi: i32) -> i32 {
+    let y = x.clamp(lo, hi);
+    //~^ ERROR: calling `clamp` with bounds that are not checked to be in order
+    assert!(lo <= hi);
+    y
+}
+
+fn asserted(x: u32, lo: u32, hi: u32) -> u32 {
+    assert!(lo <= hi);
+    x.clamp(lo, hi)
+}
+
+fn early_return(x: u32, lo: u32, hi: u32) -> Option<u32> {
+    if hi < lo {
+        return None;
+    }
+    Some(x.clamp(lo, hi))
+}
+
+fn guarded(x: u32, lo: u32, hi: u32) -> u32 {
+    if lo <= hi {
+        x.clamp(lo, hi)
+    } else {
+        x
+    }
+}
+
+fn constant_bounds(x: u32) -> u32 {
+    x.clamp(1, 10)
+}
+
+fn same_bounds(x: u32, bound: u32) -> u32 {
+    x.clamp(bound, bound)
+}
+
+fn main() {}
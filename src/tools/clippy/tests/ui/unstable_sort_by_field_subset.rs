@@ -0,0 +1,50 @@
+#![warn(clippy::unstable_sort_by_field_subset)]
+#![allow(clippy::comparison_chain, clippy::unnecessary_sort_by)]
+
+use std::cmp::Ordering;
+
+struct Point {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+mod other {
+    pub struct Hidden {
+        pub key: u32,
+        secret: u32,
+    }
+}
+
+fn key(p: &Point) -> i32 {
+    p.x + p.y + p.z
+}
+
+fn main() {
+    let mut points: Vec<Point> = vec![];
+    points.sort_unstable_by(|a, b| a.x.cmp(&b.x));
+    //~^ ERROR: this unstable sort only compares some fields of `Point`
+    points.sort_unstable_by(|a, b| a.x.cmp(&b.x).then(b.y.cmp(&a.y)));
+    //~^ ERROR: this unstable sort only compares some fields of `Point`
+    points.sort_unstable_by(|p, q| {
+        //~^ ERROR: this unstable sort only compares some fields of `Point`
+        if p.x < q.x {
+            Ordering::Less
+        } else if p.x > q.x {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    });
+
+    // Don't lint
+    points.sort_unstable_by(|a, b| a.x.cmp(&b.x).then(a.y.cmp(&b.y)).then(a.z.cmp(&b.z)));
+    points.sort_by(|a, b| a.x.cmp(&b.x));
+    points.sort_unstable_by(|a, b| key(a).cmp(&key(b)));
+    points.sort_unstable_by(|a, b| a.x.cmp(&b.x).then(key(a).cmp(&key(b))));
+    points.sort_unstable_by(|a, b| a.x.cmp(&b.y));
+    let mut hidden: Vec<other::Hidden> = vec![];
+    hidden.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+    let mut pairs = vec![(1, 2)];
+    pairs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+}
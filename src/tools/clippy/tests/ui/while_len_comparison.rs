@@ -0,0 +1,58 @@
+#![warn(clippy::while_len_comparison)]
+
+use std::cell::RefCell;
+
+struct Buffer {
+    items: Vec<u8>,
+}
+
+impl Buffer {
+    fn sum(&self) -> u32 {
+        let mut sum = 0;
+        let mut i = 0;
+        while i < self.items.len() {
+            //~^ ERROR: this loop condition calls `len` on every iteration
+            sum += u32::from(self.items[i]);
+            i += 1;
+        }
+        sum
+    }
+}
+
+fn lint(v: &[u8], text: &str) {
+    let mut i = 0;
+    while i < v.len() {
+        //~^ ERROR: this loop condition calls `len` on every iteration
+        i += 1;
+    }
+    let mut i = 0;
+    while text.chars().count() > i {
+        //~^ ERROR: this loop condition calls `count` on every iteration
+        i += 1;
+    }
+}
+
+fn no_lint(v: &[u8]) {
+    let mut w = vec![1, 2, 3];
+    let mut i = 0;
+    while i < w.len() {
+        w.push(i);
+        i += 1;
+    }
+    let cell = RefCell::new(vec![1]);
+    let mut i = 0;
+    while i < cell.borrow().len() {
+        i += 1;
+    }
+    let r = &mut w;
+    let mut i = 0;
+    while i < r.len() {
+        i += 1;
+    }
+    let mut i = 0;
+    while i < v.len() {
+        i += unsafe { std::ptr::read(&1) };
+    }
+}
+
+fn main() {}
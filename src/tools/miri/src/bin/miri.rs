@@ -13,11 +13,13 @@
 extern crate rustc_metadata;
 extern crate rustc_middle;
 extern crate rustc_session;
+extern crate rustc_target;
 
 use std::env::{self, VarError};
 use std::num::NonZeroU64;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use log::debug;
 
@@ -36,6 +38,7 @@
 use rustc_session::config::{CrateType, ErrorOutputType, OptLevel};
 use rustc_session::search_paths::PathKind;
 use rustc_session::{CtfeBacktrace, EarlyErrorHandler};
+use rustc_target::spec::PanicStrategy;
 
 use miri::{BacktraceStyle, BorrowTrackerMethod, ProvenanceMode, RetagFields};
 
@@ -391,6 +394,22 @@ fn main() {
             miri_config.isolated_op = miri::IsolatedOp::Allow;
         } else if arg == "-Zmiri-disable-leak-backtraces" {
             miri_config.collect_leak_backtraces = false;
+        } else if arg == "-Zmiri-dedup-tls-dtors" {
+            miri_config.dedup_tls_dtors = true;
+        } else if arg == "-Zmiri-tls-posix-strict" {
+            miri_config.tls_posix_strict = true;
+        } else if arg == "-Zmiri-lazy-tls-dtors" {
+            miri_config.lazy_tls_dtors = true;
+        } else if arg == "-Zmiri-validate-tls-dtor-args" {
+            miri_config.validate_tls_dtor_args = true;
+        } else if arg == "-Zmiri-aggregate-tls-dtor-errors" {
+            miri_config.aggregate_tls_dtor_errors = true;
+        } else if arg == "-Zmiri-warn-tls-overwrite" {
+            miri_config.warn_tls_overwrite = true;
+        } else if arg == "-Zmiri-tls-key-origins" {
+            miri_config.tls_key_origins = true;
+        } else if arg == "-Zmiri-tls-exempt-dtor-steps" {
+            miri_config.tls_exempt_dtor_steps = true;
         } else if arg == "-Zmiri-disable-weak-memory-emulation" {
             miri_config.weak_memory_emulation = false;
         } else if arg == "-Zmiri-track-weak-memory-loads" {
@@ -511,6 +530,72 @@ fn main() {
                     ),
             };
             miri_config.cmpxchg_weak_failure_rate = rate;
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-key-space-warning=") {
+            let fraction = match param.parse::<f64>() {
+                Ok(fraction) if fraction >= 0.0 && fraction <= 1.0 => fraction,
+                Ok(_) =>
+                    show_error!("-Zmiri-tls-key-space-warning must be between `0.0` and `1.0`"),
+                Err(err) =>
+                    show_error!(
+                        "-Zmiri-tls-key-space-warning requires a `f64` between `0.0` and `1.0`: {}",
+                        err
+                    ),
+            };
+            miri_config.tls_key_space_warning = Some(fraction);
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-dtor-iteration-cap=") {
+            let cap = param.parse::<u64>().unwrap_or_else(|_| {
+                show_error!("-Zmiri-tls-dtor-iteration-cap must be an integer that fits into u64")
+            });
+            miri_config.tls_dtor_iteration_cap = Some(cap);
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-dtor-clock-advance=") {
+            // The clock is advanced for every destructor, so keep this small enough that it
+            // cannot overflow the virtual clock in any realistic run.
+            let nanos = param.parse::<u64>().ok().filter(|&nanos| nanos <= 1_000_000_000);
+            let nanos = nanos.unwrap_or_else(|| {
+                show_error!(
+                    "-Zmiri-tls-dtor-clock-advance must be an integer of at most 1000000000 (one second)"
+                )
+            });
+            miri_config.tls_dtor_clock_advance = Duration::from_nanos(nanos);
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-dtor-schedule=") {
+            miri_config.tls_dtor_schedule = match param {
+                "exit" => miri::TlsDtorSchedule::OnExit,
+                "yield" => miri::TlsDtorSchedule::OnYield,
+                "join" => miri::TlsDtorSchedule::OnJoin,
+                _ => show_error!("-Zmiri-tls-dtor-schedule must be `exit`, `yield` or `join`"),
+            };
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-deleted-key-stores=") {
+            miri_config.tls_deleted_key_stores = match param {
+                "strict" => miri::DeletedKeyStores::Strict,
+                "lenient" => miri::DeletedKeyStores::Lenient,
+                "resurrect" => miri::DeletedKeyStores::Resurrect,
+                _ =>
+                    show_error!(
+                        "-Zmiri-tls-deleted-key-stores must be `strict`, `lenient` or `resurrect`"
+                    ),
+            };
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-thread-order=") {
+            miri_config.tls_thread_order = Some(match param {
+                "main-last" => miri::ThreadOrder::MainLast,
+                "spawn-order" => miri::ThreadOrder::SpawnOrder,
+                "reverse-spawn" => miri::ThreadOrder::ReverseSpawn,
+                _ =>
+                    show_error!(
+                        "-Zmiri-tls-thread-order must be `main-last`, `spawn-order` or `reverse-spawn`"
+                    ),
+            });
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-dtor-panic=") {
+            miri_config.tls_dtor_panic = Some(match param {
+                "abort" => PanicStrategy::Abort,
+                "unwind" => PanicStrategy::Unwind,
+                _ => show_error!("-Zmiri-tls-dtor-panic must be `abort` or `unwind`"),
+            });
+        } else if let Some(param) = arg.strip_prefix("-Zmiri-tls-libc-flavor=") {
+            miri_config.tls_libc_flavor = Some(match param {
+                "glibc" => miri::LibcFlavor::Glibc,
+                "musl" => miri::LibcFlavor::Musl,
+                _ => show_error!("-Zmiri-tls-libc-flavor must be `glibc` or `musl`"),
+            });
         } else if let Some(param) = arg.strip_prefix("-Zmiri-preemption-rate=") {
             let rate = match param.parse::<f64>() {
                 Ok(rate) if rate >= 0.0 && rate <= 1.0 => rate,
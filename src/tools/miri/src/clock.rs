@@ -99,6 +99,19 @@ pub fn sleep(&self, duration: Duration) {
         }
     }
 
+    /// Let the given duration pass on a virtual clock, without sleeping. Time on the host cannot
+    /// be moved, so this does nothing when the host clock is used.
+    pub fn advance(&self, duration: Duration) {
+        match &self.kind {
+            ClockKind::Host { .. } => {}
+            ClockKind::Virtual { nanoseconds } => {
+                // The clock stops at its maximum instead of overflowing.
+                let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+                nanoseconds.update(|x| x.saturating_add(nanos));
+            }
+        }
+    }
+
     /// Return the `anchor` instant, to convert between monotone instants and durations relative to the anchor.
     pub fn anchor(&self) -> Instant {
         match &self.kind {
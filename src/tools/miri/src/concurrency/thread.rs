@@ -448,6 +448,15 @@ pub fn get_active_thread_id(&self) -> ThreadId {
         self.active_thread
     }
 
+    /// Get the id of the currently active thread, if the thread manager has an entry for it and
+    /// the thread has not terminated. This only returns `None` if the bookkeeping is inconsistent.
+    pub fn find_active_thread_id(&self) -> Option<ThreadId> {
+        self.threads
+            .get(self.active_thread)
+            .filter(|thread| thread.state != ThreadState::Terminated)
+            .map(|_| self.active_thread)
+    }
+
     /// Get the total number of threads that were ever spawn by this program.
     pub fn get_total_thread_count(&self) -> usize {
         self.threads.len()
@@ -1056,7 +1065,11 @@ fn run_threads(&mut self) -> InterpResult<'tcx, !> {
             }
             match this.machine.threads.schedule(&this.machine.clock)? {
                 SchedulingAction::ExecuteStep => {
-                    if !this.step()? {
+                    if this.machine.tls.any_dtors_triggered() {
+                        this.run_triggered_tls_dtors()?;
+                    }
+                    // Undefined Behavior in TLS destructors may be collected instead.
+                    if !this.step().or_else(|err| this.recover_tls_dtor_error(err))? {
                         // See if this thread can do something else.
                         match this.run_on_stack_empty()? {
                             Poll::Pending => {} // keep going
@@ -1103,3 +1116,21 @@ fn terminate_active_thread(&mut self, tls_alloc_action: TlsAllocAction) -> Inter
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_active_thread_id() {
+        let mut threads = ThreadManager::default();
+        assert_eq!(threads.find_active_thread_id(), Some(ThreadId::new(0)));
+
+        threads.threads[ThreadId::new(0)].state = ThreadState::Terminated;
+        assert_eq!(threads.find_active_thread_id(), None);
+
+        threads.threads[ThreadId::new(0)].state = ThreadState::Enabled;
+        threads.active_thread = ThreadId::new(1);
+        assert_eq!(threads.find_active_thread_id(), None);
+    }
+}
@@ -4,6 +4,7 @@
 use log::trace;
 
 use rustc_errors::DiagnosticMessage;
+use rustc_index::Idx;
 use rustc_span::{SpanData, Symbol, DUMMY_SP};
 use rustc_target::abi::{Align, Size};
 
@@ -129,6 +130,30 @@ pub enum NonHaltingDiagnostic {
         details: bool,
     },
     WeakMemoryOutdatedLoad,
+    TlsKeySpaceNearlyExhausted {
+        used: u128,
+        max: u128,
+    },
+    DanglingTlsDtorArg {
+        alloc_id: AllocId,
+    },
+    TlsTeardownSkipped {
+        thread: ThreadId,
+    },
+    TlsDtorUnwinding {
+        thread: ThreadId,
+        action: DtorUnwindAction,
+    },
+    TlsValueOverwritten {
+        key: TlsKey,
+        thread: ThreadId,
+        /// Where the key was created, if key origins are recorded.
+        origin: Option<SpanData>,
+    },
+    TlsStoreToDeletedKeyIgnored {
+        key: TlsKey,
+        thread: ThreadId,
+    },
 }
 
 /// Level of Miri specific diagnostics
@@ -437,6 +462,39 @@ pub fn report_leaks<'mir, 'tcx>(
     }
 }
 
+/// Report the TLS destructors that failed, as a single error that labels where each of them
+/// failed. Destructors that could not be called at all have no span, and get a note instead. If
+/// key origins are recorded, this also points at where each failing key was created.
+pub fn report_tls_dtor_errors<'mir, 'tcx>(
+    ecx: &InterpCx<'mir, 'tcx, MiriMachine<'mir, 'tcx>>,
+    errors: Vec<DtorError>,
+) {
+    let title = match errors.len() {
+        1 => "a TLS destructor failed".to_string(),
+        n => format!("{n} TLS destructors failed"),
+    };
+    // Point at the first error; the others are labeled.
+    let span = errors.iter().find_map(|error| error.span).map_or(DUMMY_SP, |span| span.span());
+    let mut err = ecx.tcx.sess.struct_span_err(span, title);
+    for DtorError { thread, key, span, origin, msg } in errors {
+        let msg = match key {
+            Some(key) => format!("the destructor of key {key} on {thread:?} failed: {msg}"),
+            None => format!("the thread destructor on {thread:?} failed: {msg}"),
+        };
+        match span {
+            Some(span) => err.span_label(span.span(), msg),
+            None => err.note(msg),
+        };
+        if let (Some(key), Some(origin)) = (key, origin) {
+            err.span_note(origin.span(), format!("TLS key {key} was created here"));
+        }
+    }
+    if span == DUMMY_SP {
+        err.note("(no span available)");
+    }
+    err.emit();
+}
+
 /// Report an error or note (depending on the `error` argument) with the given stacktrace.
 /// Also emits a full stacktrace of the interpreter stack.
 /// We want to present a multi-line span message for some errors. Diagnostics do not support this
@@ -519,14 +577,27 @@ impl<'mir, 'tcx> MiriMachine<'mir, 'tcx> {
     pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
         use NonHaltingDiagnostic::*;
 
-        let stacktrace =
-            MiriInterpCx::generate_stacktrace_from_stack(self.threads.active_thread_stack());
+        // Do not assume that the active thread exists, this is also used to report inconsistent
+        // thread bookkeeping.
+        let active_thread = self.threads.get_active_thread_id();
+        let stack = self.threads.all_stacks().nth(active_thread.index()).unwrap_or_default();
+        let stacktrace = MiriInterpCx::generate_stacktrace_from_stack(stack);
         let (stacktrace, _was_pruned) = prune_stacktrace(stacktrace, self);
 
         let (title, diag_level) = match &e {
             RejectedIsolatedOp(_) =>
                 ("operation rejected by isolation".to_string(), DiagLevel::Warning),
             Int2Ptr { .. } => ("integer-to-pointer cast".to_string(), DiagLevel::Warning),
+            TlsKeySpaceNearlyExhausted { .. } =>
+                ("TLS key space nearly exhausted".to_string(), DiagLevel::Warning),
+            DanglingTlsDtorArg { .. } =>
+                ("dangling TLS destructor argument".to_string(), DiagLevel::Warning),
+            TlsTeardownSkipped { .. } => ("TLS teardown skipped".to_string(), DiagLevel::Warning),
+            TlsDtorUnwinding { .. } =>
+                ("unwinding out of a TLS destructor".to_string(), DiagLevel::Warning),
+            TlsValueOverwritten { .. } => ("TLS value overwritten".to_string(), DiagLevel::Warning),
+            TlsStoreToDeletedKeyIgnored { .. } =>
+                ("TLS store to a deleted key ignored".to_string(), DiagLevel::Warning),
             CreatedPointerTag(..)
             | PoppedPointerTag(..)
             | CreatedCallId(..)
@@ -560,6 +631,32 @@ pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
             Int2Ptr { .. } => format!("integer-to-pointer cast"),
             WeakMemoryOutdatedLoad =>
                 format!("weak memory emulation: outdated value returned from load"),
+            TlsKeySpaceNearlyExhausted { used, max } =>
+                format!("{used} out of {max} possible TLS keys have been created"),
+            DanglingTlsDtorArg { alloc_id } =>
+                format!(
+                    "a TLS destructor is called with a pointer to {alloc_id:?}, which has already been freed"
+                ),
+            TlsTeardownSkipped { thread } =>
+                format!(
+                    "the active thread {thread:?} has no running entry in the thread manager, so its TLS destructors are not run"
+                ),
+            TlsDtorUnwinding { thread, action: DtorUnwindAction::Abort } =>
+                format!(
+                    "a TLS destructor on {thread:?} unwinds out of its frame, which aborts the process on this target"
+                ),
+            TlsDtorUnwinding { thread, action: DtorUnwindAction::Continue } =>
+                format!(
+                    "a TLS destructor on {thread:?} unwinds out of its frame, and nothing stops the unwinding on this target"
+                ),
+            TlsValueOverwritten { key, thread, .. } =>
+                format!(
+                    "storing to TLS key {key} on {thread:?} overwrites a value whose destructor then never runs for it"
+                ),
+            TlsStoreToDeletedKeyIgnored { key, thread } =>
+                format!(
+                    "the TLS destructors of {thread:?} store to TLS key {key}, which was deleted, so the store is ignored"
+                ),
         };
 
         let notes = match &e {
@@ -568,6 +665,8 @@ pub fn emit_diagnostic(&self, e: NonHaltingDiagnostic) {
                 // identical diagnostics are being deduplicated.
                 vec![(None, format!("so far, {block_count} basic blocks have been executed"))]
             }
+            TlsValueOverwritten { key, origin: Some(origin), .. } =>
+                vec![(Some(*origin), format!("TLS key {key} was created here"))],
             _ => vec![],
         };
 
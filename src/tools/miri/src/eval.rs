@@ -6,13 +6,14 @@
 use std::path::PathBuf;
 use std::task::Poll;
 use std::thread;
+use std::time::Duration;
 
 use log::info;
 use rustc_middle::ty::Ty;
 
 use crate::borrow_tracker::RetagFields;
 use crate::concurrency::thread::TlsAllocAction;
-use crate::diagnostics::report_leaks;
+use crate::diagnostics::{report_leaks, report_tls_dtor_errors};
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def::Namespace;
 use rustc_hir::def_id::DefId;
@@ -22,6 +23,7 @@
     TyCtxt,
 };
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
 
 use rustc_session::config::EntryFnType;
 
@@ -154,6 +156,44 @@ pub struct MiriConfig {
     pub page_size: Option<u64>,
     /// Whether to collect a backtrace when each allocation is created, just in case it leaks.
     pub collect_leak_backtraces: bool,
+    /// Whether the TLS destructor of a thread runs at most once per distinct destructor and
+    /// argument during that thread's teardown.
+    pub dedup_tls_dtors: bool,
+    /// Whether TLS follows POSIX strictly, overriding the destructor deduplication, error
+    /// aggregation, iteration cap and unwinding options where they deviate from it.
+    pub tls_posix_strict: bool,
+    /// Warn once more than this fraction of the TLS key space has been used.
+    pub tls_key_space_warning: Option<f64>,
+    /// Whether pthread TLS destructors are only resolved when they are about to run.
+    pub lazy_tls_dtors: bool,
+    /// The libc whose reuse of pthread TLS keys is emulated, if any.
+    pub tls_libc_flavor: Option<LibcFlavor>,
+    /// Whether to warn when a pthread TLS destructor is called with a pointer to freed memory.
+    pub validate_tls_dtor_args: bool,
+    /// Whether Undefined Behavior of TLS destructors is reported together at the end, instead of
+    /// stopping the program at the first one.
+    pub aggregate_tls_dtor_errors: bool,
+    /// The maximum number of passes over the pthread TLS keys when a thread exits, if any.
+    pub tls_dtor_iteration_cap: Option<u64>,
+    /// Whether to warn when a store to a pthread TLS key overwrites a value that still needs its
+    /// destructor.
+    pub warn_tls_overwrite: bool,
+    /// Whether to remember where each TLS key was created, for pointing at it in diagnostics.
+    pub tls_key_origins: bool,
+    /// How far the virtual clock is advanced before each pthread or macOS TLS destructor is called.
+    pub tls_dtor_clock_advance: Duration,
+    /// When pthread TLS destructors run, in addition to thread exit.
+    pub tls_dtor_schedule: TlsDtorSchedule,
+    /// What happens when a TLS destructor stores to a deleted key.
+    pub tls_deleted_key_stores: DeletedKeyStores,
+    /// Whether the basic blocks executed by TLS destructors are left out of the basic block count.
+    pub tls_exempt_dtor_steps: bool,
+    /// The order in which the TLS of all threads is torn down when the main thread exits. `None`
+    /// means only the TLS of the main thread is.
+    pub tls_thread_order: Option<ThreadOrder>,
+    /// The panic strategy for panics in TLS destructors. `None` means the strategy of the session
+    /// is used.
+    pub tls_dtor_panic: Option<PanicStrategy>,
 }
 
 impl Default for MiriConfig {
@@ -190,6 +230,22 @@ fn default() -> MiriConfig {
             num_cpus: 1,
             page_size: None,
             collect_leak_backtraces: true,
+            dedup_tls_dtors: false,
+            tls_posix_strict: false,
+            tls_key_space_warning: None,
+            lazy_tls_dtors: false,
+            tls_libc_flavor: None,
+            validate_tls_dtor_args: false,
+            aggregate_tls_dtor_errors: false,
+            tls_dtor_iteration_cap: None,
+            warn_tls_overwrite: false,
+            tls_key_origins: false,
+            tls_dtor_clock_advance: Duration::ZERO,
+            tls_dtor_schedule: TlsDtorSchedule::OnExit,
+            tls_deleted_key_stores: DeletedKeyStores::Strict,
+            tls_exempt_dtor_steps: false,
+            tls_thread_order: None,
+            tls_dtor_panic: None,
         }
     }
 }
@@ -200,6 +256,8 @@ enum MainThreadState {
     #[default]
     Running,
     TlsDtors(tls::TlsDtorsState),
+    /// Tearing down the TLS of all threads, see `TlsData::set_thread_order`.
+    AllTlsDtors(ThreadOrder),
     Yield {
         remaining: u32,
     },
@@ -214,24 +272,20 @@ fn on_main_stack_empty<'tcx>(
         use MainThreadState::*;
         match self {
             Running => {
-                *self = TlsDtors(Default::default());
+                *self = match this.machine.tls.thread_order() {
+                    Some(order) => AllTlsDtors(order),
+                    None => TlsDtors(Default::default()),
+                };
             }
             TlsDtors(state) =>
                 match state.on_stack_empty(this)? {
                     Poll::Pending => {} // just keep going
-                    Poll::Ready(()) => {
-                        // Give background threads a chance to finish by yielding the main thread a
-                        // couple of times -- but only if we would also preempt threads randomly.
-                        if this.machine.preemption_rate > 0.0 {
-                            // There is a non-zero chance they will yield back to us often enough to
-                            // make Miri terminate eventually.
-                            *self = Yield { remaining: MAIN_THREAD_YIELDS_AT_SHUTDOWN };
-                        } else {
-                            // The other threads did not get preempted, so no need to yield back to
-                            // them.
-                            *self = Done;
-                        }
-                    }
+                    Poll::Ready(()) => *self = Self::after_tls_dtors(this),
+                },
+            AllTlsDtors(order) =>
+                match this.run_all_tls_dtors(*order)? {
+                    Poll::Pending => {} // just keep going
+                    Poll::Ready(()) => *self = Self::after_tls_dtors(this),
                 },
             Yield { remaining } =>
                 match remaining.checked_sub(1) {
@@ -254,6 +308,21 @@ fn on_main_stack_empty<'tcx>(
         }
         Ok(Poll::Pending)
     }
+
+    /// The state to go on with once the TLS destructors are done.
+    fn after_tls_dtors(this: &MiriInterpCx<'_, '_>) -> Self {
+        // Give background threads a chance to finish by yielding the main thread a
+        // couple of times -- but only if we would also preempt threads randomly.
+        if this.machine.preemption_rate > 0.0 {
+            // There is a non-zero chance they will yield back to us often enough to
+            // make Miri terminate eventually.
+            MainThreadState::Yield { remaining: MAIN_THREAD_YIELDS_AT_SHUTDOWN }
+        } else {
+            // The other threads did not get preempted, so no need to yield back to
+            // them.
+            MainThreadState::Done
+        }
+    }
 }
 
 /// Returns a freshly created `InterpCx`.
@@ -458,8 +527,19 @@ pub fn eval_entry<'tcx>(
         EnvVars::cleanup(&mut ecx).expect("error during env var cleanup");
     }
 
+    // Report the TLS destructor errors that were collected instead of stopping the program. They
+    // take precedence over the return code, like leaks do.
+    let dtor_errors = ecx.machine.tls.take_dtor_errors();
+    let had_dtor_errors = !dtor_errors.is_empty();
+    if had_dtor_errors {
+        report_tls_dtor_errors(&ecx, dtor_errors);
+    }
+
     // Process the result.
     let (return_code, leak_check) = report_error(&ecx, res)?;
+    if had_dtor_errors {
+        return None;
+    }
     if leak_check && !ignore_leaks {
         // Check for thread leaks.
         if !ecx.have_all_terminated() {
@@ -94,7 +94,12 @@
 pub use crate::shims::os_str::EvalContextExt as _;
 pub use crate::shims::panic::{CatchUnwindData, EvalContextExt as _};
 pub use crate::shims::time::EvalContextExt as _;
-pub use crate::shims::tls::TlsData;
+pub use crate::shims::tls::{
+    DeletedKeyStores, DtorError, DtorIsolation, DtorReturn, DtorStatus, DtorUnwindAction,
+    EvalContextExt as _, LibcFlavor, ModuleId, PendingDtor, PendingDtorKind, TeardownMetrics,
+    TeardownStage, ThreadOrder, TlsChange, TlsData, TlsDataBuilder, TlsDiff, TlsDtor,
+    TlsDtorSchedule, TlsError, TlsEvent, TlsOp, TlsResult, TlsSnapshot,
+};
 pub use crate::shims::EvalContextExt as _;
 
 pub use crate::borrow_tracker::stacked_borrows::{
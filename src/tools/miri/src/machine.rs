@@ -596,7 +596,7 @@ pub(crate) fn new(config: &MiriConfig, layout_cx: LayoutCx<'tcx, TyCtxt<'tcx>>)
             argc: None,
             argv: None,
             cmd_line: None,
-            tls: TlsData::default(),
+            tls: TlsData::new(config),
             isolated_op: config.isolated_op,
             validate: config.validate,
             enforce_abi: config.check_abi,
@@ -1363,16 +1363,22 @@ fn stack_mut<'a>(
     }
 
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
-        ecx.machine.basic_block_count += 1u64; // a u64 that is only incremented by 1 will "never" overflow
-        ecx.machine.since_gc += 1;
-        // Possibly report our progress.
-        if let Some(report_progress) = ecx.machine.report_progress {
-            if ecx.machine.basic_block_count % u64::from(report_progress) == 0 {
-                ecx.emit_diagnostic(NonHaltingDiagnostic::ProgressReport {
-                    block_count: ecx.machine.basic_block_count,
-                });
+        // TLS destructors can be exempt from the basic block count.
+        if !ecx.machine.tls.any_dtors_running()
+            || ecx.machine.tls.count_step(ecx.get_active_thread())
+        {
+            // A u64 that is only incremented by 1 will "never" overflow.
+            ecx.machine.basic_block_count += 1u64;
+            // Possibly report our progress.
+            if let Some(report_progress) = ecx.machine.report_progress {
+                if ecx.machine.basic_block_count % u64::from(report_progress) == 0 {
+                    ecx.emit_diagnostic(NonHaltingDiagnostic::ProgressReport {
+                        block_count: ecx.machine.basic_block_count,
+                    });
+                }
             }
         }
+        ecx.machine.since_gc += 1;
 
         // Search for BorTags to find all live pointers, then remove all other tags from borrow
         // stacks.
@@ -1389,6 +1395,10 @@ fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx>
         // Make sure some time passes.
         ecx.machine.clock.tick();
 
+        if ecx.machine.tls.any_dtor_frames() {
+            ecx.before_tls_dtor_terminator()?;
+        }
+
         Ok(())
     }
 
@@ -1433,6 +1443,9 @@ fn after_stack_pop(
         if let Some(profiler) = ecx.machine.profiler.as_ref() {
             profiler.finish_recording_interval_event(timing.unwrap());
         }
+        if !unwinding {
+            ecx.after_tls_dtor_frame_pop()?;
+        }
         res
     }
 
@@ -501,6 +501,16 @@ fn emulate_foreign_item_inner(
                 }
                 this.machine.static_roots.push(alloc_id);
             }
+            "miri_set_tls_dtor_wrapper" => {
+                let [wrapper] = this.check_shim(abi, Abi::Rust, link_name, args)?;
+                let wrapper = this.read_pointer(wrapper)?;
+                let wrapper = if this.ptr_is_null(wrapper)? {
+                    None
+                } else {
+                    Some(this.get_ptr_fn(wrapper)?.as_instance()?)
+                };
+                this.machine.tls.set_dtor_wrapper(wrapper);
+            }
             "miri_host_to_target_path" => {
                 let [ptr, out, out_size] = this.check_shim(abi, Abi::Rust, link_name, args)?;
                 let ptr = this.read_pointer(ptr)?;
@@ -61,6 +61,8 @@ fn handle_miri_start_panic(
 
         // Get the raw pointer stored in arg[0] (the panic payload).
         let [payload] = this.check_shim(abi, Abi::Rust, link_name, args)?;
+        // Panics in TLS destructors may have to abort instead.
+        this.before_tls_dtor_panic()?;
         let payload = this.read_scalar(payload)?;
         let thread = this.active_thread_mut();
         thread.panic_payloads.push(payload);
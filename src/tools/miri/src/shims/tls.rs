@@ -1,36 +1,616 @@
 //! Implement thread-local storage.
 
+use std::cell::RefCell;
 use std::collections::btree_map::Entry as BTreeEntry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::{self, Write as _};
+use std::rc::Rc;
 use std::task::Poll;
+use std::time::Duration;
 
+use either::Either;
 use log::trace;
 
-use rustc_middle::ty;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_middle::{mir, ty};
+use rustc_span::SpanData;
 use rustc_target::abi::{HasDataLayout, Size};
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
 
 use crate::*;
 
 pub type TlsKey = u128;
 
+/// The number of passes over the pthread keys after which POSIX allows to stop calling
+/// destructors, see `TlsData::set_dtor_iteration_cap`.
+const PTHREAD_DESTRUCTOR_ITERATIONS: u64 = 4;
+
+/// Identifies a loaded module, like a shared object, whose TLS keys can be deleted together when
+/// it is unloaded, see `TlsData::delete_keys_for_module`.
+pub type ModuleId = u64;
+
+pub type TlsResult<'tcx, T = ()> = Result<T, TlsError<'tcx>>;
+
+/// The ways in which TLS operations can fail.
+#[derive(Debug)]
+pub enum TlsError<'tcx> {
+    /// Deleting a key that does not exist (anymore).
+    DeleteNonexistentKey { key: TlsKey },
+    /// Loading from a key that does not exist (anymore).
+    LoadNonexistentKey { key: TlsKey, thread: ThreadId },
+    /// Storing to a key that does not exist (anymore).
+    StoreNonexistentKey { key: TlsKey, thread: ThreadId },
+    /// Querying the destructor of a key that does not exist (anymore).
+    DtorOfNonexistentKey { key: TlsKey },
+    /// Setting the destructor of a key that does not exist (anymore).
+    SetDtorOfNonexistentKey { key: TlsKey },
+    /// Setting a destructor for a key that was created as non-destructible.
+    DtorOfNonDestructibleKey { key: TlsKey },
+    /// Running the destructor of a key that does not exist (anymore).
+    RunDtorOfNonexistentKey { key: TlsKey, thread: ThreadId },
+    /// All keys that fit into the key type have been used up.
+    KeySpaceExhausted,
+    /// The configured maximum number of live keys has been reached.
+    KeyLimitReached { max_keys: usize },
+    /// The configured maximum number of values per thread has been reached by `thread`.
+    ThreadValueLimitReached { thread: ThreadId, max_values: usize },
+    /// The key creation veto rejected a key with the given message, see
+    /// `TlsData::set_key_creation_veto`.
+    KeyCreationVetoed { message: String },
+    /// A key was created after key creation was frozen.
+    KeysFrozen,
+    /// A second macOS thread destructor was set for the same thread.
+    MultipleMacosThreadDtors { thread: ThreadId },
+    /// A macOS thread destructor was set while the thread is running its destructors.
+    MacosThreadDtorWhileDestructing { thread: ThreadId },
+    /// Migrating a thread that is running its destructors.
+    MigrateWhileDestructing { from: ThreadId },
+    /// Migrating to a thread that already has TLS state.
+    MigrateToOccupiedThread { from: ThreadId, to: ThreadId },
+    /// Restoring the values of a thread that is running its destructors.
+    RestoreWhileDestructing { thread: ThreadId },
+    /// Running the teardown of a thread twice while it is already running its destructors, see
+    /// `TlsData::double_teardown`.
+    DoubleTeardown { thread: ThreadId },
+    /// A thread that is running its destructors loaded the TLS of another thread that is running
+    /// its destructors too, see `TlsData::set_dtor_isolation`.
+    CrossTeardownLoad { key: TlsKey, reader: ThreadId, owner: ThreadId },
+    /// The same key was declared more than once in a `TlsDataBuilder`.
+    DuplicateKey { key: TlsKey },
+    /// Replaying a `TlsOp::CreateKey` created a different key than the one that was recorded.
+    ReplayKeyMismatch { expected: TlsKey, actual: TlsKey },
+    /// Any other interpreter error that happened during a TLS operation.
+    Interp(InterpErrorInfo<'tcx>),
+}
+
+impl fmt::Display for TlsError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TlsError::*;
+        match self {
+            DeleteNonexistentKey { key } => write!(f, "removing a nonexistent TLS key: {key}"),
+            LoadNonexistentKey { key, .. } =>
+                write!(f, "loading from a non-existing TLS key: {key}"),
+            StoreNonexistentKey { key, .. } =>
+                write!(f, "storing to a non-existing TLS key: {key}"),
+            DtorOfNonexistentKey { key } =>
+                write!(f, "querying the destructor of a non-existing TLS key: {key}"),
+            SetDtorOfNonexistentKey { key } =>
+                write!(f, "setting the destructor of a non-existing TLS key: {key}"),
+            DtorOfNonDestructibleKey { key } =>
+                write!(
+                    f,
+                    "setting a destructor for TLS key {key}, which was created as non-destructible"
+                ),
+            RunDtorOfNonexistentKey { key, .. } =>
+                write!(f, "running the destructor of a non-existing TLS key: {key}"),
+            KeySpaceExhausted => write!(f, "we ran out of TLS key space"),
+            KeyLimitReached { max_keys } => write!(f, "TLS key limit of {max_keys} reached"),
+            ThreadValueLimitReached { thread, max_values } =>
+                write!(f, "TLS value limit for thread {} of {max_values} reached", thread.to_u32()),
+            KeyCreationVetoed { message } => write!(f, "creating a TLS key was vetoed: {message}"),
+            KeysFrozen => write!(f, "TLS keys frozen: no new keys may be created"),
+            MultipleMacosThreadDtors { .. } =>
+                write!(
+                    f,
+                    "setting more than one thread local storage destructor for the same thread is not supported"
+                ),
+            MacosThreadDtorWhileDestructing { .. } =>
+                write!(
+                    f,
+                    "setting a thread local storage destructor while the thread is running its destructors is not supported"
+                ),
+            MigrateWhileDestructing { from } =>
+                write!(
+                    f,
+                    "migrating the thread local storage of {from:?} while it is running its destructors"
+                ),
+            MigrateToOccupiedThread { from, to } =>
+                write!(
+                    f,
+                    "migrating the thread local storage of {from:?} to {to:?}, which already has thread local storage"
+                ),
+            RestoreWhileDestructing { thread } =>
+                write!(
+                    f,
+                    "restoring the thread local storage of {thread:?} while it is running its destructors"
+                ),
+            DoubleTeardown { thread } =>
+                write!(
+                    f,
+                    "running the teardown of {thread:?} twice while it is already running its destructors"
+                ),
+            CrossTeardownLoad { key, reader, owner } =>
+                write!(
+                    f,
+                    "thread {} loaded TLS key {key} of thread {} while both are running their destructors",
+                    reader.to_u32(),
+                    owner.to_u32()
+                ),
+            DuplicateKey { key } => write!(f, "TLS key {key} was declared more than once"),
+            ReplayKeyMismatch { expected, actual } =>
+                write!(f, "replaying the creation of TLS key {expected} created key {actual}"),
+            Interp(err) => write!(f, "{err:?}"),
+        }
+    }
+}
+
+impl<'tcx> From<InterpErrorInfo<'tcx>> for TlsError<'tcx> {
+    fn from(err: InterpErrorInfo<'tcx>) -> Self {
+        TlsError::Interp(err)
+    }
+}
+
+impl<'tcx> From<TlsError<'tcx>> for InterpErrorInfo<'tcx> {
+    fn from(err: TlsError<'tcx>) -> Self {
+        use TlsError::*;
+        match err {
+            Interp(err) => err,
+            DeleteNonexistentKey { .. }
+            | LoadNonexistentKey { .. }
+            | StoreNonexistentKey { .. }
+            | DtorOfNonexistentKey { .. }
+            | SetDtorOfNonexistentKey { .. }
+            | RunDtorOfNonexistentKey { .. }
+            | CrossTeardownLoad { .. } => err_ub_format!("{err}").into(),
+            KeySpaceExhausted
+            | KeyLimitReached { .. }
+            | ThreadValueLimitReached { .. }
+            | KeyCreationVetoed { .. }
+            | KeysFrozen
+            | MultipleMacosThreadDtors { .. }
+            | MacosThreadDtorWhileDestructing { .. }
+            | MigrateWhileDestructing { .. }
+            | MigrateToOccupiedThread { .. }
+            | RestoreWhileDestructing { .. }
+            | DoubleTeardown { .. }
+            | DuplicateKey { .. }
+            | ReplayKeyMismatch { .. }
+            | DtorOfNonDestructibleKey { .. } => err_unsup_format!("{err}").into(),
+        }
+    }
+}
+
+/// A libc whose reuse of pthread TLS keys can be emulated. Destructors run in key order on all
+/// of them, so which key a new key reuses determines when its destructor runs relative to the
+/// others. Miri never hands out key 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibcFlavor {
+    /// A new key is the lowest free key, so keys created after a key was deleted have their
+    /// destructors run first.
+    Glibc,
+    /// A new key is the first free key starting at the most recently created key, wrapping
+    /// around to the lowest key after `PTHREAD_KEYS_MAX` (128), so deleted keys are only reused
+    /// once all keys above them were handed out.
+    Musl,
+}
+
+impl LibcFlavor {
+    /// The `PTHREAD_KEYS_MAX` of this libc.
+    fn max_keys(self) -> TlsKey {
+        match self {
+            LibcFlavor::Glibc => 1024,
+            LibcFlavor::Musl => 128,
+        }
+    }
+}
+
+/// The destructor of a TLS key.
+#[derive(Clone, Copy, Debug)]
+pub enum TlsDtor<'tcx> {
+    /// The destructor was resolved when the key was created.
+    Resolved(ty::Instance<'tcx>),
+    /// The (non-NULL) function pointer of the destructor, which is only resolved when the
+    /// destructor is about to run.
+    Lazy(Pointer<Option<Provenance>>),
+}
+
+impl fmt::Display for TlsDtor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsDtor::Resolved(instance) => write!(f, "{instance}"),
+            TlsDtor::Lazy(ptr) => write!(f, "unresolved {ptr:?}"),
+        }
+    }
+}
+
+impl<'tcx> TlsDtor<'tcx> {
+    /// Identifies this destructor for deduplicating destructor calls.
+    fn id(&self) -> DtorId<'tcx> {
+        match *self {
+            TlsDtor::Resolved(instance) => DtorId::Instance(instance),
+            TlsDtor::Lazy(ptr) => {
+                let (prov, offset) = ptr.into_parts();
+                DtorId::Ptr((prov.and_then(|prov| prov.get_alloc_id()), offset.bytes().into()))
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TlsEntry<'tcx> {
     /// The data for this key. None is used to represent NULL.
     /// (We normalize this early to avoid having to do a NULL-ptr-test each time we access the data.)
     data: BTreeMap<ThreadId, Scalar<Provenance>>,
-    dtor: Option<ty::Instance<'tcx>>,
+    dtor: Option<TlsDtor<'tcx>>,
+    /// The module the key belongs to, if any.
+    module: Option<ModuleId>,
+    /// Where the key was created, if key origins are recorded.
+    origin: Option<SpanData>,
+    /// Whether the key must never have a destructor, see `create_non_destructible_tls_key`.
+    non_destructible: bool,
 }
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 struct RunningDtorState {
     /// The last TlsKey used to retrieve a TLS destructor. `None` means that we
     /// have not tried to retrieve a TLS destructor yet or that we already tried
     /// all keys.
     last_key: Option<TlsKey>,
+    /// The number of passes over the keys that were started so far.
+    passes: u64,
 }
 
-#[derive(Debug)]
+/// The stack frame of a TLS destructor that is currently running.
+#[derive(Clone, Copy, Debug)]
+struct DtorFrame {
+    /// The index of the frame in the stack of its thread.
+    frame: usize,
+    /// The key the destructor belongs to, if any.
+    key: Option<TlsKey>,
+}
+
+/// Undefined Behavior of a TLS destructor, collected instead of stopping the program when
+/// `aggregate_dtor_errors` is set. This is either a pthread destructor that could not be called,
+/// or a destructor that caused Undefined Behavior while it ran.
+#[derive(Clone, Debug)]
+pub struct DtorError {
+    /// The thread that ran the destructor.
+    pub thread: ThreadId,
+    /// The key the destructor belongs to. `None` for destructors that do not belong to a key,
+    /// like the macOS thread destructor.
+    pub key: Option<TlsKey>,
+    /// Where the Undefined Behavior happened. `None` if the destructor could not be called.
+    pub span: Option<SpanData>,
+    /// Where the key was created, if key origins are recorded.
+    pub origin: Option<SpanData>,
+    /// The rendered Undefined Behavior error.
+    pub msg: String,
+}
+
+/// How a TLS destructor's stack frame was left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DtorStatus {
+    /// The destructor returned normally.
+    Returned,
+    /// The destructor unwound out of its frame, which it must never do.
+    Unwound,
+}
+
+/// What happens when a TLS destructor unwinds out of its frame. This depends on who calls the
+/// destructors on the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DtorUnwindAction {
+    /// The destructors are called from the C library, which cannot be unwound through, so the
+    /// process aborts.
+    Abort,
+    /// Nothing stops the unwinding, so it continues past the destructor's frame, which is
+    /// Undefined Behavior.
+    Continue,
+}
+
+impl DtorUnwindAction {
+    /// Returns what happens on the target operating system `os`.
+    pub fn for_os(os: &str) -> Self {
+        match os {
+            "linux" | "freebsd" | "android" | "macos" => DtorUnwindAction::Abort,
+            _ => DtorUnwindAction::Continue,
+        }
+    }
+}
+
+/// One stage of the TLS teardown of a thread, see `TeardownStage::for_os`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TeardownStage {
+    /// The macOS thread destructor registered with `_tlv_atexit`, which std uses for all
+    /// `thread_local!` destructors.
+    MacosThreadDtor,
+    /// The destructors of the pthread keys, in passes until no values are left.
+    PthreadDtors,
+    /// The TLS callback that std registers on Windows.
+    WindowsCallback,
+}
+
+impl TeardownStage {
+    /// Returns the stages that the teardown of a thread goes through on the target operating
+    /// system `os`, in order. Each stage starts once the destructors of the previous one have
+    /// returned. An empty list means that TLS destructors are not supported.
+    pub fn for_os(os: &str) -> &'static [TeardownStage] {
+        use TeardownStage::*;
+        match os {
+            // We do not provide `__cxa_thread_atexit_impl`, so std falls back to registering its
+            // destructors with a pthread key, and they run among the other pthread destructors.
+            "linux" | "freebsd" | "android" => &[PthreadDtors],
+            // The macOS thread wide destructor runs "before any TLS slots get freed".
+            "macos" => &[MacosThreadDtor, PthreadDtors],
+            "windows" => &[WindowsCallback],
+            // FIXME: should we do something on wasi?
+            _ => &[],
+        }
+    }
+}
+
+/// When the pthread destructors of a thread run, see `TlsData::set_dtor_schedule`. This is for
+/// modeling runtimes that run TLS destructors at scheduling points other than thread exit. The
+/// regular teardown always happens when the thread exits, for the values that are still left then.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TlsDtorSchedule {
+    /// Only when the thread exits.
+    #[default]
+    OnExit,
+    /// Also whenever the thread yields with `sched_yield`.
+    OnYield,
+    /// Also whenever the thread joins another thread with `pthread_join`.
+    OnJoin,
+}
+
+/// What happens when a thread that is running its destructors stores to a key that was deleted,
+/// see `TlsData::set_deleted_key_stores`. This is for modeling how tolerant runtimes are of
+/// destructors that use keys which were already torn down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DeletedKeyStores {
+    /// The store is undefined behavior, like any store to a nonexistent key.
+    #[default]
+    Strict,
+    /// The store is ignored, with a warning.
+    Lenient,
+    /// The key is created again, without a destructor, and the value is stored.
+    Resurrect,
+}
+
+/// What happens when a thread that is running its destructors loads the TLS of another thread
+/// that is running its destructors too, see `TlsData::set_dtor_isolation`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DtorIsolation {
+    /// The load returns the value, however far the other thread's teardown got.
+    #[default]
+    Off,
+    /// The load is undefined behavior.
+    Error,
+    /// The load returns NULL, as if the other thread's destructors were already done.
+    Null,
+}
+
+/// The order in which the TLS of all threads is torn down at the end of the program, see
+/// `EvalContextExt::run_all_tls_dtors`. Threads are spawned in the order of their IDs, starting
+/// with the main thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThreadOrder {
+    /// The other threads in spawn order, then the main thread.
+    MainLast,
+    /// All threads in spawn order, so the main thread goes first.
+    SpawnOrder,
+    /// All threads in reverse spawn order, so the main thread goes last.
+    ReverseSpawn,
+}
+
+/// How far `EvalContextExt::run_all_tls_dtors` got.
+#[derive(Clone, Debug)]
+struct AllDtorsProgress {
+    /// The threads whose TLS is still to be torn down, in order.
+    queue: VecDeque<ThreadId>,
+    /// The thread that holds the TLS of the main thread while threads before it are torn down.
+    parked_main: Option<ThreadId>,
+    /// The teardown that is currently running on the main thread.
+    current: Option<TlsDtorsState>,
+}
+
+/// A TLS destructor that finished, recorded when `record_dtor_returns` is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DtorReturn {
+    /// The thread that ran the destructor.
+    pub thread: ThreadId,
+    /// The key the destructor belongs to. `None` for destructors that do not belong to a key,
+    /// like the macOS thread destructor.
+    pub key: Option<TlsKey>,
+    /// How the destructor finished.
+    pub status: DtorStatus,
+}
+
+/// What a `PendingDtor` belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PendingDtorKind {
+    /// The destructor of a pthread key.
+    Key(TlsKey),
+    /// The macOS thread destructor registered with `_tlv_atexit`.
+    MacosThreadDtor,
+}
+
+/// A TLS destructor that would run if its thread exited now, see `TlsData::all_pending_dtors`.
+#[derive(Clone, Copy, Debug)]
+pub struct PendingDtor<'tcx> {
+    /// The thread whose teardown would call the destructor.
+    pub thread: ThreadId,
+    /// What the destructor belongs to.
+    pub kind: PendingDtorKind,
+    /// The destructor itself.
+    pub dtor: TlsDtor<'tcx>,
+    /// The argument the destructor would be called with.
+    pub data: Scalar<Provenance>,
+}
+
+/// Something that happened to the TLS state, recorded when `record_events` is set. Unlike the
+/// trace output, this is meant for tools that analyze how a program uses TLS.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsEvent {
+    /// The key was created.
+    KeyCreated { key: TlsKey },
+    /// The key was deleted.
+    KeyDeleted { key: TlsKey },
+    /// A (non-NULL) value was stored for the key on the thread.
+    ValueStored { key: TlsKey, thread: ThreadId },
+    /// The value of the key on the thread was loaded.
+    ValueLoaded { key: TlsKey, thread: ThreadId },
+    /// NULL was stored for the key on the thread.
+    ValueRemoved { key: TlsKey, thread: ThreadId },
+    /// A destructor is about to be called on the thread. `key` is `None` for destructors that do
+    /// not belong to a key, like the macOS thread destructor.
+    DtorRun { key: Option<TlsKey>, thread: ThreadId },
+}
+
+/// An operation that changed the TLS state, recorded when `record_ops` is set, see
+/// `TlsData::replay`.
+#[derive(Clone, Copy, Debug)]
+pub enum TlsOp<'tcx> {
+    /// `key` was created, see `TlsData::create_module_tls_key`.
+    CreateKey { key: TlsKey, dtor: Option<TlsDtor<'tcx>>, module: Option<ModuleId>, max_size: Size },
+    /// The key was marked as non-destructible, see `TlsData::create_non_destructible_tls_key`.
+    MarkNonDestructible { key: TlsKey },
+    /// The destructor of the key was set, see `TlsData::set_tls_key_dtor`.
+    SetKeyDtor { key: TlsKey, dtor: Option<TlsDtor<'tcx>> },
+    /// The key was deleted, see `TlsData::delete_tls_key`.
+    DeleteKey { key: TlsKey },
+    /// The keys of the module were deleted, see `TlsData::delete_keys_for_module`.
+    DeleteModuleKeys { module: ModuleId },
+    /// A value (possibly NULL) was stored, see `TlsData::store_tls`.
+    Store { key: TlsKey, thread: ThreadId, value: Scalar<Provenance> },
+    /// The macOS thread destructor was set, see `TlsData::set_macos_thread_dtor`.
+    SetMacosThreadDtor { thread: ThreadId, dtor: ty::Instance<'tcx>, data: Scalar<Provenance> },
+}
+
+/// A summary of the TLS destructors that ran during one teardown of a thread.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TeardownMetrics {
+    /// The number of destructor calls, counting pthread destructors and the macOS thread
+    /// destructor. On Windows, destructors are run by std and are not counted.
+    pub dtor_calls: u64,
+    /// The number of passes over the pthread keys that ran at least one destructor.
+    pub passes: u64,
+    /// Whether the teardown stopped calling destructors because the iteration cap was hit, even
+    /// though there were still values left, see `TlsData::set_dtor_iteration_cap`.
+    pub iteration_cap_hit: bool,
+}
+
+/// A copy of the keys, values and destructors of a `TlsData`, see `TlsData::snapshot`.
+#[derive(Clone, Debug)]
+pub struct TlsSnapshot<'tcx> {
+    keys: BTreeMap<TlsKey, TlsEntry<'tcx>>,
+    macos_thread_dtors: BTreeSet<ThreadId>,
+}
+
+/// One difference between two `TlsSnapshot`s. This cannot be compared, since the values of
+/// `ChangedValue` have provenance.
+#[derive(Clone, Copy, Debug)]
+pub enum TlsChange {
+    /// The key was created.
+    CreatedKey(TlsKey),
+    /// The key was deleted.
+    DeletedKey(TlsKey),
+    /// The key existed before and after, and now has a destructor it did not have before.
+    AddedDtor(TlsKey),
+    /// The key existed before and after, and lost its destructor.
+    RemovedDtor(TlsKey),
+    /// The value of the key for the thread changed. `None` stands for NULL, which is also the
+    /// value of keys that do not exist.
+    ChangedValue {
+        key: TlsKey,
+        thread: ThreadId,
+        before: Option<Scalar<Provenance>>,
+        after: Option<Scalar<Provenance>>,
+    },
+    /// The thread registered a macOS thread destructor.
+    AddedMacosThreadDtor(ThreadId),
+    /// The macOS thread destructor of the thread was removed.
+    RemovedMacosThreadDtor(ThreadId),
+}
+
+impl fmt::Display for TlsChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Renders a value like `debug_dump` does.
+        struct Value(Option<Scalar<Provenance>>);
+        impl fmt::Display for Value {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.0 {
+                    Some(value) => write!(f, "{value:x}"),
+                    None => write!(f, "NULL"),
+                }
+            }
+        }
+
+        match *self {
+            TlsChange::CreatedKey(key) => write!(f, "created key {key}"),
+            TlsChange::DeletedKey(key) => write!(f, "deleted key {key}"),
+            TlsChange::AddedDtor(key) => write!(f, "added dtor to key {key}"),
+            TlsChange::RemovedDtor(key) => write!(f, "removed dtor from key {key}"),
+            TlsChange::ChangedValue { key, thread, before, after } =>
+                write!(
+                    f,
+                    "key {key}, thread {}: {} -> {}",
+                    thread.to_u32(),
+                    Value(before),
+                    Value(after)
+                ),
+            TlsChange::AddedMacosThreadDtor(thread) =>
+                write!(f, "added macos thread dtor on thread {}", thread.to_u32()),
+            TlsChange::RemovedMacosThreadDtor(thread) =>
+                write!(f, "removed macos thread dtor on thread {}", thread.to_u32()),
+        }
+    }
+}
+
+/// The differences between two `TlsSnapshot`s, see `TlsSnapshot::diff`.
+#[derive(Clone, Debug, Default)]
+pub struct TlsDiff {
+    changes: Vec<TlsChange>,
+}
+
+impl TlsDiff {
+    /// The changes, ordered by key and then by thread, followed by the changes to macOS thread
+    /// destructors ordered by thread.
+    pub fn changes(&self) -> &[TlsChange] {
+        &self.changes
+    }
+
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Renders one change per line, in the order of `changes`, so the output can be compared against
+/// in tests.
+impl fmt::Display for TlsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The TLS state of all threads. Cloning it copies all keys, values and settings, so that tools can
+/// explore e.g. one order of teardowns on a copy while the original stays as it is.
+#[derive(Clone, Debug)]
 pub struct TlsData<'tcx> {
     /// The Key to use for the next thread-local allocation.
     next_key: TlsKey,
@@ -38,9 +618,232 @@ pub struct TlsData<'tcx> {
     /// pthreads-style thread-local storage.
     keys: BTreeMap<TlsKey, TlsEntry<'tcx>>,
 
+    /// The maximum number of keys that may be live at the same time, on top of the limit imposed
+    /// by the key size. `None` means there is no such limit.
+    max_keys: Option<usize>,
+
+    /// The maximum number of (non-NULL) values a single thread may have stored at the same time,
+    /// across all keys. `None` means there is no such limit.
+    max_values_per_thread: Option<usize>,
+
+    /// Decides whether a key may be created, on top of the limits above. `None` means every key
+    /// may be.
+    key_creation_veto: Option<KeyCreationVeto<'tcx>>,
+
+    /// Decides which panic strategy applies to panics in the TLS destructors of a thread. `None`
+    /// means the strategy of the session does.
+    dtor_panic_hook: Option<DtorPanicHook<'tcx>>,
+
     /// A single per thread destructor of the thread local storage (that's how
     /// things work on macOS) with a data argument.
     macos_thread_dtors: BTreeMap<ThreadId, (ty::Instance<'tcx>, Scalar<Provenance>)>,
+
+    /// The threads that are currently running their TLS destructors.
+    dtors_running: BTreeSet<ThreadId>,
+
+    /// Whether a pthread destructor is skipped if it already ran with the same argument during the
+    /// current teardown of the thread.
+    dedup_dtors: bool,
+
+    /// Warn once more than this fraction of the key space has been used. `None` disables that.
+    key_space_warning: Option<f64>,
+
+    /// Whether the key space warning was already emitted.
+    key_space_warned: bool,
+
+    /// The libc whose key reuse is emulated. `None` means keys are never reused.
+    libc_flavor: Option<LibcFlavor>,
+
+    /// The most recently created key.
+    last_key: Option<TlsKey>,
+
+    /// Whether pthread destructors are stored as function pointers and only resolved when they
+    /// are about to run.
+    lazy_dtors: bool,
+
+    /// Whether to follow POSIX strictly. This overrides all options that deviate from POSIX for
+    /// convenience: destructor calls are not deduplicated (`dedup_dtors`), the first destructor
+    /// error stops the program (`aggregate_dtor_errors`), iteration caps below
+    /// `PTHREAD_DESTRUCTOR_ITERATIONS` are raised to it, and unwinding out of a destructor always
+    /// aborts, like it does when the C library calls the destructors.
+    posix_strict: bool,
+
+    /// The destructor calls that already happened during the current teardown of each thread,
+    /// used when `dedup_dtors` is set.
+    executed_dtors: BTreeMap<ThreadId, FxHashSet<(DtorId<'tcx>, DtorArg)>>,
+
+    /// Whether to warn when a pthread destructor is called with a pointer to freed memory.
+    validate_dtor_args: bool,
+
+    /// Whether to collect `TeardownMetrics`.
+    collect_teardown_metrics: bool,
+
+    /// The metrics of the teardowns that are currently running, if they are collected.
+    teardown_metrics: BTreeMap<ThreadId, TeardownMetrics>,
+
+    /// The metrics of the most recently finished teardown, if they were collected.
+    last_teardown_metrics: Option<TeardownMetrics>,
+
+    /// Whether Undefined Behavior of TLS destructors is collected in `dtor_errors`, so that the
+    /// program can go on as if the destructor had returned, see
+    /// `EvalContextExt::recover_tls_dtor_error`.
+    aggregate_dtor_errors: bool,
+
+    /// The destructor errors collected so far, in the order they happened.
+    dtor_errors: Vec<DtorError>,
+
+    /// The maximum number of passes over the pthread keys during a thread's teardown. `None`
+    /// means destructors are called until no values are left.
+    dtor_iteration_cap: Option<u64>,
+
+    /// Threads whose iteration cap differs from `dtor_iteration_cap`.
+    thread_dtor_iteration_caps: BTreeMap<ThreadId, u64>,
+
+    /// Whether to record how each TLS destructor finished in `dtor_returns`.
+    record_dtor_returns: bool,
+
+    /// The frames of the TLS destructors that are currently running on each thread, innermost
+    /// last. A destructor can run on top of another one, e.g. with `EvalContextExt::run_key_dtor`.
+    dtor_frames: BTreeMap<ThreadId, Vec<DtorFrame>>,
+
+    /// The destructor returns recorded so far, in the order they happened.
+    dtor_returns: Vec<DtorReturn>,
+
+    /// Whether creating keys is an error, see `freeze`.
+    keys_frozen: bool,
+
+    /// Whether to warn when a value that still needs its destructor is overwritten.
+    warn_on_overwrite: bool,
+
+    /// Whether to remember where each key was created, for pointing at it in diagnostics.
+    record_key_origins: bool,
+
+    /// How far the virtual clock is advanced before each destructor call, see
+    /// `set_dtor_clock_advance`.
+    dtor_clock_advance: Duration,
+
+    /// The function that pthread and macOS TLS destructors are called through, see
+    /// `set_dtor_wrapper`.
+    dtor_wrapper: Option<ty::Instance<'tcx>>,
+
+    /// When the pthread destructors of each thread run, see `set_dtor_schedule`.
+    dtor_schedule: TlsDtorSchedule,
+
+    /// Threads whose schedule differs from `dtor_schedule`.
+    thread_dtor_schedules: BTreeMap<ThreadId, TlsDtorSchedule>,
+
+    /// Whether to record `TlsEvent`s in `events`.
+    record_events: bool,
+
+    /// The events recorded so far, in the order they happened. This is a `RefCell` since loads
+    /// are recorded as well.
+    events: RefCell<Vec<TlsEvent>>,
+
+    /// Whether to record `TlsOp`s in `ops`.
+    record_ops: bool,
+
+    /// The operations recorded so far, in the order they happened.
+    ops: Vec<TlsOp<'tcx>>,
+
+    /// The threads that reached the scheduling point of their destructors, and run them before
+    /// their next step.
+    triggered_dtors: BTreeSet<ThreadId>,
+
+    /// What happens when a destructor stores to a deleted key, see `set_deleted_key_stores`.
+    deleted_key_stores: DeletedKeyStores,
+
+    /// The keys that were deleted and not created again since.
+    deleted_keys: BTreeSet<TlsKey>,
+
+    /// What happens when a destructor loads the TLS of another thread that is tearing down, see
+    /// `set_dtor_isolation`.
+    dtor_isolation: DtorIsolation,
+
+    /// Whether the steps of destructors are left out of the basic block count, see
+    /// `set_exempt_dtor_steps`.
+    exempt_dtor_steps: bool,
+
+    /// The number of basic blocks each thread executed while running its destructors.
+    dtor_steps: BTreeMap<ThreadId, u64>,
+
+    /// The order in which the TLS of all threads is torn down when the main thread exits, see
+    /// `set_thread_order`. `None` means only the main thread's is.
+    thread_order: Option<ThreadOrder>,
+
+    /// How far tearing down the TLS of all threads got, see `EvalContextExt::run_all_tls_dtors`.
+    all_dtors: Option<AllDtorsProgress>,
+}
+
+/// Returns how many keys fit into an integer of the given size, or `None` if that is not a limit
+/// for the number of keys we can create.
+fn max_keys_for(max_size: Size) -> Option<u128> {
+    (max_size.bits() < 128).then(|| 1u128 << max_size.bits())
+}
+
+/// Packages `key` as an integer of the given size, for returning it to the program.
+fn key_to_scalar<'tcx>(key: TlsKey, size: Size) -> TlsResult<'tcx, Scalar<Provenance>> {
+    Scalar::try_from_uint(key, size).ok_or(TlsError::KeySpaceExhausted)
+}
+
+/// Identifies the argument of a destructor call, for deduplicating destructor calls.
+/// We cannot use `Scalar<Provenance>` directly since provenance cannot be compared.
+type DtorArg = (Option<AllocId>, u128);
+
+/// Identifies a destructor, for deduplicating destructor calls.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum DtorId<'tcx> {
+    Instance(ty::Instance<'tcx>),
+    Ptr(DtorArg),
+}
+
+fn dtor_arg(data: Scalar<Provenance>) -> DtorArg {
+    match data {
+        Scalar::Int(int) => (None, int.assert_bits(int.size())),
+        Scalar::Ptr(ptr, _) => {
+            let (prov, offset) = ptr.into_parts();
+            (prov.get_alloc_id(), offset.bytes().into())
+        }
+    }
+}
+
+/// Returns whether `err` is Undefined Behavior, including the kinds that the borrow trackers and the
+/// data race detector report.
+fn is_ub(err: &InterpErrorInfo<'_>) -> bool {
+    match err.kind() {
+        InterpError::UndefinedBehavior(_) => true,
+        InterpError::MachineStop(info) =>
+            matches!(
+                info.downcast_ref::<TerminationInfo>(),
+                Some(
+                    TerminationInfo::StackedBorrowsUb { .. }
+                        | TerminationInfo::TreeBorrowsUb { .. }
+                        | TerminationInfo::DataRace { .. }
+                )
+            ),
+        _ => false,
+    }
+}
+
+/// Decides whether a key with the given destructor and key size may be created, see
+/// `TlsData::set_key_creation_veto`.
+#[derive(Clone)]
+struct KeyCreationVeto<'tcx>(Rc<dyn Fn(Option<&TlsDtor<'tcx>>, Size) -> Result<(), String> + 'tcx>);
+
+impl fmt::Debug for KeyCreationVeto<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("KeyCreationVeto(..)")
+    }
+}
+
+/// Decides which panic strategy applies to panics in the TLS destructors of a thread, see
+/// `TlsData::set_dtor_panic_hook`.
+#[derive(Clone)]
+struct DtorPanicHook<'tcx>(Rc<dyn Fn(ThreadId) -> PanicStrategy + 'tcx>);
+
+impl fmt::Debug for DtorPanicHook<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DtorPanicHook(..)")
+    }
 }
 
 impl<'tcx> Default for TlsData<'tcx> {
@@ -48,39 +851,705 @@ fn default() -> Self {
         TlsData {
             next_key: 1, // start with 1 as we must not use 0 on Windows
             keys: Default::default(),
+            max_keys: None,
+            max_values_per_thread: None,
+            key_creation_veto: None,
+            dtor_panic_hook: None,
             macos_thread_dtors: Default::default(),
+            dtors_running: Default::default(),
+            dedup_dtors: false,
+            key_space_warning: None,
+            key_space_warned: false,
+            lazy_dtors: false,
+            libc_flavor: None,
+            last_key: None,
+            posix_strict: false,
+            executed_dtors: Default::default(),
+            validate_dtor_args: false,
+            collect_teardown_metrics: false,
+            teardown_metrics: Default::default(),
+            last_teardown_metrics: None,
+            aggregate_dtor_errors: false,
+            dtor_errors: Vec::new(),
+            dtor_iteration_cap: None,
+            thread_dtor_iteration_caps: Default::default(),
+            record_dtor_returns: false,
+            dtor_frames: Default::default(),
+            dtor_returns: Vec::new(),
+            keys_frozen: false,
+            warn_on_overwrite: false,
+            record_key_origins: false,
+            dtor_clock_advance: Duration::ZERO,
+            dtor_wrapper: None,
+            dtor_schedule: TlsDtorSchedule::OnExit,
+            thread_dtor_schedules: Default::default(),
+            record_events: false,
+            events: Default::default(),
+            record_ops: false,
+            ops: Vec::new(),
+            triggered_dtors: Default::default(),
+            deleted_key_stores: DeletedKeyStores::Strict,
+            deleted_keys: Default::default(),
+            dtor_isolation: DtorIsolation::Off,
+            exempt_dtor_steps: false,
+            dtor_steps: Default::default(),
+            thread_order: None,
+            all_dtors: None,
         }
     }
 }
 
 impl<'tcx> TlsData<'tcx> {
+    pub fn new(config: &MiriConfig) -> Self {
+        TlsData {
+            dedup_dtors: config.dedup_tls_dtors,
+            posix_strict: config.tls_posix_strict,
+            lazy_dtors: config.lazy_tls_dtors,
+            libc_flavor: config.tls_libc_flavor,
+            validate_dtor_args: config.validate_tls_dtor_args,
+            aggregate_dtor_errors: config.aggregate_tls_dtor_errors,
+            dtor_iteration_cap: config.tls_dtor_iteration_cap,
+            key_space_warning: config.tls_key_space_warning,
+            warn_on_overwrite: config.warn_tls_overwrite,
+            record_key_origins: config.tls_key_origins,
+            dtor_clock_advance: config.tls_dtor_clock_advance,
+            dtor_schedule: config.tls_dtor_schedule,
+            deleted_key_stores: config.tls_deleted_key_stores,
+            exempt_dtor_steps: config.tls_exempt_dtor_steps,
+            thread_order: config.tls_thread_order,
+            dtor_panic_hook: config
+                .tls_dtor_panic
+                .map(|strategy| DtorPanicHook(Rc::new(move |_: ThreadId| strategy))),
+            ..Default::default()
+        }
+    }
+
+    /// Enable or disable following POSIX strictly, see the `posix_strict` field.
+    pub fn set_posix_strict(&mut self, posix_strict: bool) {
+        self.posix_strict = posix_strict;
+    }
+
+    /// Enable or disable resolving pthread destructors only when they are about to run.
+    pub fn set_lazy_dtors(&mut self, lazy_dtors: bool) {
+        self.lazy_dtors = lazy_dtors;
+    }
+
+    /// Emulate the key reuse of the given libc, see `LibcFlavor`. `None` means keys are never
+    /// reused.
+    pub fn set_libc_flavor(&mut self, libc_flavor: Option<LibcFlavor>) {
+        self.libc_flavor = libc_flavor;
+    }
+
+    /// Whether pthread destructors are resolved only when they are about to run.
+    pub fn lazy_dtors(&self) -> bool {
+        self.lazy_dtors
+    }
+
+    /// Enable or disable warning when a pthread destructor is called with a pointer to freed
+    /// memory.
+    pub fn set_validate_dtor_args(&mut self, validate_dtor_args: bool) {
+        self.validate_dtor_args = validate_dtor_args;
+    }
+
+    /// Enable or disable warning when a store to a key with a destructor overwrites a different
+    /// value, whose destructor then never runs for it.
+    pub fn set_warn_on_overwrite(&mut self, warn_on_overwrite: bool) {
+        self.warn_on_overwrite = warn_on_overwrite;
+    }
+
+    /// Enable or disable remembering where each key was created. This is off by default since it
+    /// costs a span per key.
+    pub fn set_record_key_origins(&mut self, record_key_origins: bool) {
+        self.record_key_origins = record_key_origins;
+    }
+
+    /// Remember that `key` was created at `origin`, if key origins are recorded.
+    fn record_key_origin(&mut self, key: TlsKey, origin: SpanData) {
+        if self.record_key_origins
+            && let Some(entry) = self.keys.get_mut(&key)
+        {
+            entry.origin = Some(origin);
+        }
+    }
+
+    /// Returns where `key` was created, if key origins were recorded when it was.
+    pub fn key_origin(&self, key: TlsKey) -> Option<SpanData> {
+        self.keys.get(&key).and_then(|entry| entry.origin)
+    }
+
+    /// Returns whether storing `new_data` for `key` on `thread_id` should be warned about, see
+    /// `set_warn_on_overwrite`.
+    fn check_overwrite_warning(
+        &self,
+        key: TlsKey,
+        thread_id: ThreadId,
+        new_data: Scalar<Provenance>,
+    ) -> bool {
+        // Null values are never stored, so any stored value still needs its destructor.
+        self.warn_on_overwrite
+            && self.keys.get(&key).is_some_and(|entry| {
+                entry.dtor.is_some()
+                    && entry
+                        .data
+                        .get(&thread_id)
+                        .is_some_and(|&old_data| dtor_arg(old_data) != dtor_arg(new_data))
+            })
+    }
+
+    /// Call every pthread and macOS TLS destructor through `wrapper` instead of directly. The
+    /// wrapper gets a pointer to the destructor and the destructor's argument, and is expected
+    /// to call the destructor itself, so that it can log or intercept destructor calls. `None`
+    /// calls destructors directly again.
+    pub fn set_dtor_wrapper(&mut self, wrapper: Option<ty::Instance<'tcx>>) {
+        self.dtor_wrapper = wrapper;
+    }
+
+    /// Enable or disable collecting destructor errors instead of stopping the program, see
+    /// `take_dtor_errors`.
+    pub fn set_aggregate_dtor_errors(&mut self, aggregate_dtor_errors: bool) {
+        self.aggregate_dtor_errors = aggregate_dtor_errors;
+    }
+
+    /// Returns the destructor errors collected so far and clears them.
+    pub fn take_dtor_errors(&mut self) -> Vec<DtorError> {
+        std::mem::take(&mut self.dtor_errors)
+    }
+
+    /// Set the maximum number of passes over the pthread keys during a thread's teardown. Once
+    /// that many passes ran, the remaining values are dropped without calling their destructors,
+    /// which POSIX allows after `PTHREAD_DESTRUCTOR_ITERATIONS` passes. `None` means destructors
+    /// are called until no values are left.
+    pub fn set_dtor_iteration_cap(&mut self, cap: Option<u64>) {
+        self.dtor_iteration_cap = cap;
+    }
+
+    /// Override the iteration cap for the given thread. `None` removes the override, so the cap
+    /// set with `set_dtor_iteration_cap` applies again.
+    pub fn set_thread_dtor_iteration_cap(&mut self, thread: ThreadId, cap: Option<u64>) {
+        match cap {
+            Some(cap) => self.thread_dtor_iteration_caps.insert(thread, cap),
+            None => self.thread_dtor_iteration_caps.remove(&thread),
+        };
+    }
+
+    /// Advance the virtual clock by `advance` before each pthread or macOS destructor call, so
+    /// that tests of destructors that look at the time are deterministic. This does not model how
+    /// long destructors take, and it does nothing when the host clock is used.
+    pub fn set_dtor_clock_advance(&mut self, advance: Duration) {
+        self.dtor_clock_advance = advance;
+    }
+
+    /// Set when the pthread destructors of all threads run. With a schedule other than `OnExit`,
+    /// reaching the scheduling point runs one pass of the destructors of the thread, before it
+    /// executes its next step.
+    pub fn set_dtor_schedule(&mut self, schedule: TlsDtorSchedule) {
+        self.dtor_schedule = schedule;
+    }
+
+    /// Override the schedule for the given thread. `None` removes the override, so the schedule
+    /// set with `set_dtor_schedule` applies again.
+    pub fn set_thread_dtor_schedule(
+        &mut self,
+        thread: ThreadId,
+        schedule: Option<TlsDtorSchedule>,
+    ) {
+        match schedule {
+            Some(schedule) => self.thread_dtor_schedules.insert(thread, schedule),
+            None => self.thread_dtor_schedules.remove(&thread),
+        };
+    }
+
+    /// The schedule that applies to the given thread.
+    pub fn dtor_schedule(&self, thread: ThreadId) -> TlsDtorSchedule {
+        self.thread_dtor_schedules.get(&thread).copied().unwrap_or(self.dtor_schedule)
+    }
+
+    /// Record that the given thread reached the scheduling point `point`. Returns whether that
+    /// triggered its destructors, which is the case if `point` is the thread's schedule. Threads
+    /// that are running their destructors are never triggered.
+    fn trigger_dtors(&mut self, thread: ThreadId, point: TlsDtorSchedule) -> bool {
+        if point == TlsDtorSchedule::OnExit
+            || self.dtor_schedule(thread) != point
+            || self.dtors_running(thread)
+        {
+            return false;
+        }
+        self.triggered_dtors.insert(thread);
+        true
+    }
+
+    /// Returns whether the destructors of any thread were triggered and have not run yet. This is
+    /// checked before every step, so it is kept cheap.
+    pub fn any_dtors_triggered(&self) -> bool {
+        !self.triggered_dtors.is_empty()
+    }
+
+    /// Enable or disable recording how each TLS destructor finished, see `take_dtor_returns`.
+    pub fn set_record_dtor_returns(&mut self, record_dtor_returns: bool) {
+        self.record_dtor_returns = record_dtor_returns;
+    }
+
+    /// Returns the destructor returns recorded so far and clears them.
+    pub fn take_dtor_returns(&mut self) -> Vec<DtorReturn> {
+        std::mem::take(&mut self.dtor_returns)
+    }
+
+    /// Enable or disable recording what happens to the TLS state, see `take_events`.
+    pub fn set_record_events(&mut self, record_events: bool) {
+        self.record_events = record_events;
+    }
+
+    /// Returns the events recorded so far and clears them.
+    pub fn take_events(&mut self) -> Vec<TlsEvent> {
+        self.events.take()
+    }
+
+    /// Record `event` if events are recorded.
+    fn record_event(&self, event: TlsEvent) {
+        if self.record_events {
+            self.events.borrow_mut().push(event);
+        }
+    }
+
+    /// Set what happens when a thread that is running its destructors stores to a key that was
+    /// deleted and not created again since, e.g. because one destructor deleted a key another
+    /// destructor still uses. With `DeletedKeyStores::Resurrect`, the key gets no destructor, so
+    /// storing to it does not cause more destructor passes. Keys that are created again, like
+    /// recycled keys with a `LibcFlavor`, are not deleted anymore.
+    pub fn set_deleted_key_stores(&mut self, deleted_key_stores: DeletedKeyStores) {
+        self.deleted_key_stores = deleted_key_stores;
+    }
+
+    /// Set what happens when a thread that is running its destructors loads the TLS of another
+    /// thread that is running its destructors too, with `load_tls_from`. The other thread's values
+    /// may be half torn down at that point, so such a load is a race between the two teardowns.
+    /// `DtorIsolation::Error` reports it, and `DtorIsolation::Null` hides the values instead.
+    pub fn set_dtor_isolation(&mut self, dtor_isolation: DtorIsolation) {
+        self.dtor_isolation = dtor_isolation;
+    }
+
+    /// Returns how a store of `thread` to `key` is handled if it is a store to a deleted key by a
+    /// thread that is running its destructors, see `set_deleted_key_stores`.
+    fn deleted_key_store(&self, key: TlsKey, thread: ThreadId) -> Option<DeletedKeyStores> {
+        (self.dtors_running.contains(&thread)
+            && self.deleted_keys.contains(&key)
+            && !self.keys.contains_key(&key))
+        .then_some(self.deleted_key_stores)
+    }
+
+    /// Enable or disable leaving the basic blocks that a thread executes while running its
+    /// destructors out of the machine's basic block count, which progress reports are based on.
+    /// They are still counted per thread, see `dtor_steps`.
+    pub fn set_exempt_dtor_steps(&mut self, exempt_dtor_steps: bool) {
+        self.exempt_dtor_steps = exempt_dtor_steps;
+    }
+
+    /// Returns how many basic blocks `thread` executed while running its destructors.
+    pub fn dtor_steps(&self, thread: ThreadId) -> u64 {
+        self.dtor_steps.get(&thread).copied().unwrap_or(0)
+    }
+
+    /// Returns whether any thread is running its destructors. This is checked before every
+    /// terminator, so it is kept cheap.
+    pub fn any_dtors_running(&self) -> bool {
+        !self.dtors_running.is_empty()
+    }
+
+    /// Account for a basic block that `thread` is about to execute. Returns whether the block
+    /// counts toward the machine's basic block count.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub(crate) fn count_step(&mut self, thread: ThreadId) -> bool {
+        if !self.dtors_running.contains(&thread) {
+            return true;
+        }
+        *self.dtor_steps.entry(thread).or_default() += 1;
+        !self.exempt_dtor_steps
+    }
+
+    /// Set the order in which the TLS of all threads is torn down when the main thread exits, see
+    /// `EvalContextExt::run_all_tls_dtors`. With `None`, only the TLS of the main thread is, and
+    /// the destructors of the threads that are still running then never run, like on a real
+    /// system.
+    pub fn set_thread_order(&mut self, thread_order: Option<ThreadOrder>) {
+        self.thread_order = thread_order;
+    }
+
+    /// Returns the order in which the TLS of all threads is torn down when the main thread exits.
+    pub fn thread_order(&self) -> Option<ThreadOrder> {
+        self.thread_order
+    }
+
+    /// Returns the threads whose TLS is torn down at the end of the program, in the given order:
+    /// `main`, and all other threads that still have TLS values or a macOS thread destructor.
+    pub fn threads_to_finalize(&self, main: ThreadId, order: ThreadOrder) -> Vec<ThreadId> {
+        let mut threads: BTreeSet<ThreadId> = self
+            .keys
+            .values()
+            .flat_map(|TlsEntry { data, .. }| data.keys().copied())
+            .chain(self.macos_thread_dtors.keys().copied())
+            .collect();
+        threads.insert(main);
+        let mut threads: Vec<ThreadId> = threads.into_iter().collect();
+        match order {
+            ThreadOrder::MainLast => threads.sort_by_key(|&thread| thread == main),
+            ThreadOrder::SpawnOrder => {}
+            ThreadOrder::ReverseSpawn => threads.reverse(),
+        }
+        threads
+    }
+
+    /// Swap the TLS values and macOS thread destructors of threads `a` and `b`.
+    fn swap_thread_tls(&mut self, a: ThreadId, b: ThreadId) {
+        fn swap<V>(map: &mut BTreeMap<ThreadId, V>, a: ThreadId, b: ThreadId) {
+            let value_a = map.remove(&a);
+            if let Some(value_b) = map.remove(&b) {
+                map.insert(a, value_b);
+            }
+            if let Some(value_a) = value_a {
+                map.insert(b, value_a);
+            }
+        }
+        for TlsEntry { data, .. } in self.keys.values_mut() {
+            swap(data, a, b);
+        }
+        swap(&mut self.macos_thread_dtors, a, b);
+    }
+
+    /// Enable or disable recording the operations that change the TLS state, see `take_ops`.
+    pub fn set_record_ops(&mut self, record_ops: bool) {
+        self.record_ops = record_ops;
+    }
+
+    /// Returns the operations recorded so far and clears them.
+    pub fn take_ops(&mut self) -> Vec<TlsOp<'tcx>> {
+        std::mem::take(&mut self.ops)
+    }
+
+    /// Record `op` if operations are recorded.
+    fn record_op(&mut self, op: TlsOp<'tcx>) {
+        if self.record_ops {
+            self.ops.push(op);
+        }
+    }
+
+    /// Apply the recorded operations `ops` again, in order. Replaying all operations recorded
+    /// since a `TlsData` was created into a fresh one with the same settings reproduces its keys,
+    /// values and macOS thread destructors, as long as no thread ran its destructors: those
+    /// changes are not recorded.
+    ///
+    /// This fails if one of the operations fails, or creates a different key than it did when it
+    /// was recorded.
+    pub fn replay(&mut self, ops: &[TlsOp<'tcx>], cx: &impl HasDataLayout) -> TlsResult<'tcx> {
+        for &op in ops {
+            match op {
+                TlsOp::CreateKey { key, dtor, module, max_size } => {
+                    let actual = self.create_module_tls_key(dtor, module, max_size)?;
+                    if actual != key {
+                        return Err(TlsError::ReplayKeyMismatch { expected: key, actual });
+                    }
+                }
+                TlsOp::MarkNonDestructible { key } => {
+                    let entry =
+                        self.keys.get_mut(&key).ok_or(TlsError::SetDtorOfNonexistentKey { key })?;
+                    entry.non_destructible = true;
+                }
+                TlsOp::SetKeyDtor { key, dtor } => self.set_tls_key_dtor(key, dtor)?,
+                TlsOp::DeleteKey { key } => self.delete_tls_key(key)?,
+                TlsOp::DeleteModuleKeys { module } => {
+                    self.delete_keys_for_module(module);
+                }
+                TlsOp::Store { key, thread, value } => self.store_tls(key, thread, value, cx)?,
+                TlsOp::SetMacosThreadDtor { thread, dtor, data } =>
+                    self.set_macos_thread_dtor(thread, dtor, data)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Record that the destructor of `key` was pushed as frame `frame` onto the stack of `thread`.
+    fn push_dtor_frame(&mut self, thread: ThreadId, frame: usize, key: Option<TlsKey>) {
+        self.dtor_frames.entry(thread).or_default().push(DtorFrame { frame, key });
+    }
+
+    /// Returns whether the frame of a TLS destructor is on the stack of any thread. This is
+    /// checked before every terminator, so it is kept cheap.
+    pub fn any_dtor_frames(&self) -> bool {
+        !self.dtor_frames.is_empty()
+    }
+
+    /// Returns the frame of the innermost TLS destructor that is running on `thread`, if any.
+    fn innermost_dtor_frame(&self, thread: ThreadId) -> Option<DtorFrame> {
+        self.dtor_frames.get(&thread).and_then(|frames| frames.last()).copied()
+    }
+
+    /// Returns whether frame `frame` on the stack of `thread` is the frame of the innermost TLS
+    /// destructor that is running on it.
+    fn is_dtor_frame(&self, thread: ThreadId, frame: usize) -> bool {
+        self.innermost_dtor_frame(thread).is_some_and(|dtor| dtor.frame == frame)
+    }
+
+    /// Forget the frame of the innermost TLS destructor that is running on `thread`.
+    fn pop_dtor_frame(&mut self, thread: ThreadId) -> Option<DtorFrame> {
+        let frames = self.dtor_frames.get_mut(&thread)?;
+        let dtor = frames.pop();
+        if frames.is_empty() {
+            self.dtor_frames.remove(&thread);
+        }
+        dtor
+    }
+
+    /// Record that the destructor whose frame is frame `frame` on the stack of `thread` finished
+    /// with the given status. Does nothing if that is not the frame of a destructor.
+    fn record_dtor_return(&mut self, thread: ThreadId, frame: usize, status: DtorStatus) {
+        if !self.is_dtor_frame(thread, frame) {
+            return;
+        }
+        let DtorFrame { key, .. } = self.pop_dtor_frame(thread).unwrap();
+        if self.record_dtor_returns {
+            self.dtor_returns.push(DtorReturn { thread, key, status });
+        }
+    }
+
+    /// The iteration cap that applies to the given thread. In strict POSIX mode, it is never below
+    /// `PTHREAD_DESTRUCTOR_ITERATIONS`.
+    fn dtor_iteration_cap(&self, thread: ThreadId) -> Option<u64> {
+        let cap = self.thread_dtor_iteration_caps.get(&thread).copied().or(self.dtor_iteration_cap);
+        if self.posix_strict { cap.map(|cap| cap.max(PTHREAD_DESTRUCTOR_ITERATIONS)) } else { cap }
+    }
+
+    /// Whether destructor calls are deduplicated, which is never the case in strict POSIX mode.
+    fn dedup_dtors(&self) -> bool {
+        self.dedup_dtors && !self.posix_strict
+    }
+
+    /// Whether destructor errors are collected, which is never the case in strict POSIX mode.
+    fn aggregate_dtor_errors(&self) -> bool {
+        self.aggregate_dtor_errors && !self.posix_strict
+    }
+
+    /// What happens when a TLS destructor unwinds out of its frame on the target operating system
+    /// `os`. In strict POSIX mode, this always aborts.
+    pub fn dtor_unwind_action(&self, os: &str) -> DtorUnwindAction {
+        if self.posix_strict { DtorUnwindAction::Abort } else { DtorUnwindAction::for_os(os) }
+    }
+
+    /// Enable or disable collecting `TeardownMetrics`. This only affects teardowns that start
+    /// afterwards.
+    pub fn set_collect_teardown_metrics(&mut self, collect: bool) {
+        self.collect_teardown_metrics = collect;
+    }
+
+    /// Returns the metrics of the most recently finished thread teardown. `None` if no teardown
+    /// finished while metrics were collected.
+    pub fn last_teardown_metrics(&self) -> Option<TeardownMetrics> {
+        self.last_teardown_metrics
+    }
+
+    /// Limit the number of keys that may be live at the same time. `None` removes the limit.
+    pub fn set_max_keys(&mut self, max_keys: Option<usize>) {
+        self.max_keys = max_keys;
+    }
+
+    /// Limit the number of (non-NULL) values a single thread may have stored at the same time,
+    /// across all keys, so that one thread cannot use up the storage. Storing to a key that the
+    /// thread already has a value for, or storing NULL, never hits the limit. `None` removes the
+    /// limit. Values that are already stored are not affected by changing the limit.
+    pub fn set_max_values_per_thread(&mut self, max_values: Option<usize>) {
+        self.max_values_per_thread = max_values;
+    }
+
+    /// Consult `veto` with the destructor and the size of every key before it is created. If it
+    /// returns an error, the key is not created and creating it fails with
+    /// `TlsError::KeyCreationVetoed`, carrying the message. The other limits are checked first, so
+    /// `veto` only sees keys that could be created otherwise.
+    pub fn set_key_creation_veto(
+        &mut self,
+        veto: impl Fn(Option<&TlsDtor<'tcx>>, Size) -> Result<(), String> + 'tcx,
+    ) {
+        self.key_creation_veto = Some(KeyCreationVeto(Rc::new(veto)));
+    }
+
+    /// Remove the predicate set by `set_key_creation_veto`.
+    pub fn clear_key_creation_veto(&mut self) {
+        self.key_creation_veto = None;
+    }
+
+    /// Consult `hook` with the thread whenever one of its TLS destructors panics, to decide
+    /// whether the panic aborts the process or unwinds, like it would with `-C panic=abort` or
+    /// `-C panic=unwind`. This can only make panics abort: with `-C panic=abort`, the panic runtime
+    /// aborts before Miri sees the panic.
+    pub fn set_dtor_panic_hook(&mut self, hook: impl Fn(ThreadId) -> PanicStrategy + 'tcx) {
+        self.dtor_panic_hook = Some(DtorPanicHook(Rc::new(hook)));
+    }
+
+    /// Remove the hook set by `set_dtor_panic_hook`.
+    pub fn clear_dtor_panic_hook(&mut self) {
+        self.dtor_panic_hook = None;
+    }
+
+    /// The panic strategy that applies to panics in the TLS destructors of `thread`, which is
+    /// `session` unless a hook was set with `set_dtor_panic_hook`.
+    pub fn dtor_panic_strategy(&self, thread: ThreadId, session: PanicStrategy) -> PanicStrategy {
+        self.dtor_panic_hook.as_ref().map_or(session, |hook| (hook.0)(thread))
+    }
+
+    /// Warn once more than the given fraction of the key space has been used. `None` disables
+    /// the warning.
+    pub fn set_key_space_warning(&mut self, fraction: Option<f64>) {
+        self.key_space_warning = fraction;
+    }
+
+    /// Make creating keys fail with `TlsError::KeysFrozen` until `unfreeze` is called. This
+    /// helps catching keys that are created after the program is done initializing. Existing keys
+    /// can still be used and deleted, and their destructors still run.
+    pub fn freeze(&mut self) {
+        self.keys_frozen = true;
+    }
+
+    /// Allow creating keys again after `freeze`.
+    pub fn unfreeze(&mut self) {
+        self.keys_frozen = false;
+    }
+
     /// Generate a new TLS key with the given destructor.
     /// `max_size` determines the integer size the key has to fit in.
-    #[allow(clippy::arithmetic_side_effects)]
+    ///
+    /// This may also be called by a TLS destructor while its thread is tearing down. Unless a
+    /// `LibcFlavor` is emulated, keys are handed out in increasing order and `fetch_tls_dtor`
+    /// visits the keys in order, so the new key is always after the one whose destructor is
+    /// currently running. Values stored for it are thus picked up later in the same pass, and the
+    /// teardown state is not affected. A reused key may come before the running destructor, in
+    /// which case its values are picked up in the next pass.
     pub fn create_tls_key(
         &mut self,
-        dtor: Option<ty::Instance<'tcx>>,
+        dtor: Option<TlsDtor<'tcx>>,
         max_size: Size,
-    ) -> InterpResult<'tcx, TlsKey> {
-        let new_key = self.next_key;
-        self.next_key += 1;
-        self.keys.try_insert(new_key, TlsEntry { data: Default::default(), dtor }).unwrap();
-        trace!("New TLS key allocated: {} with dtor {:?}", new_key, dtor);
+    ) -> TlsResult<'tcx, TlsKey> {
+        self.create_module_tls_key(dtor, None, max_size)
+    }
+
+    /// Like `create_tls_key`, but the key has no destructor and `set_tls_key_dtor` refuses to give
+    /// it one. This is for keys whose values must never be destroyed, like borrowed or static data.
+    pub fn create_non_destructible_tls_key(&mut self, max_size: Size) -> TlsResult<'tcx, TlsKey> {
+        let key = self.create_tls_key(None, max_size)?;
+        self.keys.get_mut(&key).unwrap().non_destructible = true;
+        self.record_op(TlsOp::MarkNonDestructible { key });
+        Ok(key)
+    }
+
+    /// Like `create_tls_key`, but the key belongs to `module`, so that it is deleted by
+    /// `delete_keys_for_module`.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub fn create_module_tls_key(
+        &mut self,
+        dtor: Option<TlsDtor<'tcx>>,
+        module: Option<ModuleId>,
+        max_size: Size,
+    ) -> TlsResult<'tcx, TlsKey> {
+        if self.keys_frozen {
+            return Err(TlsError::KeysFrozen);
+        }
+        if let Some(max_keys) = self.max_keys
+            && self.keys.len() >= max_keys
+        {
+            return Err(TlsError::KeyLimitReached { max_keys });
+        }
+        if let Some(veto) = &self.key_creation_veto {
+            (veto.0)(dtor.as_ref(), max_size)
+                .map_err(|message| TlsError::KeyCreationVetoed { message })?;
+        }
+        let new_key = match self.libc_flavor {
+            None => self.next_key,
+            Some(flavor @ LibcFlavor::Glibc) =>
+                (1..flavor.max_keys())
+                    .find(|key| !self.keys.contains_key(key))
+                    .ok_or(TlsError::KeySpaceExhausted)?,
+            Some(flavor @ LibcFlavor::Musl) => {
+                let start = self.last_key.unwrap_or(1);
+                (start..flavor.max_keys())
+                    .chain(1..start)
+                    .find(|key| !self.keys.contains_key(key))
+                    .ok_or(TlsError::KeySpaceExhausted)?
+            }
+        };
+        self.next_key = self.next_key.max(new_key + 1);
+        self.last_key = Some(new_key);
+        self.deleted_keys.remove(&new_key);
+        self.keys
+            .try_insert(
+                new_key,
+                TlsEntry {
+                    data: Default::default(),
+                    dtor,
+                    module,
+                    origin: None,
+                    non_destructible: false,
+                },
+            )
+            .unwrap();
+        trace!("New TLS key allocated: {} with dtor {:?} in module {:?}", new_key, dtor, module);
 
-        if max_size.bits() < 128 && new_key >= (1u128 << max_size.bits()) {
-            throw_unsup_format!("we ran out of TLS key space");
+        if let Some(max) = max_keys_for(max_size)
+            && new_key >= max
+        {
+            return Err(TlsError::KeySpaceExhausted);
         }
+        self.record_event(TlsEvent::KeyCreated { key: new_key });
+        self.record_op(TlsOp::CreateKey { key: new_key, dtor, module, max_size });
         Ok(new_key)
     }
 
-    pub fn delete_tls_key(&mut self, key: TlsKey) -> InterpResult<'tcx> {
+    /// Returns the number of keys that were created and the size of the key space if `key` is
+    /// the first key that exceeds the key space warning threshold.
+    fn check_key_space_warning(&mut self, key: TlsKey, max_size: Size) -> Option<(u128, u128)> {
+        let fraction = self.key_space_warning?;
+        let max = max_keys_for(max_size)?;
+        if self.key_space_warned || (key as f64) < fraction * (max as f64) {
+            return None;
+        }
+        self.key_space_warned = true;
+        Some((key, max))
+    }
+
+    pub fn delete_tls_key(&mut self, key: TlsKey) -> TlsResult<'tcx> {
         match self.keys.remove(&key) {
             Some(_) => {
                 trace!("TLS key {} removed", key);
+                self.deleted_keys.insert(key);
+                self.record_event(TlsEvent::KeyDeleted { key });
+                self.record_op(TlsOp::DeleteKey { key });
                 Ok(())
             }
-            None => throw_ub_format!("removing a nonexistent TLS key: {}", key),
+            None => Err(TlsError::DeleteNonexistentKey { key }),
+        }
+    }
+
+    /// Delete all keys that belong to `module`, as when the module is unloaded. No destructors
+    /// are run; instead, the destructor calls that the values of the deleted keys still needed
+    /// are returned, in key and thread order, for callers that want to run them anyway.
+    pub fn delete_keys_for_module(
+        &mut self,
+        module: ModuleId,
+    ) -> Vec<(TlsDtor<'tcx>, Scalar<Provenance>, ThreadId)> {
+        let mut dtors = Vec::new();
+        let mut deleted = Vec::new();
+        self.keys.retain(|&key, TlsEntry { data, dtor, module: key_module, .. }| {
+            if *key_module != Some(module) {
+                return true;
+            }
+            if let Some(dtor) = dtor {
+                dtors.extend(data.iter().map(|(&thread, &value)| (*dtor, value, thread)));
+            }
+            trace!("TLS key {} of module {} removed", key, module);
+            deleted.push(key);
+            false
+        });
+        for key in deleted {
+            self.deleted_keys.insert(key);
+            self.record_event(TlsEvent::KeyDeleted { key });
         }
+        self.record_op(TlsOp::DeleteModuleKeys { module });
+        dtors
     }
 
     pub fn load_tls(
@@ -88,14 +1557,38 @@ pub fn load_tls(
         key: TlsKey,
         thread_id: ThreadId,
         cx: &impl HasDataLayout,
-    ) -> InterpResult<'tcx, Scalar<Provenance>> {
+    ) -> TlsResult<'tcx, Scalar<Provenance>> {
         match self.keys.get(&key) {
             Some(TlsEntry { data, .. }) => {
                 let value = data.get(&thread_id).copied();
                 trace!("TLS key {} for thread {:?} loaded: {:?}", key, thread_id, value);
+                self.record_event(TlsEvent::ValueLoaded { key, thread: thread_id });
                 Ok(value.unwrap_or_else(|| Scalar::null_ptr(cx)))
             }
-            None => throw_ub_format!("loading from a non-existing TLS key: {}", key),
+            None => Err(TlsError::LoadNonexistentKey { key, thread: thread_id }),
+        }
+    }
+
+    /// Like `load_tls`, but the value that `owner` has for `key` is loaded by `reader`, which is
+    /// subject to `set_dtor_isolation` if both threads are running their destructors.
+    pub fn load_tls_from(
+        &self,
+        key: TlsKey,
+        reader: ThreadId,
+        owner: ThreadId,
+        cx: &impl HasDataLayout,
+    ) -> TlsResult<'tcx, Scalar<Provenance>> {
+        let value = self.load_tls(key, owner, cx)?;
+        if reader == owner || !self.dtors_running(reader) || !self.dtors_running(owner) {
+            return Ok(value);
+        }
+        match self.dtor_isolation {
+            DtorIsolation::Off => Ok(value),
+            DtorIsolation::Error => Err(TlsError::CrossTeardownLoad { key, reader, owner }),
+            DtorIsolation::Null => {
+                trace!("TLS key {} of thread {:?} hidden from {:?}", key, owner, reader);
+                Ok(Scalar::null_ptr(cx))
+            }
         }
     }
 
@@ -105,20 +1598,74 @@ pub fn store_tls(
         thread_id: ThreadId,
         new_data: Scalar<Provenance>,
         cx: &impl HasDataLayout,
-    ) -> InterpResult<'tcx> {
+    ) -> TlsResult<'tcx> {
+        match self.deleted_key_store(key, thread_id) {
+            Some(DeletedKeyStores::Lenient) => {
+                trace!("TLS store to deleted key {} for thread {:?} ignored", key, thread_id);
+                return Ok(());
+            }
+            Some(DeletedKeyStores::Resurrect) => {
+                trace!("deleted TLS key {} created again for thread {:?}", key, thread_id);
+                self.deleted_keys.remove(&key);
+                let entry = TlsEntry {
+                    data: Default::default(),
+                    dtor: None,
+                    module: None,
+                    origin: None,
+                    non_destructible: false,
+                };
+                self.keys.try_insert(key, entry).unwrap();
+                self.record_event(TlsEvent::KeyCreated { key });
+            }
+            Some(DeletedKeyStores::Strict) | None => {}
+        }
+        let reached_value_limit = self
+            .max_values_per_thread
+            .filter(|&max_values| self.thread_value_count(thread_id) >= max_values);
         match self.keys.get_mut(&key) {
             Some(TlsEntry { data, .. }) => {
-                if new_data.to_target_usize(cx)? != 0 {
+                let event = if new_data.to_target_usize(cx)? != 0 {
+                    if let Some(max_values) = reached_value_limit
+                        && !data.contains_key(&thread_id)
+                    {
+                        return Err(TlsError::ThreadValueLimitReached {
+                            thread: thread_id,
+                            max_values,
+                        });
+                    }
                     trace!("TLS key {} for thread {:?} stored: {:?}", key, thread_id, new_data);
                     data.insert(thread_id, new_data);
+                    TlsEvent::ValueStored { key, thread: thread_id }
                 } else {
                     trace!("TLS key {} for thread {:?} removed", key, thread_id);
                     data.remove(&thread_id);
-                }
+                    TlsEvent::ValueRemoved { key, thread: thread_id }
+                };
+                self.record_event(event);
+                self.record_op(TlsOp::Store { key, thread: thread_id, value: new_data });
                 Ok(())
             }
-            None => throw_ub_format!("storing to a non-existing TLS key: {}", key),
+            None => Err(TlsError::StoreNonexistentKey { key, thread: thread_id }),
+        }
+    }
+
+    /// Store the given values for `thread`, as if `store_tls` was called for each of them. The
+    /// whole batch is rejected, without storing anything, if any of the keys does not exist. If the
+    /// batch hits the value limit of `set_max_values_per_thread`, the values before the one that
+    /// hit it stay stored.
+    pub fn register_thread_dtors(
+        &mut self,
+        thread: ThreadId,
+        dtors: Vec<(TlsKey, Scalar<Provenance>)>,
+        cx: &impl HasDataLayout,
+    ) -> TlsResult<'tcx> {
+        if let Some(&(key, _)) = dtors.iter().find(|(key, _)| !self.keys.contains_key(key)) {
+            return Err(TlsError::StoreNonexistentKey { key, thread });
+        }
+        for (key, value) in dtors {
+            self.store_tls(key, thread, value, cx)?;
         }
+        Ok(())
     }
 
     /// Set the thread wide destructor of the thread local storage for the given
@@ -135,15 +1682,133 @@ pub fn set_macos_thread_dtor(
         thread: ThreadId,
         dtor: ty::Instance<'tcx>,
         data: Scalar<Provenance>,
-    ) -> InterpResult<'tcx> {
+    ) -> TlsResult<'tcx> {
         if self.macos_thread_dtors.insert(thread, (dtor, data)).is_some() {
-            throw_unsup_format!(
-                "setting more than one thread local storage destructor for the same thread is not supported"
-            );
+            return Err(TlsError::MultipleMacosThreadDtors { thread });
+        }
+        self.record_op(TlsOp::SetMacosThreadDtor { thread, dtor, data });
+        Ok(())
+    }
+
+    /// Move all TLS state of thread `from` to thread `to`: the values stored for every key and the
+    /// macOS thread destructor. Afterwards, `from` has no TLS values anymore.
+    ///
+    /// This fails if `from` is running its destructors, or if `to` already has any TLS state.
+    pub fn migrate_thread(&mut self, from: ThreadId, to: ThreadId) -> TlsResult<'tcx> {
+        if self.dtors_running.contains(&from) {
+            return Err(TlsError::MigrateWhileDestructing { from });
+        }
+        if self.macos_thread_dtors.contains_key(&to)
+            || self.keys.values().any(|TlsEntry { data, .. }| data.contains_key(&to))
+        {
+            return Err(TlsError::MigrateToOccupiedThread { from, to });
+        }
+        for TlsEntry { data, .. } in self.keys.values_mut() {
+            if let Some(value) = data.remove(&from) {
+                data.insert(to, value);
+            }
+        }
+        if let Some(dtor) = self.macos_thread_dtors.remove(&from) {
+            self.macos_thread_dtors.insert(to, dtor);
         }
+        trace!("TLS of {:?} migrated to {:?}", from, to);
         Ok(())
     }
 
+    /// Replace the destructor of `key`. Values that are already stored will also be destroyed with
+    /// the new destructor. Fails if `key` was created with `create_non_destructible_tls_key` and
+    /// `dtor` is not `None`.
+    pub fn set_tls_key_dtor(
+        &mut self,
+        key: TlsKey,
+        dtor: Option<TlsDtor<'tcx>>,
+    ) -> TlsResult<'tcx> {
+        let Some(entry) = self.keys.get_mut(&key) else {
+            return Err(TlsError::SetDtorOfNonexistentKey { key });
+        };
+        if entry.non_destructible && dtor.is_some() {
+            return Err(TlsError::DtorOfNonDestructibleKey { key });
+        }
+        trace!("TLS key {} now has dtor {:?}", key, dtor);
+        entry.dtor = dtor;
+        self.record_op(TlsOp::SetKeyDtor { key, dtor });
+        Ok(())
+    }
+
+    /// Returns the destructor that will run for values of `key` when a thread exits, without
+    /// running it.
+    pub fn key_dtor(&self, key: TlsKey) -> TlsResult<'tcx, Option<TlsDtor<'tcx>>> {
+        match self.keys.get(&key) {
+            Some(TlsEntry { dtor, .. }) => Ok(*dtor),
+            None => Err(TlsError::DtorOfNonexistentKey { key }),
+        }
+    }
+
+    /// Returns the keys for which `thread` currently has a (non-NULL) value, together with that
+    /// value, in key order.
+    pub fn keys_for_thread(
+        &self,
+        thread: ThreadId,
+    ) -> impl Iterator<Item = (TlsKey, Scalar<Provenance>)> + '_ {
+        self.keys
+            .iter()
+            .filter_map(move |(&key, TlsEntry { data, .. })| Some((key, *data.get(&thread)?)))
+    }
+
+    /// Returns every destructor that would run if all threads exited now, without running or
+    /// removing anything. The destructors are ordered by thread, and within a thread in the order
+    /// of its teardown: the macOS thread destructor first, then the pthread destructors in key
+    /// order. Only the first pass over the keys is listed, as later passes depend on what the
+    /// destructors store.
+    pub fn all_pending_dtors(&self) -> Vec<PendingDtor<'tcx>> {
+        let threads: BTreeSet<ThreadId> = self
+            .keys
+            .values()
+            .filter(|TlsEntry { dtor, .. }| dtor.is_some())
+            .flat_map(|TlsEntry { data, .. }| data.keys().copied())
+            .chain(self.macos_thread_dtors.keys().copied())
+            .collect();
+        let mut pending = Vec::new();
+        for thread in threads {
+            if let Some(&(instance, data)) = self.macos_thread_dtors.get(&thread) {
+                pending.push(PendingDtor {
+                    thread,
+                    kind: PendingDtorKind::MacosThreadDtor,
+                    dtor: TlsDtor::Resolved(instance),
+                    data,
+                });
+            }
+            for (&key, TlsEntry { data, dtor, .. }) in &self.keys {
+                if let (Some(dtor), Some(&data)) = (dtor, data.get(&thread)) {
+                    pending.push(PendingDtor {
+                        thread,
+                        kind: PendingDtorKind::Key(key),
+                        dtor: *dtor,
+                        data,
+                    });
+                }
+            }
+        }
+        pending
+    }
+
+    /// Returns the number of (non-NULL) values stored across all keys and threads, e.g. for
+    /// noticing TLS growth in long-running programs. This takes time linear in the number of keys.
+    pub fn tls_value_count(&self) -> usize {
+        self.keys.values().map(|TlsEntry { data, .. }| data.len()).sum()
+    }
+
+    /// Returns the number of (non-NULL) values that `thread` has stored across all keys, which is
+    /// what `set_max_values_per_thread` limits. This takes time linear in the number of keys.
+    pub fn thread_value_count(&self, thread: ThreadId) -> usize {
+        self.keys.values().filter(|TlsEntry { data, .. }| data.contains_key(&thread)).count()
+    }
+
+    /// Returns whether the given thread is currently running its TLS destructors.
+    pub fn dtors_running(&self, thread: ThreadId) -> bool {
+        self.dtors_running.contains(&thread)
+    }
+
     /// Returns a dtor, its argument and its index, if one is supposed to run.
     /// `key` is the last dtors that was run; we return the *next* one after that.
     ///
@@ -163,13 +1828,18 @@ pub fn set_macos_thread_dtor(
     /// with associated destructors, implementations may stop calling destructors,
     /// or they may continue calling destructors until no non-NULL values with
     /// associated destructors exist, even though this might result in an infinite loop.
+    ///
+    /// If `dedup_dtors` is set (and `posix_strict` is not), a destructor that already ran with the
+    /// same argument during this teardown of the thread is skipped (but the value is still set to
+    /// NULL).
     fn fetch_tls_dtor(
         &mut self,
         key: Option<TlsKey>,
         thread_id: ThreadId,
-    ) -> Option<(ty::Instance<'tcx>, Scalar<Provenance>, TlsKey)> {
+    ) -> Option<(TlsDtor<'tcx>, Scalar<Provenance>, TlsKey)> {
         use std::ops::Bound::*;
 
+        let dedup_dtors = self.dedup_dtors();
         let thread_local = &mut self.keys;
         let start = match key {
             Some(key) => Excluded(key),
@@ -182,12 +1852,26 @@ fn fetch_tls_dtor(
         // start the next round.
         // TODO: In the future, we might consider randomizing destructor order, but we still have to
         // uphold this requirement.
-        for (&key, TlsEntry { data, dtor }) in thread_local.range_mut((start, Unbounded)) {
+        for (&key, TlsEntry { data, dtor, .. }) in thread_local.range_mut((start, Unbounded)) {
             match data.entry(thread_id) {
                 BTreeEntry::Occupied(entry) => {
                     if let Some(dtor) = dtor {
                         // Set TLS data to NULL, and call dtor with old value.
                         let data_scalar = entry.remove();
+                        if dedup_dtors
+                            && !self
+                                .executed_dtors
+                                .entry(thread_id)
+                                .or_default()
+                                .insert((dtor.id(), dtor_arg(data_scalar)))
+                        {
+                            trace!(
+                                "Skipping TLS dtor {:?} on {:?}: already ran",
+                                dtor,
+                                data_scalar
+                            );
+                            continue;
+                        }
                         let ret = Some((*dtor, data_scalar, key));
                         return ret;
                     }
@@ -198,6 +1882,116 @@ fn fetch_tls_dtor(
         None
     }
 
+    /// Clear the value of `key` for the given thread and return the key's destructor together with
+    /// the old value, so the destructor can be run once outside of the regular teardown. Returns
+    /// `None`, and leaves the value alone, if the key has no destructor, and also returns `None` if
+    /// there is no value.
+    ///
+    /// If the thread is running its destructors, this counts as one of its destructor calls: with
+    /// `dedup_dtors`, the destructor is skipped (but the value is still cleared) if it already ran
+    /// with the same argument during this teardown.
+    fn take_key_dtor(
+        &mut self,
+        key: TlsKey,
+        thread_id: ThreadId,
+    ) -> TlsResult<'tcx, Option<(TlsDtor<'tcx>, Scalar<Provenance>)>> {
+        let destructing = self.dtors_running(thread_id);
+        let dedup_dtors = self.dedup_dtors() && destructing;
+        let Some(TlsEntry { data, dtor, .. }) = self.keys.get_mut(&key) else {
+            return Err(TlsError::RunDtorOfNonexistentKey { key, thread: thread_id });
+        };
+        let Some(dtor) = *dtor else {
+            return Ok(None);
+        };
+        let Some(data_scalar) = data.remove(&thread_id) else {
+            return Ok(None);
+        };
+        if dedup_dtors
+            && !self
+                .executed_dtors
+                .entry(thread_id)
+                .or_default()
+                .insert((dtor.id(), dtor_arg(data_scalar)))
+        {
+            trace!("Skipping TLS dtor {:?} on {:?}: already ran", dtor, data_scalar);
+            return Ok(None);
+        }
+        if destructing {
+            self.count_dtor_call(thread_id, false);
+        }
+        self.record_event(TlsEvent::DtorRun { key: Some(key), thread: thread_id });
+        Ok(Some((dtor, data_scalar)))
+    }
+
+    /// Returns the next pthread destructor to run on the given thread, its argument and its key,
+    /// starting a new pass over the keys once the current one is done. Returns `None` instead of
+    /// starting a pass beyond the thread's iteration cap.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn next_pthread_dtor(
+        &mut self,
+        state: &mut RunningDtorState,
+        thread_id: ThreadId,
+    ) -> Option<(TlsDtor<'tcx>, Scalar<Provenance>, TlsKey)> {
+        // Fetch next dtor after `key`.
+        let (dtor, starts_pass) = match self.fetch_tls_dtor(state.last_key, thread_id) {
+            dtor @ Some(_) => (dtor, state.last_key.is_none()),
+            // We ran each dtor once, start over from the beginning.
+            None => (self.fetch_tls_dtor(None, thread_id), true),
+        };
+        let (dtor, ptr, key) = dtor?;
+        if starts_pass {
+            if let Some(cap) = self.dtor_iteration_cap(thread_id)
+                && state.passes >= cap
+            {
+                // The remaining values are deleted when the teardown finishes.
+                trace!("TLS dtor iteration cap of {} hit on {:?}", cap, thread_id);
+                if let Some(metrics) = self.teardown_metrics.get_mut(&thread_id) {
+                    metrics.iteration_cap_hit = true;
+                }
+                return None;
+            }
+            state.passes += 1;
+        }
+        state.last_key = Some(key);
+        self.count_dtor_call(thread_id, starts_pass);
+        self.record_event(TlsEvent::DtorRun { key: Some(key), thread: thread_id });
+        Some((dtor, ptr, key))
+    }
+
+    /// Record a destructor call in the metrics of the given thread's teardown, if they are
+    /// collected.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn count_dtor_call(&mut self, thread_id: ThreadId, starts_pass: bool) {
+        if let Some(metrics) = self.teardown_metrics.get_mut(&thread_id) {
+            metrics.dtor_calls += 1;
+            if starts_pass {
+                metrics.passes += 1;
+            }
+        }
+    }
+
+    /// Mark the given thread as running its TLS destructors.
+    fn start_teardown(&mut self, thread_id: ThreadId) {
+        self.dtors_running.insert(thread_id);
+        self.executed_dtors.remove(&thread_id);
+        if self.collect_teardown_metrics {
+            self.teardown_metrics.insert(thread_id, TeardownMetrics::default());
+        }
+    }
+
+    /// Mark the given thread as done with its TLS destructors, and delete its remaining TLS
+    /// entries.
+    fn finish_teardown(&mut self, thread_id: ThreadId) {
+        self.delete_all_thread_tls(thread_id);
+        self.dtors_running.remove(&thread_id);
+        self.dtor_frames.remove(&thread_id);
+        self.executed_dtors.remove(&thread_id);
+        self.triggered_dtors.remove(&thread_id);
+        if let Some(metrics) = self.teardown_metrics.remove(&thread_id) {
+            self.last_teardown_metrics = Some(metrics);
+        }
+    }
+
     /// Delete all TLS entries for the given thread. This function should be
     /// called after all TLS destructors have already finished.
     fn delete_all_thread_tls(&mut self, thread_id: ThreadId) {
@@ -205,29 +1999,499 @@ fn delete_all_thread_tls(&mut self, thread_id: ThreadId) {
             data.remove(&thread_id);
         }
     }
+
+    /// Render a human-readable snapshot of the entire TLS state: every key with its
+    /// destructor and per-thread values, the macOS thread destructors, and the set of
+    /// threads that are currently running their destructors.
+    ///
+    /// Everything is printed in key and thread order, so the output is deterministic and
+    /// can be compared against in tests. This neither mutates the state nor fails.
+    pub fn debug_dump(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "next key: {}", self.next_key).unwrap();
+        for (key, TlsEntry { data, dtor, non_destructible, .. }) in &self.keys {
+            match dtor {
+                Some(dtor) => writeln!(out, "key {key}: dtor {dtor}").unwrap(),
+                None if *non_destructible => writeln!(out, "key {key}: non-destructible").unwrap(),
+                None => writeln!(out, "key {key}: no dtor").unwrap(),
+            }
+            for (thread, value) in data {
+                writeln!(out, "    thread {}: {value:x}", thread.to_u32()).unwrap();
+            }
+        }
+        for (thread, (dtor, data)) in &self.macos_thread_dtors {
+            writeln!(out, "macos thread dtor on thread {}: {dtor} with {data:x}", thread.to_u32())
+                .unwrap();
+        }
+        let running: Vec<u32> = self.dtors_running.iter().map(|thread| thread.to_u32()).collect();
+        writeln!(out, "dtors running: {running:?}").unwrap();
+        out
+    }
+
+    /// Check the internal consistency of the TLS state, for catching bugs in this module early in
+    /// tests. This checks that `next_key` is larger than all existing keys, that no NULL value is
+    /// stored (those are removed instead), that non-destructible keys have no destructor, that no
+    /// existing key is marked as deleted, and that the per-thread teardown state only exists for
+    /// threads that are running their destructors. Returns a description of the first violation.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if let Some((&key, _)) = self.keys.last_key_value()
+            && key >= self.next_key
+        {
+            return Err(format!("key {key} exists, but the next key is {}", self.next_key));
+        }
+        if let Some(last_key) = self.last_key
+            && last_key >= self.next_key
+        {
+            return Err(format!(
+                "key {last_key} was created last, but the next key is {}",
+                self.next_key
+            ));
+        }
+        for (key, TlsEntry { data, dtor, non_destructible, .. }) in &self.keys {
+            let null = data.iter().find(|(_, value)| dtor_arg(**value) == (None, 0));
+            if let Some((thread, _)) = null {
+                return Err(format!("key {key} stores NULL for {thread:?}"));
+            }
+            if *non_destructible && dtor.is_some() {
+                return Err(format!("key {key} is non-destructible, but has a destructor"));
+            }
+            if self.deleted_keys.contains(key) {
+                return Err(format!("key {key} exists, but is marked as deleted"));
+            }
+        }
+        let teardown_threads = self.executed_dtors.keys().chain(self.teardown_metrics.keys());
+        for thread in teardown_threads {
+            if !self.dtors_running.contains(thread) {
+                return Err(format!(
+                    "{thread:?} has teardown state, but is not running its destructors"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Take a snapshot of all keys with their destructors and values, and of which threads have
+    /// a macOS thread destructor. Two snapshots can be compared with `TlsSnapshot::diff`.
+    pub fn snapshot(&self) -> TlsSnapshot<'tcx> {
+        TlsSnapshot {
+            keys: self.keys.clone(),
+            macos_thread_dtors: self.macos_thread_dtors.keys().copied().collect(),
+        }
+    }
+
+    /// Store the values `thread` had when `snapshot` was taken, and clear its values of keys that
+    /// did not exist back then. Keys and destructors themselves are not restored, and neither are
+    /// macOS thread destructors.
+    ///
+    /// This fails if the thread is running its destructors, or if one of the keys it had a value
+    /// for has been deleted since.
+    pub fn restore_thread(
+        &mut self,
+        snapshot: &TlsSnapshot<'tcx>,
+        thread: ThreadId,
+    ) -> TlsResult<'tcx> {
+        if self.dtors_running(thread) {
+            return Err(TlsError::RestoreWhileDestructing { thread });
+        }
+        if let Some((&key, _)) = snapshot.keys.iter().find(|(key, TlsEntry { data, .. })| {
+            data.contains_key(&thread) && !self.keys.contains_key(key)
+        }) {
+            return Err(TlsError::StoreNonexistentKey { key, thread });
+        }
+        for (key, TlsEntry { data, .. }) in &mut self.keys {
+            match snapshot.keys.get(key).and_then(|entry| entry.data.get(&thread)) {
+                Some(&value) => data.insert(thread, value),
+                None => data.remove(&thread),
+            };
+        }
+        trace!("TLS of {:?} restored", thread);
+        Ok(())
+    }
+
+    /// Run the pthread destructors of `thread` twice, starting from the same values both times,
+    /// to check that they can safely run again. `run_dtor` stands in for calling a destructor; it
+    /// gets the key, the destructor and its argument, and may store new values like a destructor
+    /// could. Returns what `run_dtor` returned during each of the two teardowns.
+    ///
+    /// This is only meant for testing destructors: a real thread never runs its teardown twice.
+    pub fn double_teardown<T>(
+        &mut self,
+        thread: ThreadId,
+        mut run_dtor: impl FnMut(&mut Self, TlsKey, TlsDtor<'tcx>, Scalar<Provenance>) -> T,
+    ) -> TlsResult<'tcx, [Vec<T>; 2]> {
+        if self.dtors_running(thread) {
+            return Err(TlsError::DoubleTeardown { thread });
+        }
+        let snapshot = self.snapshot();
+        let mut teardown = |tls: &mut Self| {
+            let mut state = RunningDtorState::default();
+            let mut results = Vec::new();
+            tls.start_teardown(thread);
+            while let Some((dtor, arg, key)) = tls.next_pthread_dtor(&mut state, thread) {
+                results.push(run_dtor(tls, key, dtor, arg));
+            }
+            tls.finish_teardown(thread);
+            results
+        };
+        let first = teardown(self);
+        self.restore_thread(&snapshot, thread)?;
+        let second = teardown(self);
+        Ok([first, second])
+    }
+}
+
+impl<'tcx> TlsSnapshot<'tcx> {
+    /// Compute what changed between this snapshot and the later snapshot `after`.
+    pub fn diff(&self, after: &TlsSnapshot<'tcx>) -> TlsDiff {
+        let mut changes = Vec::new();
+        let keys: BTreeSet<TlsKey> = self.keys.keys().chain(after.keys.keys()).copied().collect();
+        for key in keys {
+            let (before_entry, after_entry) = (self.keys.get(&key), after.keys.get(&key));
+            match (before_entry, after_entry) {
+                (None, Some(_)) => changes.push(TlsChange::CreatedKey(key)),
+                (Some(_), None) => changes.push(TlsChange::DeletedKey(key)),
+                (Some(before_entry), Some(after_entry)) => {
+                    let before_dtor = before_entry.dtor.map(|dtor| dtor.id());
+                    let after_dtor = after_entry.dtor.map(|dtor| dtor.id());
+                    if before_dtor != after_dtor {
+                        if before_dtor.is_some() {
+                            changes.push(TlsChange::RemovedDtor(key));
+                        }
+                        if after_dtor.is_some() {
+                            changes.push(TlsChange::AddedDtor(key));
+                        }
+                    }
+                }
+                (None, None) => unreachable!(),
+            }
+            let value = |entry: Option<&TlsEntry<'tcx>>, thread| {
+                entry.and_then(|entry| entry.data.get(&thread).copied())
+            };
+            let threads: BTreeSet<ThreadId> = before_entry
+                .into_iter()
+                .chain(after_entry)
+                .flat_map(|entry| entry.data.keys().copied())
+                .collect();
+            for thread in threads {
+                let (before, after) = (value(before_entry, thread), value(after_entry, thread));
+                // Provenance cannot be compared, so this compares the allocations and offsets.
+                if before.map(dtor_arg) != after.map(dtor_arg) {
+                    changes.push(TlsChange::ChangedValue { key, thread, before, after });
+                }
+            }
+        }
+        for &thread in self.macos_thread_dtors.union(&after.macos_thread_dtors) {
+            match (
+                self.macos_thread_dtors.contains(&thread),
+                after.macos_thread_dtors.contains(&thread),
+            ) {
+                (true, false) => changes.push(TlsChange::RemovedMacosThreadDtor(thread)),
+                (false, true) => changes.push(TlsChange::AddedMacosThreadDtor(thread)),
+                _ => {}
+            }
+        }
+        TlsDiff { changes }
+    }
+}
+
+/// Builds a `TlsData` that starts out with some keys and the given configuration.
+#[derive(Debug, Default)]
+pub struct TlsDataBuilder<'tcx> {
+    keys: Vec<(TlsKey, Option<TlsDtor<'tcx>>)>,
+    max_keys: Option<usize>,
+    max_values_per_thread: Option<usize>,
+    dedup_dtors: bool,
+    posix_strict: bool,
+    lazy_dtors: bool,
+    libc_flavor: Option<LibcFlavor>,
+    key_space_warning: Option<f64>,
+    validate_dtor_args: bool,
+    warn_on_overwrite: bool,
+    record_key_origins: bool,
+    collect_teardown_metrics: bool,
+    aggregate_dtor_errors: bool,
+    dtor_iteration_cap: Option<u64>,
+    record_dtor_returns: bool,
+    record_events: bool,
+    record_ops: bool,
+    deleted_key_stores: DeletedKeyStores,
+    dtor_isolation: DtorIsolation,
+    exempt_dtor_steps: bool,
+    thread_order: Option<ThreadOrder>,
+}
+
+impl<'tcx> TlsDataBuilder<'tcx> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a key with the given destructor. Keys created later on are numbered after the
+    /// largest declared key.
+    pub fn key(mut self, key: TlsKey, dtor: Option<TlsDtor<'tcx>>) -> Self {
+        self.keys.push((key, dtor));
+        self
+    }
+
+    /// See `TlsData::set_max_keys`.
+    pub fn max_keys(mut self, max_keys: Option<usize>) -> Self {
+        self.max_keys = max_keys;
+        self
+    }
+
+    /// See `TlsData::set_max_values_per_thread`.
+    pub fn max_values_per_thread(mut self, max_values: Option<usize>) -> Self {
+        self.max_values_per_thread = max_values;
+        self
+    }
+
+    /// See the `dedup_dtors` field of `TlsData`.
+    pub fn dedup_dtors(mut self, dedup_dtors: bool) -> Self {
+        self.dedup_dtors = dedup_dtors;
+        self
+    }
+
+    /// See `TlsData::set_posix_strict`.
+    pub fn posix_strict(mut self, posix_strict: bool) -> Self {
+        self.posix_strict = posix_strict;
+        self
+    }
+
+    /// See `TlsData::set_lazy_dtors`.
+    pub fn lazy_dtors(mut self, lazy_dtors: bool) -> Self {
+        self.lazy_dtors = lazy_dtors;
+        self
+    }
+
+    /// See `TlsData::set_libc_flavor`.
+    pub fn libc_flavor(mut self, libc_flavor: Option<LibcFlavor>) -> Self {
+        self.libc_flavor = libc_flavor;
+        self
+    }
+
+    /// See `TlsData::set_key_space_warning`.
+    pub fn key_space_warning(mut self, fraction: Option<f64>) -> Self {
+        self.key_space_warning = fraction;
+        self
+    }
+
+    /// See `TlsData::set_validate_dtor_args`.
+    pub fn validate_dtor_args(mut self, validate_dtor_args: bool) -> Self {
+        self.validate_dtor_args = validate_dtor_args;
+        self
+    }
+
+    /// See `TlsData::set_warn_on_overwrite`.
+    pub fn warn_on_overwrite(mut self, warn_on_overwrite: bool) -> Self {
+        self.warn_on_overwrite = warn_on_overwrite;
+        self
+    }
+
+    /// See `TlsData::set_record_key_origins`.
+    pub fn record_key_origins(mut self, record_key_origins: bool) -> Self {
+        self.record_key_origins = record_key_origins;
+        self
+    }
+
+    /// See `TlsData::set_collect_teardown_metrics`.
+    pub fn collect_teardown_metrics(mut self, collect: bool) -> Self {
+        self.collect_teardown_metrics = collect;
+        self
+    }
+
+    /// See `TlsData::set_aggregate_dtor_errors`.
+    pub fn aggregate_dtor_errors(mut self, aggregate_dtor_errors: bool) -> Self {
+        self.aggregate_dtor_errors = aggregate_dtor_errors;
+        self
+    }
+
+    /// See `TlsData::set_dtor_iteration_cap`.
+    pub fn dtor_iteration_cap(mut self, cap: Option<u64>) -> Self {
+        self.dtor_iteration_cap = cap;
+        self
+    }
+
+    /// See `TlsData::set_record_dtor_returns`.
+    pub fn record_dtor_returns(mut self, record_dtor_returns: bool) -> Self {
+        self.record_dtor_returns = record_dtor_returns;
+        self
+    }
+
+    /// See `TlsData::set_record_events`.
+    pub fn record_events(mut self, record_events: bool) -> Self {
+        self.record_events = record_events;
+        self
+    }
+
+    /// See `TlsData::set_record_ops`.
+    pub fn record_ops(mut self, record_ops: bool) -> Self {
+        self.record_ops = record_ops;
+        self
+    }
+
+    /// See `TlsData::set_deleted_key_stores`.
+    pub fn deleted_key_stores(mut self, deleted_key_stores: DeletedKeyStores) -> Self {
+        self.deleted_key_stores = deleted_key_stores;
+        self
+    }
+
+    /// See `TlsData::set_dtor_isolation`.
+    pub fn dtor_isolation(mut self, dtor_isolation: DtorIsolation) -> Self {
+        self.dtor_isolation = dtor_isolation;
+        self
+    }
+
+    /// See `TlsData::set_exempt_dtor_steps`.
+    pub fn exempt_dtor_steps(mut self, exempt_dtor_steps: bool) -> Self {
+        self.exempt_dtor_steps = exempt_dtor_steps;
+        self
+    }
+
+    /// See `TlsData::set_thread_order`.
+    pub fn thread_order(mut self, thread_order: Option<ThreadOrder>) -> Self {
+        self.thread_order = thread_order;
+        self
+    }
+
+    /// Build the `TlsData`. Fails if a key was declared more than once.
+    pub fn build(self) -> TlsResult<'tcx, TlsData<'tcx>> {
+        let mut tls = TlsData {
+            max_keys: self.max_keys,
+            max_values_per_thread: self.max_values_per_thread,
+            dedup_dtors: self.dedup_dtors,
+            posix_strict: self.posix_strict,
+            lazy_dtors: self.lazy_dtors,
+            libc_flavor: self.libc_flavor,
+            key_space_warning: self.key_space_warning,
+            validate_dtor_args: self.validate_dtor_args,
+            warn_on_overwrite: self.warn_on_overwrite,
+            record_key_origins: self.record_key_origins,
+            collect_teardown_metrics: self.collect_teardown_metrics,
+            aggregate_dtor_errors: self.aggregate_dtor_errors,
+            dtor_iteration_cap: self.dtor_iteration_cap,
+            record_dtor_returns: self.record_dtor_returns,
+            record_events: self.record_events,
+            record_ops: self.record_ops,
+            deleted_key_stores: self.deleted_key_stores,
+            dtor_isolation: self.dtor_isolation,
+            exempt_dtor_steps: self.exempt_dtor_steps,
+            thread_order: self.thread_order,
+            ..Default::default()
+        };
+        for (key, dtor) in self.keys {
+            if tls
+                .keys
+                .insert(
+                    key,
+                    TlsEntry {
+                        data: Default::default(),
+                        dtor,
+                        module: None,
+                        origin: None,
+                        non_destructible: false,
+                    },
+                )
+                .is_some()
+            {
+                return Err(TlsError::DuplicateKey { key });
+            }
+            tls.next_key = tls.next_key.max(key.saturating_add(1));
+        }
+        Ok(tls)
+    }
 }
 
 impl VisitTags for TlsData<'_> {
     fn visit_tags(&self, visit: &mut dyn FnMut(BorTag)) {
-        let TlsData { keys, macos_thread_dtors, next_key: _ } = self;
+        let TlsData {
+            keys,
+            macos_thread_dtors,
+            next_key: _,
+            max_keys: _,
+            max_values_per_thread: _,
+            key_creation_veto: _,
+            dtor_panic_hook: _,
+            dtors_running: _,
+            dedup_dtors: _,
+            posix_strict: _,
+            key_space_warning: _,
+            key_space_warned: _,
+            lazy_dtors: _,
+            libc_flavor: _,
+            last_key: _,
+            executed_dtors: _,
+            validate_dtor_args: _,
+            collect_teardown_metrics: _,
+            teardown_metrics: _,
+            last_teardown_metrics: _,
+            aggregate_dtor_errors: _,
+            dtor_errors: _,
+            dtor_iteration_cap: _,
+            thread_dtor_iteration_caps: _,
+            record_dtor_returns: _,
+            dtor_frames: _,
+            dtor_returns: _,
+            keys_frozen: _,
+            warn_on_overwrite: _,
+            record_key_origins: _,
+            dtor_clock_advance: _,
+            dtor_wrapper: _,
+            dtor_schedule: _,
+            thread_dtor_schedules: _,
+            record_events: _,
+            events: _,
+            record_ops: _,
+            ops,
+            triggered_dtors: _,
+            deleted_key_stores: _,
+            deleted_keys: _,
+            dtor_isolation: _,
+            exempt_dtor_steps: _,
+            dtor_steps: _,
+            thread_order: _,
+            all_dtors: _,
+        } = self;
 
-        for scalar in keys.values().flat_map(|v| v.data.values()) {
-            scalar.visit_tags(visit);
+        for entry in keys.values() {
+            for scalar in entry.data.values() {
+                scalar.visit_tags(visit);
+            }
+            if let Some(TlsDtor::Lazy(ptr)) = entry.dtor {
+                ptr.visit_tags(visit);
+            }
         }
         for (_, scalar) in macos_thread_dtors.values() {
             scalar.visit_tags(visit);
         }
+        for op in ops {
+            match op {
+                TlsOp::CreateKey { dtor: Some(TlsDtor::Lazy(ptr)), .. } => ptr.visit_tags(visit),
+                TlsOp::SetKeyDtor { dtor: Some(TlsDtor::Lazy(ptr)), .. } => ptr.visit_tags(visit),
+                TlsOp::Store { value: scalar, .. }
+                | TlsOp::SetMacosThreadDtor { data: scalar, .. } => scalar.visit_tags(visit),
+                TlsOp::CreateKey { .. }
+                | TlsOp::MarkNonDestructible { .. }
+                | TlsOp::SetKeyDtor { .. }
+                | TlsOp::DeleteKey { .. }
+                | TlsOp::DeleteModuleKeys { .. } => {}
+            }
+        }
     }
 }
 
-#[derive(Debug, Default)]
+/// The teardown of one thread's TLS. It is driven by the thread's `on_stack_empty` callback, so
+/// the destructors run on the exiting thread itself, after its entry point returned and before it
+/// terminates. Joining a thread only waits for that; it does not run any destructors.
+#[derive(Clone, Debug, Default)]
 pub struct TlsDtorsState(TlsDtorsStatePriv);
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 enum TlsDtorsStatePriv {
     #[default]
     Init,
-    PthreadDtors(RunningDtorState),
+    /// About to start the first of the given stages.
+    Stages(&'static [TeardownStage]),
+    /// Running the pthread dtors, followed by the given stages.
+    PthreadDtors(RunningDtorState, &'static [TeardownStage]),
     Done,
 }
 
@@ -239,45 +2503,342 @@ pub fn on_stack_empty<'tcx>(
         use TlsDtorsStatePriv::*;
         match &mut self.0 {
             Init => {
-                match this.tcx.sess.target.os.as_ref() {
-                    "linux" | "freebsd" | "android" => {
-                        // Run the pthread dtors.
-                        self.0 = PthreadDtors(Default::default());
-                    }
-                    "macos" => {
-                        // The macOS thread wide destructor runs "before any TLS slots get
-                        // freed", so do that first.
+                let Some(active_thread) = this.machine.threads.find_active_thread_id() else {
+                    // We cannot tell whose destructors to run, so report that and skip the
+                    // teardown instead of panicking later.
+                    let thread = this.get_active_thread();
+                    this.emit_diagnostic(NonHaltingDiagnostic::TlsTeardownSkipped { thread });
+                    return Ok(Poll::Ready(()));
+                };
+                this.machine.tls.start_teardown(active_thread);
+                self.0 = Stages(TeardownStage::for_os(&this.tcx.sess.target.os));
+            }
+            Stages(&mut stages) =>
+                match stages.split_first() {
+                    None => self.0 = Done,
+                    Some((TeardownStage::MacosThreadDtor, rest)) => {
                         this.schedule_macos_tls_dtor()?;
-                        // When the stack is empty again, go on with the pthread dtors.
-                        self.0 = PthreadDtors(Default::default());
+                        // When the stack is empty again, go on with the next stage.
+                        self.0 = Stages(rest);
+                    }
+                    Some((TeardownStage::PthreadDtors, rest)) => {
+                        self.0 = PthreadDtors(Default::default(), rest);
                     }
-                    "windows" => {
+                    Some((TeardownStage::WindowsCallback, rest)) => {
                         // Run the special magic hook.
                         this.schedule_windows_tls_dtors()?;
-                        // And move to the final state.
-                        self.0 = Done;
+                        self.0 = Stages(rest);
                     }
-                    _ => {
-                        // No TLS dtor support.
-                        // FIXME: should we do something on wasi?
-                        self.0 = Done;
-                    }
-                }
-            }
-            PthreadDtors(state) => {
+                },
+            PthreadDtors(state, rest) => {
                 match this.schedule_next_pthread_tls_dtor(state)? {
                     Poll::Pending => {} // just keep going
-                    Poll::Ready(()) => self.0 = Done,
+                    Poll::Ready(()) => self.0 = Stages(*rest),
                 }
             }
             Done => {
-                this.machine.tls.delete_all_thread_tls(this.get_active_thread());
+                let active_thread = this.get_active_thread();
+                this.machine.tls.finish_teardown(active_thread);
+                return Ok(Poll::Ready(()));
+            }
+        }
+
+        Ok(Poll::Pending)
+    }
+
+    /// Returns the number of passes over the pthread keys that this teardown started so far, or
+    /// `None` if it is not running the pthread destructors. This is the counter that
+    /// `TlsData::set_dtor_iteration_cap` limits.
+    #[cfg(any(test, feature = "tls-debug"))]
+    pub fn dtor_passes(&self) -> Option<u64> {
+        match &self.0 {
+            TlsDtorsStatePriv::PthreadDtors(state, _) => Some(state.passes),
+            _ => None,
+        }
+    }
+
+    /// Overwrite the number of passes over the pthread keys that this teardown started so far,
+    /// e.g. to put it right below the iteration cap. The pass that is currently running is not
+    /// affected, the next one is counted from `passes`. Returns `false` and does nothing if the
+    /// teardown is not running the pthread destructors.
+    #[cfg(any(test, feature = "tls-debug"))]
+    pub fn set_dtor_passes(&mut self, passes: u64) -> bool {
+        match &mut self.0 {
+            TlsDtorsStatePriv::PthreadDtors(state, _) => {
+                state.passes = passes;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> EvalContextExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
+pub trait EvalContextExt<'mir, 'tcx: 'mir>: crate::MiriInterpCxExt<'mir, 'tcx> {
+    /// Generate a new TLS key with the given destructor, see `TlsData::create_tls_key`. This also
+    /// emits a warning once the key space is nearly exhausted, and records where the key was
+    /// created if key origins are recorded.
+    fn create_tls_key(
+        &mut self,
+        dtor: Option<TlsDtor<'tcx>>,
+        max_size: Size,
+    ) -> InterpResult<'tcx, TlsKey> {
+        let this = self.eval_context_mut();
+        let key = this.machine.tls.create_tls_key(dtor, max_size)?;
+        if this.machine.tls.record_key_origins {
+            let origin = this.machine.current_span().data();
+            this.machine.tls.record_key_origin(key, origin);
+        }
+        if let Some((used, max)) = this.machine.tls.check_key_space_warning(key, max_size) {
+            this.emit_diagnostic(NonHaltingDiagnostic::TlsKeySpaceNearlyExhausted { used, max });
+        }
+        Ok(key)
+    }
+
+    /// Generate a new TLS key like `create_tls_key`, and return it as an integer of size `size`,
+    /// ready to be written to the program's key variable.
+    fn create_tls_key_scalar(
+        &mut self,
+        dtor: Option<TlsDtor<'tcx>>,
+        size: Size,
+    ) -> InterpResult<'tcx, Scalar<Provenance>> {
+        let this = self.eval_context_mut();
+        let key = this.create_tls_key(dtor, size)?;
+        Ok(key_to_scalar(key, size)?)
+    }
+
+    /// Store `new_data` for `key` on `thread`, see `TlsData::store_tls`. This also emits a warning
+    /// if that overwrites a value whose destructor then never runs for it.
+    fn store_tls(
+        &mut self,
+        key: TlsKey,
+        thread: ThreadId,
+        new_data: Scalar<Provenance>,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        if this.machine.tls.check_overwrite_warning(key, thread, new_data) {
+            let origin = this.machine.tls.key_origin(key);
+            this.emit_diagnostic(NonHaltingDiagnostic::TlsValueOverwritten { key, thread, origin });
+        }
+        if this.machine.tls.deleted_key_store(key, thread) == Some(DeletedKeyStores::Lenient) {
+            this.emit_diagnostic(NonHaltingDiagnostic::TlsStoreToDeletedKeyIgnored { key, thread });
+        }
+        Ok(this.machine.tls.store_tls(key, thread, new_data, &*this.tcx)?)
+    }
+
+    /// Run the destructor of `key` once for the value that `thread` has stored for it, outside of
+    /// the regular teardown, see `TlsData::take_key_dtor`. The destructor is pushed as a new stack
+    /// frame on the active thread, which continues where it was once the destructor returns, see
+    /// `tls_dtor_stack_pop`. This is refused if the active thread is in the middle of a basic
+    /// block, e.g. in a shim. Returns whether a destructor was scheduled.
+    fn run_key_dtor(&mut self, key: TlsKey, thread: ThreadId) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_mut();
+        if this.tls_dtor_stack_pop().is_none() {
+            throw_unsup_format!(
+                "running a TLS destructor in the middle of a basic block is not supported"
+            );
+        }
+        let Some((dtor, ptr)) = this.machine.tls.take_key_dtor(key, thread)? else {
+            return Ok(false);
+        };
+        trace!("Running TLS dtor {} of key {} on {:?} for {:?}", dtor, key, ptr, thread);
+        this.call_pthread_tls_dtor(dtor, ptr, key)?;
+        Ok(true)
+    }
+
+    /// Tear down the TLS of all threads at the end of the program, in the given order. This has
+    /// to be called on the main thread whenever its stack is empty, until it returns `Ready`.
+    ///
+    /// The destructors of each thread run on the main thread, going through the stages of the
+    /// target like a regular thread exit. While they run, the TLS values of that thread are moved
+    /// to the main thread, so `pthread_getspecific` in the destructors sees them. The other threads
+    /// are not stopped, so this is only meant for when the program is about to exit anyway. On
+    /// Windows, std keeps the destructors of a thread in its thread-local statics, which cannot be
+    /// moved, so only the TLS of the main thread is torn down.
+    fn run_all_tls_dtors(&mut self, order: ThreadOrder) -> InterpResult<'tcx, Poll<()>> {
+        let this = self.eval_context_mut();
+        let main = this.get_active_thread();
+        let mut progress = match this.machine.tls.all_dtors.take() {
+            Some(progress) => progress,
+            None => {
+                let queue = if this.tcx.sess.target.os == "windows" {
+                    vec![main]
+                } else {
+                    this.machine.tls.threads_to_finalize(main, order)
+                };
+                AllDtorsProgress { queue: queue.into(), parked_main: None, current: None }
+            }
+        };
+
+        if let Some(state) = &mut progress.current {
+            if state.on_stack_empty(this)?.is_ready() {
+                progress.current = None;
+            }
+        } else {
+            let Some(thread) = progress.queue.pop_front() else {
                 return Ok(Poll::Ready(()));
+            };
+            if thread == main {
+                if let Some(parked) = progress.parked_main.take() {
+                    this.machine.tls.swap_thread_tls(parked, main);
+                }
+            } else {
+                // The TLS of the main thread is empty once it was torn down. Before that, it is
+                // parked on the thread whose TLS takes its place.
+                this.machine.tls.swap_thread_tls(thread, main);
+                if progress.parked_main.is_none() && progress.queue.contains(&main) {
+                    progress.parked_main = Some(thread);
+                }
             }
+            trace!("Tearing down the TLS of {:?} on {:?}", thread, main);
+            progress.current = Some(TlsDtorsState::default());
         }
-
+        this.machine.tls.all_dtors = Some(progress);
         Ok(Poll::Pending)
     }
+
+    /// Set when the pthread destructors of `thread` run, see `TlsData::set_dtor_schedule`.
+    fn schedule_tls_dtors(&mut self, thread: ThreadId, when: TlsDtorSchedule) {
+        let this = self.eval_context_mut();
+        this.machine.tls.set_thread_dtor_schedule(thread, Some(when));
+    }
+
+    /// Called when the active thread reaches the scheduling point `point`. If that is when its
+    /// destructors run, they are run before the thread executes its next step; see
+    /// `run_triggered_tls_dtors`.
+    fn trigger_tls_dtors(&mut self, point: TlsDtorSchedule) {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        if this.machine.tls.trigger_dtors(thread, point) {
+            trace!("TLS dtors of {:?} triggered by {:?}", thread, point);
+        }
+    }
+
+    /// Called before each step of the active thread. If its destructors were triggered, this runs
+    /// one pass of them: the values of all keys with a destructor are cleared, and the destructors
+    /// are pushed as new stack frames on top of what the thread was doing, so they run in key
+    /// order before the thread goes on. The thread can only be continued at the start of a basic
+    /// block, see `tls_dtor_stack_pop`, so this waits until it gets there. Scheduling points are
+    /// shim calls, after which the thread is at the start of the block the call returns to.
+    fn run_triggered_tls_dtors(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        if !this.machine.tls.triggered_dtors.contains(&thread)
+            || this.tls_dtor_stack_pop().is_none()
+        {
+            return Ok(());
+        }
+        this.machine.tls.triggered_dtors.remove(&thread);
+        let keys: Vec<TlsKey> =
+            this.machine.tls.keys_for_thread(thread).map(|(key, _)| key).collect();
+        // The frame pushed last runs first.
+        for key in keys.into_iter().rev() {
+            this.run_key_dtor(key, thread)?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the active thread is currently running its TLS destructors. Shims that
+    /// behave differently during thread teardown should use this to check for that.
+    fn is_active_thread_destructing(&self) -> bool {
+        let this = self.eval_context_ref();
+        this.machine.tls.dtors_running(this.get_active_thread())
+    }
+
+    /// Called after a stack frame was popped normally. If that was the frame of a TLS destructor,
+    /// this records that the destructor returned.
+    fn after_tls_dtor_frame_pop(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        // The popped frame was the one right above the remaining ones.
+        let frame = this.active_thread_stack().len();
+        this.machine.tls.record_dtor_return(thread, frame, DtorStatus::Returned);
+        Ok(())
+    }
+
+    /// Called before each terminator. If a TLS destructor is about to unwind out of its frame,
+    /// this reports that and handles it the way the target does, see `DtorUnwindAction`. This
+    /// has to happen before the frame is popped, since unwinding out of a destructor's frame is
+    /// rejected right away.
+    fn before_tls_dtor_terminator(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        let frame_idx = this.frame_idx();
+        let frame = this.frame();
+        if !this.machine.tls.is_dtor_frame(thread, frame_idx)
+            || !matches!(
+                frame.current_loc(),
+                Either::Left(loc)
+                    if matches!(
+                        frame.body.basic_blocks[loc.block].terminator().kind,
+                        mir::TerminatorKind::UnwindResume
+                    )
+            )
+        {
+            return Ok(());
+        }
+        this.machine.tls.record_dtor_return(thread, frame_idx, DtorStatus::Unwound);
+        let action = this.machine.tls.dtor_unwind_action(&this.tcx.sess.target.os);
+        this.emit_diagnostic(NonHaltingDiagnostic::TlsDtorUnwinding { thread, action });
+        match action {
+            DtorUnwindAction::Abort =>
+                throw_machine_stop!(TerminationInfo::Abort(
+                    "unwinding out of a TLS destructor".to_owned()
+                )),
+            // The engine reports unwinding out of the frame when it is popped: destructors either
+            // are the bottom frame or return to a frame that does not allow unwinding.
+            DtorUnwindAction::Continue => Ok(()),
+        }
+    }
+
+    /// Called when a step of the active thread failed with `err`. If that is Undefined Behavior in a
+    /// TLS destructor and destructor errors are aggregated, see `TlsData::take_dtor_errors`, this
+    /// records the error with the span where it happened, and pops the frames of the destructor
+    /// without running any more of its code. The thread then goes on as if the destructor had
+    /// returned, and this returns `Ok(true)`. Otherwise, this returns `err`.
+    fn recover_tls_dtor_error(&mut self, err: InterpErrorInfo<'tcx>) -> InterpResult<'tcx, bool> {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        let Some(DtorFrame { frame, key }) = this.machine.tls.innermost_dtor_frame(thread) else {
+            return Err(err);
+        };
+        if !this.machine.tls.aggregate_dtor_errors()
+            || !is_ub(&err)
+            || this.active_thread_stack().len() <= frame
+        {
+            return Err(err);
+        }
+        let span = Some(this.machine.current_span().data());
+        let origin = key.and_then(|key| this.machine.tls.key_origin(key));
+        let msg = this.format_error(err);
+        trace!("TLS dtor on {:?} failed: {}", thread, msg);
+        this.machine.tls.dtor_errors.push(DtorError { thread, key, span, origin, msg });
+        while this.active_thread_stack().len() > frame {
+            this.abandon_frame()?;
+        }
+        // The frame below, if any, is still at the start of the block the destructor returns to.
+        this.machine.tls.pop_dtor_frame(thread);
+        Ok(true)
+    }
+
+    /// Called when the active thread starts to unwind from a panic. If it is running its TLS
+    /// destructors and the panic strategy for them is `abort`, see `TlsData::set_dtor_panic_hook`,
+    /// this aborts the process instead, like the `panic_abort` runtime does. Otherwise the panic
+    /// unwinds, and `before_tls_dtor_terminator` handles it if it leaves the destructor.
+    fn before_tls_dtor_panic(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let thread = this.get_active_thread();
+        if !this.machine.tls.dtors_running(thread) {
+            return Ok(());
+        }
+        match this.machine.tls.dtor_panic_strategy(thread, this.tcx.sess.panic_strategy()) {
+            PanicStrategy::Abort =>
+                throw_machine_stop!(TerminationInfo::Abort(
+                    "panicking in a TLS destructor with panic=abort".to_owned()
+                )),
+            PanicStrategy::Unwind => Ok(()),
+        }
+    }
 }
 
 impl<'mir, 'tcx: 'mir> EvalContextPrivExt<'mir, 'tcx> for crate::MiriInterpCx<'mir, 'tcx> {}
@@ -315,6 +2876,8 @@ fn schedule_windows_tls_dtors(&mut self) -> InterpResult<'tcx> {
             None,
             StackPopCleanup::Root { cleanup: true },
         )?;
+        let (thread, frame) = (this.get_active_thread(), this.frame_idx());
+        this.machine.tls.push_dtor_frame(thread, frame, None);
         Ok(())
     }
 
@@ -325,14 +2888,9 @@ fn schedule_macos_tls_dtor(&mut self) -> InterpResult<'tcx> {
         let thread_id = this.get_active_thread();
         if let Some((instance, data)) = this.machine.tls.macos_thread_dtors.remove(&thread_id) {
             trace!("Running macos dtor {:?} on {:?} at {:?}", instance, data, thread_id);
-
-            this.call_function(
-                instance,
-                Abi::C { unwind: false },
-                &[data.into()],
-                None,
-                StackPopCleanup::Root { cleanup: true },
-            )?;
+            this.machine.tls.count_dtor_call(thread_id, false);
+            this.machine.tls.record_event(TlsEvent::DtorRun { key: None, thread: thread_id });
+            this.call_tls_dtor(instance, data, None)?;
         }
         Ok(())
     }
@@ -346,31 +2904,1355 @@ fn schedule_next_pthread_tls_dtor(
         let this = self.eval_context_mut();
         let active_thread = this.get_active_thread();
 
-        // Fetch next dtor after `key`.
-        let dtor = match this.machine.tls.fetch_tls_dtor(state.last_key, active_thread) {
-            dtor @ Some(_) => dtor,
-            // We ran each dtor once, start over from the beginning.
-            None => this.machine.tls.fetch_tls_dtor(None, active_thread),
+        if let Some((dtor, ptr, key)) = this.machine.tls.next_pthread_dtor(state, active_thread) {
+            trace!("Running TLS dtor {} on {:?} at {:?}", dtor, ptr, active_thread);
+            let instance = match this.resolve_tls_dtor(dtor) {
+                Ok(instance) => instance,
+                Err(err)
+                    if this.machine.tls.aggregate_dtor_errors()
+                        && matches!(err.kind(), InterpError::UndefinedBehavior(_)) =>
+                {
+                    // Remember the error and go on with the next destructor.
+                    let msg = this.format_error(err);
+                    this.machine.tls.dtor_errors.push(DtorError {
+                        thread: active_thread,
+                        key: Some(key),
+                        span: None,
+                        origin: this.machine.tls.key_origin(key),
+                        msg,
+                    });
+                    return Ok(Poll::Pending);
+                }
+                Err(err) => return Err(err),
+            };
+            this.call_pthread_tls_dtor(TlsDtor::Resolved(instance), ptr, key)?;
+            return Ok(Poll::Pending);
+        }
+
+        Ok(Poll::Ready(()))
+    }
+
+    /// Pop the topmost frame of the active thread without returning from it or unwinding it: its
+    /// protectors end and its locals are deallocated, but none of its code runs anymore.
+    fn abandon_frame(&mut self) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        if this.machine.borrow_tracker.is_some() {
+            this.on_stack_pop(this.frame())?;
+        }
+        let frame = this.active_thread_stack_mut().pop().unwrap();
+        for local in &frame.locals {
+            if let Some(Either::Left((ptr, _))) = local.as_mplace_or_imm() {
+                this.deallocate_ptr(ptr, None, MemoryKind::Stack)?;
+            }
+        }
+        this.active_thread_mut().recompute_top_user_relevant_frame();
+        Ok(())
+    }
+
+    /// Returns the function a pthread destructor refers to, resolving it if that was deferred.
+    fn resolve_tls_dtor(&self, dtor: TlsDtor<'tcx>) -> InterpResult<'tcx, ty::Instance<'tcx>> {
+        let this = self.eval_context_ref();
+        match dtor {
+            TlsDtor::Resolved(instance) => Ok(instance),
+            TlsDtor::Lazy(dtor) => this.get_ptr_fn(dtor)?.as_instance(),
+        }
+    }
+
+    /// Push a stack frame that calls the pthread destructor `dtor` of `key` with the argument
+    /// `ptr`. With `validate_dtor_args`, this warns if `ptr` points to memory that has already
+    /// been freed.
+    fn call_pthread_tls_dtor(
+        &mut self,
+        dtor: TlsDtor<'tcx>,
+        ptr: Scalar<Provenance>,
+        key: TlsKey,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        let instance = this.resolve_tls_dtor(dtor)?;
+        assert!(
+            !ptr.to_target_usize(this).unwrap() != 0,
+            "data can't be NULL when dtor is called!"
+        );
+        if this.machine.tls.validate_dtor_args
+            && let Ok((alloc_id, _, _)) = this.ptr_try_get_alloc_id(ptr.to_pointer(this)?)
+            && matches!(this.get_alloc_info(alloc_id).2, AllocKind::Dead)
+        {
+            this.emit_diagnostic(NonHaltingDiagnostic::DanglingTlsDtorArg { alloc_id });
+        }
+        this.call_tls_dtor(instance, ptr, Some(key))
+    }
+
+    /// Returns how the frame of a TLS destructor that is pushed onto the stack of the active thread
+    /// is popped. On an empty stack, the destructor frame is the root. Otherwise, it returns to
+    /// the topmost frame, which is continued at the start of the basic block it is about to
+    /// execute. This returns `None` if that frame is in the middle of a basic block, since there is
+    /// no way to continue it there.
+    fn tls_dtor_stack_pop(&self) -> Option<StackPopCleanup> {
+        let this = self.eval_context_ref();
+        let Some(frame) = this.active_thread_stack().last() else {
+            return Some(StackPopCleanup::Root { cleanup: true });
         };
-        if let Some((instance, ptr, key)) = dtor {
-            state.last_key = Some(key);
-            trace!("Running TLS dtor {:?} on {:?} at {:?}", instance, ptr, active_thread);
-            assert!(
-                !ptr.to_target_usize(this).unwrap() != 0,
-                "data can't be NULL when dtor is called!"
-            );
+        match frame.current_loc() {
+            Either::Left(loc) if loc.statement_index == 0 =>
+                Some(StackPopCleanup::Goto {
+                    ret: Some(loc.block),
+                    // Destructors are called with an ABI that does not allow unwinding.
+                    unwind: mir::UnwindAction::Unreachable,
+                }),
+            _ => None,
+        }
+    }
+
+    /// Push a stack frame that calls the TLS destructor `dtor` of `key` with the argument `data`,
+    /// through the destructor wrapper if there is one, and remember that frame so that its return
+    /// can be recorded. The clock is advanced first, see `TlsData::set_dtor_clock_advance`.
+    /// Callers have to make sure that the frame can be pushed, see `tls_dtor_stack_pop`.
+    fn call_tls_dtor(
+        &mut self,
+        dtor: ty::Instance<'tcx>,
+        data: Scalar<Provenance>,
+        key: Option<TlsKey>,
+    ) -> InterpResult<'tcx> {
+        let this = self.eval_context_mut();
+        this.machine.clock.advance(this.machine.tls.dtor_clock_advance);
+        let (instance, args) = match this.machine.tls.dtor_wrapper {
+            Some(wrapper) => {
+                let dtor = this.fn_ptr(FnVal::Instance(dtor));
+                (wrapper, vec![Scalar::from_pointer(dtor, this).into(), data.into()])
+            }
+            None => (dtor, vec![data.into()]),
+        };
+        let stack_pop = this
+            .tls_dtor_stack_pop()
+            .expect("TLS destructor called in the middle of a basic block");
+        this.call_function(instance, Abi::C { unwind: false }, &args, None, stack_pop)?;
+        let (thread, frame) = (this.get_active_thread(), this.frame_idx());
+        this.machine.tls.push_dtor_frame(thread, frame, key);
+        Ok(())
+    }
+}
 
-            this.call_function(
-                instance,
-                Abi::C { unwind: false },
-                &[ptr.into()],
-                None,
-                StackPopCleanup::Root { cleanup: true },
-            )?;
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
 
-            return Ok(Poll::Pending);
+    use rustc_index::Idx;
+    use rustc_span::{BytePos, SyntaxContext};
+    use rustc_target::abi::TargetDataLayout;
+
+    use super::*;
+
+    #[test]
+    fn debug_dump() {
+        let dl = TargetDataLayout::default();
+        let mut tls = TlsData::default();
+        let key1 = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        let key2 = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.store_tls(key1, ThreadId::new(0), Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        tls.store_tls(key1, ThreadId::new(1), Scalar::from_target_usize(0x20, &dl), &dl).unwrap();
+        tls.store_tls(key2, ThreadId::new(1), Scalar::from_target_usize(0x30, &dl), &dl).unwrap();
+
+        let dump = tls.debug_dump();
+        assert_eq!(
+            dump,
+            "next key: 3\n\
+             key 1: no dtor\n    \
+             thread 0: 0x0000000000000010\n    \
+             thread 1: 0x0000000000000020\n\
+             key 2: no dtor\n    \
+             thread 1: 0x0000000000000030\n\
+             dtors running: []\n"
+        );
+        // Dumping does not change anything.
+        assert_eq!(tls.debug_dump(), dump);
+    }
+
+    #[test]
+    fn check_invariants() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.store_tls(key, thread, Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        tls.start_teardown(thread);
+        tls.executed_dtors.entry(thread).or_default();
+        assert_eq!(tls.check_invariants(), Ok(()));
+        tls.finish_teardown(thread);
+        assert_eq!(tls.check_invariants(), Ok(()));
+
+        let mut lagging = TlsData::default();
+        lagging.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        lagging.next_key = 1;
+        assert_eq!(
+            lagging.check_invariants(),
+            Err("key 1 exists, but the next key is 1".to_string())
+        );
+
+        let mut null = TlsData::default();
+        let key = null.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        null.keys.get_mut(&key).unwrap().data.insert(thread, Scalar::null_ptr(&dl));
+        assert_eq!(null.check_invariants(), Err("key 1 stores NULL for ThreadId(1)".to_string()));
+
+        let mut stale = TlsData::default();
+        stale.executed_dtors.entry(thread).or_default();
+        assert_eq!(
+            stale.check_invariants(),
+            Err("ThreadId(1) has teardown state, but is not running its destructors".to_string())
+        );
+    }
+
+    #[test]
+    fn clone() {
+        let dl = TargetDataLayout::default();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        let value = |value: u64| Scalar::from_target_usize(value, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new().key(1, Some(dtor)).key(2, None).build().unwrap();
+        tls.store_tls(1, first, value(0x10), &dl).unwrap();
+        tls.store_tls(2, second, value(0x20), &dl).unwrap();
+        let before = tls.debug_dump();
+
+        let mut fork = tls.clone();
+        fork.store_tls(1, first, value(0x30), &dl).unwrap();
+        fork.delete_tls_key(2).unwrap();
+        fork.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        fork.start_teardown(second);
+        assert_ne!(fork.debug_dump(), before);
+
+        // The original is not affected by what happened to the copy.
+        assert_eq!(tls.debug_dump(), before);
+        assert_eq!(tls.load_tls(1, first, &dl).unwrap(), value(0x10));
+        assert!(!tls.dtors_running(second));
+        assert_eq!(tls.create_tls_key(None, Size::from_bytes(4)).unwrap(), 3);
+    }
+
+    #[test]
+    fn snapshot_diff() {
+        let dl = TargetDataLayout::default();
+        let value = |value: u64| Scalar::from_target_usize(value, &dl);
+        let (first, second) = (ThreadId::new(0), ThreadId::new(1));
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls =
+            TlsDataBuilder::new().key(1, None).key(2, None).key(3, Some(dtor)).build().unwrap();
+        tls.store_tls(1, first, value(0x10), &dl).unwrap();
+        tls.store_tls(1, second, value(0x20), &dl).unwrap();
+        tls.store_tls(2, second, value(0x30), &dl).unwrap();
+        tls.store_tls(3, first, value(0x40), &dl).unwrap();
+
+        let before = tls.snapshot();
+        assert!(before.diff(&tls.snapshot()).is_empty());
+        tls.store_tls(1, first, value(0x11), &dl).unwrap();
+        tls.store_tls(1, second, Scalar::null_ptr(&dl), &dl).unwrap();
+        tls.delete_tls_key(2).unwrap();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.store_tls(key, second, value(0x50), &dl).unwrap();
+
+        let diff = before.diff(&tls.snapshot());
+        assert!(matches!(diff.changes()[2], TlsChange::DeletedKey(2)));
+        assert_eq!(
+            diff.to_string(),
+            "key 1, thread 0: 0x0000000000000010 -> 0x0000000000000011\n\
+             key 1, thread 1: 0x0000000000000020 -> NULL\n\
+             deleted key 2\n\
+             key 2, thread 1: 0x0000000000000030 -> NULL\n\
+             created key 4\n\
+             key 4, thread 1: NULL -> 0x0000000000000050\n"
+        );
+    }
+
+    #[test]
+    fn snapshot_diff_pointers() {
+        let dl = TargetDataLayout::default();
+        let ptr = |alloc: u64, offset: u64| {
+            let prov = Provenance::Concrete {
+                alloc_id: AllocId(NonZeroU64::new(alloc).unwrap()),
+                tag: BorTag::new(1).unwrap(),
+            };
+            Scalar::from_pointer(Pointer::new(prov, Size::from_bytes(offset)), &dl)
+        };
+        let thread = ThreadId::new(0);
+        let mut tls = TlsDataBuilder::new().key(1, None).key(2, None).build().unwrap();
+        tls.store_tls(1, thread, ptr(1, 0), &dl).unwrap();
+        tls.store_tls(2, thread, ptr(2, 8), &dl).unwrap();
+
+        let before = tls.snapshot();
+        assert!(before.diff(&tls.snapshot()).is_empty());
+        // Storing the same pointer again is no change, but moving it or pointing it to another
+        // allocation is.
+        tls.store_tls(1, thread, ptr(1, 0), &dl).unwrap();
+        assert!(before.diff(&tls.snapshot()).is_empty());
+        tls.store_tls(1, thread, ptr(1, 4), &dl).unwrap();
+        tls.store_tls(2, thread, ptr(3, 8), &dl).unwrap();
+        let diff = before.diff(&tls.snapshot());
+        let changed: Vec<TlsKey> = diff
+            .changes()
+            .iter()
+            .map(|change| {
+                match *change {
+                    TlsChange::ChangedValue { key, .. } => key,
+                    _ => panic!("unexpected change: {change}"),
+                }
+            })
+            .collect();
+        assert_eq!(changed, [1, 2]);
+    }
+
+    #[test]
+    fn key_to_scalar() {
+        let mut tls = TlsData::default();
+        let size = Size::from_bytes(4);
+        let key = tls.create_tls_key(None, size).unwrap();
+        let scalar = super::key_to_scalar(key, size).unwrap();
+        assert_eq!(scalar.size(), size);
+        assert_eq!(scalar.to_u32().unwrap(), 1);
+        // Keys that do not fit are rejected instead of being truncated.
+        let err = super::key_to_scalar(1 << 32, size).unwrap_err();
+        assert!(matches!(err, TlsError::KeySpaceExhausted));
+    }
+
+    #[test]
+    fn max_keys() {
+        let mut tls = TlsData::default();
+        tls.set_max_keys(Some(2));
+        let key1 = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+
+        let err = tls.create_tls_key(None, Size::from_bytes(4)).unwrap_err();
+        assert!(matches!(err, TlsError::KeyLimitReached { max_keys: 2 }));
+        assert_eq!(err.to_string(), "TLS key limit of 2 reached");
+
+        // Deleting a key makes room for a new one.
+        tls.delete_tls_key(key1).unwrap();
+        tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+    }
+
+    #[test]
+    fn max_values_per_thread() {
+        let dl = TargetDataLayout::default();
+        let (thread, other) = (ThreadId::new(1), ThreadId::new(2));
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let mut tls = TlsDataBuilder::new().max_values_per_thread(Some(2)).build().unwrap();
+        let keys: Vec<TlsKey> =
+            (0..3).map(|_| tls.create_tls_key(None, Size::from_bytes(4)).unwrap()).collect();
+        tls.store_tls(keys[0], thread, value, &dl).unwrap();
+        tls.store_tls(keys[1], thread, value, &dl).unwrap();
+
+        let err = tls.store_tls(keys[2], thread, value, &dl).unwrap_err();
+        assert!(matches!(
+            err,
+            TlsError::ThreadValueLimitReached { thread: t, max_values: 2 } if t == thread
+        ));
+        assert_eq!(err.to_string(), "TLS value limit for thread 1 of 2 reached");
+        assert_eq!(tls.load_tls(keys[2], thread, &dl).unwrap(), Scalar::null_ptr(&dl));
+
+        // Overwriting and clearing values never hits the limit, and other threads have their own.
+        tls.store_tls(keys[1], thread, value, &dl).unwrap();
+        tls.store_tls(keys[0], other, value, &dl).unwrap();
+        tls.store_tls(keys[2], other, value, &dl).unwrap();
+        tls.store_tls(keys[0], thread, Scalar::null_ptr(&dl), &dl).unwrap();
+        tls.store_tls(keys[2], thread, value, &dl).unwrap();
+        assert_eq!((tls.thread_value_count(thread), tls.tls_value_count()), (2, 4));
+    }
+
+    #[test]
+    fn key_creation_veto() {
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsData::default();
+        tls.set_key_creation_veto(|dtor, _| {
+            if dtor.is_some() {
+                Err("keys with destructors are not allowed".to_owned())
+            } else {
+                Ok(())
+            }
+        });
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+
+        let err = tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap_err();
+        assert!(matches!(err, TlsError::KeyCreationVetoed { .. }));
+        assert_eq!(
+            err.to_string(),
+            "creating a TLS key was vetoed: keys with destructors are not allowed"
+        );
+        // Nothing was allocated for the vetoed key.
+        assert_eq!(tls.create_tls_key(None, Size::from_bytes(4)).unwrap(), key + 1);
+
+        tls.clear_key_creation_veto();
+        tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap();
+    }
+
+    #[test]
+    fn dtor_panic_strategy() {
+        let mut tls = TlsData::default();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        // Without a hook, the strategy of the session applies.
+        assert_eq!(tls.dtor_panic_strategy(first, PanicStrategy::Unwind), PanicStrategy::Unwind);
+        assert_eq!(tls.dtor_panic_strategy(first, PanicStrategy::Abort), PanicStrategy::Abort);
+
+        tls.set_dtor_panic_hook(move |thread| {
+            if thread == second {
+                PanicStrategy::Abort
+            } else {
+                PanicStrategy::Unwind
+            }
+        });
+        assert_eq!(tls.dtor_panic_strategy(first, PanicStrategy::Abort), PanicStrategy::Unwind);
+        assert_eq!(tls.dtor_panic_strategy(second, PanicStrategy::Unwind), PanicStrategy::Abort);
+
+        tls.clear_dtor_panic_hook();
+        assert_eq!(tls.dtor_panic_strategy(second, PanicStrategy::Unwind), PanicStrategy::Unwind);
+    }
+
+    #[test]
+    fn register_thread_dtors() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let mut tls = TlsData::default();
+        let keys: Vec<TlsKey> =
+            (0..3).map(|_| tls.create_tls_key(None, Size::from_bytes(4)).unwrap()).collect();
+        let value = |i: u64| Scalar::from_target_usize(0x10 * (i + 1), &dl);
+
+        tls.register_thread_dtors(
+            thread,
+            keys.iter().map(|&key| (key, value(key as u64))).collect(),
+            &dl,
+        )
+        .unwrap();
+        for &key in &keys {
+            assert_eq!(tls.load_tls(key, thread, &dl).unwrap(), value(key as u64));
         }
 
-        Ok(Poll::Ready(()))
+        // A batch with a missing key is rejected as a whole.
+        let other = ThreadId::new(2);
+        assert!(matches!(
+            tls.register_thread_dtors(other, vec![(keys[0], value(0)), (42, value(1))], &dl),
+            Err(TlsError::StoreNonexistentKey { key: 42, thread }) if thread == other
+        ));
+        assert_eq!(tls.load_tls(keys[0], other, &dl).unwrap(), Scalar::null_ptr(&dl));
+    }
+
+    #[test]
+    fn key_space_warning() {
+        let mut tls = TlsData::default();
+        tls.set_key_space_warning(Some(0.5));
+        let size = Size::from_bits(3);
+        // Keys start at 1, so key 4 is the first one to reach half of the 8 possible keys.
+        let warnings: Vec<_> = (0..7)
+            .map(|_| {
+                let key = tls.create_tls_key(None, size).unwrap();
+                tls.check_key_space_warning(key, size)
+            })
+            .collect();
+        assert_eq!(warnings, [None, None, None, Some((4, 8)), None, None, None]);
+    }
+
+    #[test]
+    fn migrate_thread() {
+        let dl = TargetDataLayout::default();
+        let (from, to) = (ThreadId::new(1), ThreadId::new(2));
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        let value = Scalar::from_target_usize(0x10, &dl);
+        tls.store_tls(key, from, value, &dl).unwrap();
+
+        tls.migrate_thread(from, to).unwrap();
+        assert_eq!(tls.load_tls(key, to, &dl).unwrap(), value);
+        assert_eq!(tls.load_tls(key, from, &dl).unwrap(), Scalar::null_ptr(&dl));
+
+        // `to` now has a value, so migrating to it again fails.
+        tls.store_tls(key, from, value, &dl).unwrap();
+        assert!(matches!(
+            tls.migrate_thread(from, to),
+            Err(TlsError::MigrateToOccupiedThread { from: f, to: t }) if f == from && t == to
+        ));
+
+        // Threads running their destructors can't be migrated.
+        tls.dtors_running.insert(from);
+        assert!(matches!(
+            tls.migrate_thread(from, ThreadId::new(3)),
+            Err(TlsError::MigrateWhileDestructing { from: f }) if f == from
+        ));
+    }
+
+    #[test]
+    fn dtor_isolation() {
+        let dl = TargetDataLayout::default();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        let (value1, value2) =
+            (Scalar::from_target_usize(0x10, &dl), Scalar::from_target_usize(0x20, &dl));
+        let setup = |mode| {
+            let mut tls = TlsDataBuilder::new().dtor_isolation(mode).build().unwrap();
+            let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+            tls.store_tls(key, first, value1, &dl).unwrap();
+            tls.store_tls(key, second, value2, &dl).unwrap();
+            (tls, key)
+        };
+
+        // Only one thread is tearing down, so both see each other's values.
+        let (mut tls, key) = setup(DtorIsolation::Error);
+        tls.start_teardown(first);
+        assert_eq!(tls.load_tls_from(key, first, second, &dl).unwrap(), value2);
+        assert_eq!(tls.load_tls_from(key, second, first, &dl).unwrap(), value1);
+
+        // Both are tearing down, and their destructors read each other's values.
+        tls.start_teardown(second);
+        let err = tls.load_tls_from(key, first, second, &dl).unwrap_err();
+        assert!(matches!(
+            err,
+            TlsError::CrossTeardownLoad { key: k, reader, owner }
+                if k == key && reader == first && owner == second
+        ));
+        assert_eq!(
+            err.to_string(),
+            "thread 1 loaded TLS key 1 of thread 2 while both are running their destructors"
+        );
+        assert!(tls.load_tls_from(key, second, first, &dl).is_err());
+        // A thread can always read its own values.
+        assert_eq!(tls.load_tls_from(key, first, first, &dl).unwrap(), value1);
+
+        let (mut tls, key) = setup(DtorIsolation::Null);
+        tls.start_teardown(first);
+        tls.start_teardown(second);
+        assert_eq!(tls.load_tls_from(key, first, second, &dl).unwrap(), Scalar::null_ptr(&dl));
+        assert_eq!(tls.load_tls_from(key, second, second, &dl).unwrap(), value2);
+
+        let (mut tls, key) = setup(DtorIsolation::Off);
+        tls.start_teardown(first);
+        tls.start_teardown(second);
+        assert_eq!(tls.load_tls_from(key, first, second, &dl).unwrap(), value2);
+    }
+
+    #[test]
+    fn posix_strict() {
+        let thread = ThreadId::new(1);
+        let mut tls = TlsDataBuilder::new()
+            .dedup_dtors(true)
+            .aggregate_dtor_errors(true)
+            .dtor_iteration_cap(Some(1))
+            .build()
+            .unwrap();
+        tls.set_thread_dtor_iteration_cap(thread, Some(6));
+        assert!(tls.dedup_dtors());
+        assert!(tls.aggregate_dtor_errors());
+        assert_eq!(tls.dtor_iteration_cap(ThreadId::new(0)), Some(1));
+        assert_eq!(tls.dtor_unwind_action("windows"), DtorUnwindAction::Continue);
+
+        tls.set_posix_strict(true);
+        assert!(!tls.dedup_dtors());
+        assert!(!tls.aggregate_dtor_errors());
+        // Caps are raised to what POSIX allows, but larger caps are kept.
+        assert_eq!(tls.dtor_iteration_cap(ThreadId::new(0)), Some(PTHREAD_DESTRUCTOR_ITERATIONS));
+        assert_eq!(tls.dtor_iteration_cap(thread), Some(6));
+        assert_eq!(tls.dtor_unwind_action("windows"), DtorUnwindAction::Abort);
+    }
+
+    #[test]
+    fn builder() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(0);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(3, Some(dtor))
+            .key(1, None)
+            .dedup_dtors(true)
+            .posix_strict(true)
+            .build()
+            .unwrap();
+        assert!(!tls.dedup_dtors());
+        // New keys come after the largest declared key.
+        assert_eq!(tls.create_tls_key(None, Size::from_bytes(4)).unwrap(), 4);
+        for key in [1, 3, 4] {
+            tls.store_tls(key, thread, value, &dl).unwrap();
+        }
+
+        // Only key 3 has a destructor, and its value gets cleared when it is fetched.
+        let (_, arg, key) = tls.fetch_tls_dtor(None, thread).unwrap();
+        assert_eq!(key, 3);
+        assert_eq!(arg.to_target_usize(&dl).unwrap(), 0x10);
+        assert!(tls.fetch_tls_dtor(Some(3), thread).is_none());
+        assert!(tls.fetch_tls_dtor(None, thread).is_none());
+        assert_eq!(tls.load_tls(3, thread, &dl).unwrap().to_target_usize(&dl).unwrap(), 0);
+
+        let err = TlsDataBuilder::new().key(1, None).key(1, None).build().unwrap_err();
+        assert!(matches!(err, TlsError::DuplicateKey { key: 1 }));
+    }
+
+    #[test]
+    fn key_dtor() {
+        let mut tls = TlsData::default();
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let with_dtor = tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap();
+        let without_dtor = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+
+        assert!(matches!(
+            tls.key_dtor(with_dtor),
+            Ok(Some(TlsDtor::Lazy(ptr))) if ptr.addr().bytes() == 0x100
+        ));
+        assert!(matches!(tls.key_dtor(without_dtor), Ok(None)));
+        tls.delete_tls_key(with_dtor).unwrap();
+        assert!(matches!(
+            tls.key_dtor(with_dtor),
+            Err(TlsError::DtorOfNonexistentKey { key }) if key == with_dtor
+        ));
+    }
+
+    #[test]
+    fn keys_for_thread() {
+        let dl = TargetDataLayout::default();
+        let mut tls = TlsData::default();
+        let keys: Vec<TlsKey> =
+            (0..3).map(|_| tls.create_tls_key(None, Size::from_bytes(4)).unwrap()).collect();
+        let (first, second) = (ThreadId::new(0), ThreadId::new(1));
+        tls.store_tls(keys[2], first, Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        tls.store_tls(keys[0], first, Scalar::from_target_usize(0x20, &dl), &dl).unwrap();
+        tls.store_tls(keys[1], second, Scalar::from_target_usize(0x30, &dl), &dl).unwrap();
+        // Storing NULL removes the value again.
+        tls.store_tls(keys[1], first, Scalar::from_target_usize(0x40, &dl), &dl).unwrap();
+        tls.store_tls(keys[1], first, Scalar::null_ptr(&dl), &dl).unwrap();
+
+        let values = |thread| -> Vec<(TlsKey, u64)> {
+            tls.keys_for_thread(thread)
+                .map(|(key, value)| (key, value.to_target_usize(&dl).unwrap()))
+                .collect()
+        };
+        assert_eq!(values(first), [(keys[0], 0x20), (keys[2], 0x10)]);
+        assert_eq!(values(second), [(keys[1], 0x30)]);
+        assert!(values(ThreadId::new(2)).is_empty());
+    }
+
+    #[test]
+    fn all_pending_dtors() {
+        let dl = TargetDataLayout::default();
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(1, Some(dtor))
+            .key(2, None)
+            .key(3, Some(dtor))
+            .build()
+            .unwrap();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        tls.store_tls(3, second, Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        tls.store_tls(1, second, Scalar::from_target_usize(0x20, &dl), &dl).unwrap();
+        tls.store_tls(3, first, Scalar::from_target_usize(0x30, &dl), &dl).unwrap();
+        // Values of keys without a destructor have nothing to run.
+        tls.store_tls(2, first, Scalar::from_target_usize(0x40, &dl), &dl).unwrap();
+        tls.store_tls(2, ThreadId::new(3), Scalar::from_target_usize(0x50, &dl), &dl).unwrap();
+
+        let pending: Vec<(ThreadId, PendingDtorKind, u64)> = tls
+            .all_pending_dtors()
+            .into_iter()
+            .map(|PendingDtor { thread, kind, dtor: pending_dtor, data }| {
+                assert_eq!(pending_dtor.id(), dtor.id());
+                (thread, kind, data.to_target_usize(&dl).unwrap())
+            })
+            .collect();
+        assert_eq!(
+            pending,
+            [
+                (first, PendingDtorKind::Key(3), 0x30),
+                (second, PendingDtorKind::Key(1), 0x20),
+                (second, PendingDtorKind::Key(3), 0x10),
+            ]
+        );
+        // Nothing was run or removed.
+        assert_eq!(tls.tls_value_count(), 5);
+    }
+
+    #[test]
+    fn dtor_schedule() {
+        let mut tls = TlsData::default();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        // By default, destructors only run at thread exit.
+        assert_eq!(tls.dtor_schedule(first), TlsDtorSchedule::OnExit);
+        assert!(!tls.trigger_dtors(first, TlsDtorSchedule::OnYield));
+        assert!(!tls.trigger_dtors(first, TlsDtorSchedule::OnExit));
+
+        tls.set_dtor_schedule(TlsDtorSchedule::OnYield);
+        tls.set_thread_dtor_schedule(second, Some(TlsDtorSchedule::OnJoin));
+        assert!(!tls.trigger_dtors(first, TlsDtorSchedule::OnJoin));
+        assert!(tls.trigger_dtors(first, TlsDtorSchedule::OnYield));
+        assert!(!tls.trigger_dtors(second, TlsDtorSchedule::OnYield));
+        assert!(tls.trigger_dtors(second, TlsDtorSchedule::OnJoin));
+        assert_eq!(tls.triggered_dtors, BTreeSet::from([first, second]));
+
+        // Removing the override brings back the global schedule.
+        tls.set_thread_dtor_schedule(second, None);
+        assert_eq!(tls.dtor_schedule(second), TlsDtorSchedule::OnYield);
+
+        // The teardown forgets about destructors that were triggered but did not run yet, and
+        // scheduling points during the teardown do not trigger anything.
+        tls.start_teardown(first);
+        assert!(!tls.trigger_dtors(first, TlsDtorSchedule::OnYield));
+        tls.finish_teardown(first);
+        assert_eq!(tls.triggered_dtors, BTreeSet::from([second]));
+    }
+
+    #[test]
+    fn record_events() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new().record_events(true).build().unwrap();
+        let key = tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap();
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        tls.load_tls(key, thread, &dl).unwrap();
+        tls.store_tls(key, thread, Scalar::null_ptr(&dl), &dl).unwrap();
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        tls.take_key_dtor(key, thread).unwrap().unwrap();
+        tls.delete_tls_key(key).unwrap();
+        // Failed operations are not recorded.
+        tls.delete_tls_key(key).unwrap_err();
+
+        use TlsEvent::*;
+        assert_eq!(
+            tls.take_events(),
+            [
+                KeyCreated { key },
+                ValueStored { key, thread },
+                ValueLoaded { key, thread },
+                ValueRemoved { key, thread },
+                ValueStored { key, thread },
+                DtorRun { key: Some(key), thread },
+                KeyDeleted { key },
+            ]
+        );
+        assert!(tls.take_events().is_empty());
+
+        // Nothing is recorded by default.
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        assert!(tls.take_events().is_empty());
+    }
+
+    #[test]
+    fn replay() {
+        let dl = TargetDataLayout::default();
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let size = Size::from_bytes(4);
+        let mut tls = TlsDataBuilder::new().record_ops(true).build().unwrap();
+        let key1 = tls.create_tls_key(Some(dtor), size).unwrap();
+        let key2 = tls.create_module_tls_key(None, Some(1), size).unwrap();
+        let key3 = tls.create_tls_key(None, size).unwrap();
+        tls.store_tls(key1, first, Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        tls.store_tls(key1, second, Scalar::from_target_usize(0x20, &dl), &dl).unwrap();
+        tls.store_tls(key2, first, Scalar::from_target_usize(0x30, &dl), &dl).unwrap();
+        tls.store_tls(key3, second, Scalar::from_target_usize(0x40, &dl), &dl).unwrap();
+        tls.store_tls(key1, second, Scalar::null_ptr(&dl), &dl).unwrap();
+        tls.delete_keys_for_module(1);
+        tls.delete_tls_key(key3).unwrap();
+        // Failed operations are not recorded.
+        tls.delete_tls_key(key3).unwrap_err();
+        let ops = tls.take_ops();
+        assert_eq!(ops.len(), 10);
+
+        let mut replayed = TlsData::default();
+        replayed.replay(&ops, &dl).unwrap();
+        assert_eq!(replayed.debug_dump(), tls.debug_dump());
+        // Nothing is recorded by default, not even while replaying.
+        assert!(replayed.take_ops().is_empty());
+
+        // Replaying into a `TlsData` that already has keys creates different keys.
+        let mut other = TlsData::default();
+        other.create_tls_key(None, size).unwrap();
+        assert!(matches!(
+            other.replay(&ops, &dl),
+            Err(TlsError::ReplayKeyMismatch { expected, actual }) if expected == key1 && actual == 2
+        ));
+    }
+
+    #[test]
+    fn non_destructible_keys() {
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let size = Size::from_bytes(4);
+        let mut tls = TlsData::default();
+        let marked = tls.create_non_destructible_tls_key(size).unwrap();
+        let normal = tls.create_tls_key(None, size).unwrap();
+        assert!(tls.key_dtor(marked).unwrap().is_none());
+
+        let err = tls.set_tls_key_dtor(marked, Some(dtor)).unwrap_err();
+        assert!(matches!(err, TlsError::DtorOfNonDestructibleKey { key } if key == marked));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "setting a destructor for TLS key {marked}, which was created as non-destructible"
+            )
+        );
+        assert!(tls.key_dtor(marked).unwrap().is_none());
+        // Removing the (nonexistent) destructor is fine.
+        tls.set_tls_key_dtor(marked, None).unwrap();
+
+        // Other keys are not affected.
+        tls.set_tls_key_dtor(normal, Some(dtor)).unwrap();
+        assert_eq!(tls.key_dtor(normal).unwrap().map(|dtor| dtor.id()), Some(dtor.id()));
+        assert!(matches!(
+            tls.set_tls_key_dtor(3, Some(dtor)),
+            Err(TlsError::SetDtorOfNonexistentKey { key: 3 })
+        ));
+        tls.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn deleted_key_stores() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let setup = |mode| {
+            let mut tls = TlsDataBuilder::new().deleted_key_stores(mode).build().unwrap();
+            let key = tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap();
+            tls.delete_tls_key(key).unwrap();
+            tls.start_teardown(thread);
+            (tls, key)
+        };
+
+        let (mut tls, key) = setup(DeletedKeyStores::Strict);
+        assert!(matches!(
+            tls.store_tls(key, thread, value, &dl),
+            Err(TlsError::StoreNonexistentKey { .. })
+        ));
+
+        let (mut tls, key) = setup(DeletedKeyStores::Lenient);
+        assert_eq!(tls.deleted_key_store(key, thread), Some(DeletedKeyStores::Lenient));
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        assert!(matches!(tls.load_tls(key, thread, &dl), Err(TlsError::LoadNonexistentKey { .. })));
+        // Only stores during the teardown are handled leniently.
+        let other = ThreadId::new(2);
+        assert_eq!(tls.deleted_key_store(key, other), None);
+        assert!(tls.store_tls(key, other, value, &dl).is_err());
+
+        let (mut tls, key) = setup(DeletedKeyStores::Resurrect);
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        assert_eq!(tls.load_tls(key, thread, &dl).unwrap().to_target_usize(&dl).unwrap(), 0x10);
+        // The key comes back without its destructor, so there is nothing left to run.
+        assert!(tls.key_dtor(key).unwrap().is_none());
+        assert!(tls.fetch_tls_dtor(None, thread).is_none());
+        assert_eq!(tls.deleted_key_store(key, thread), None);
+        tls.check_invariants().unwrap();
+
+        // A recycled key is not deleted anymore.
+        let mut tls = TlsDataBuilder::new()
+            .libc_flavor(Some(LibcFlavor::Glibc))
+            .deleted_key_stores(DeletedKeyStores::Lenient)
+            .build()
+            .unwrap();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.delete_tls_key(key).unwrap();
+        assert_eq!(tls.create_tls_key(None, Size::from_bytes(4)).unwrap(), key);
+        tls.start_teardown(thread);
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        assert_eq!(tls.tls_value_count(), 1);
+    }
+
+    #[test]
+    fn dtor_steps() {
+        let (first, second) = (ThreadId::new(1), ThreadId::new(2));
+        let mut tls = TlsData::default();
+        // Steps outside of destructors always count, and are not attributed to destructors.
+        assert!(tls.count_step(first));
+        tls.start_teardown(first);
+        assert!(tls.count_step(first));
+        assert!(tls.count_step(first));
+        assert!(tls.count_step(second));
+        tls.finish_teardown(first);
+        assert!(tls.count_step(first));
+        assert_eq!((tls.dtor_steps(first), tls.dtor_steps(second)), (2, 0));
+
+        // Exempt destructor steps are still counted per thread.
+        let mut tls = TlsDataBuilder::new().exempt_dtor_steps(true).build().unwrap();
+        tls.start_teardown(first);
+        assert!(!tls.count_step(first));
+        assert!(tls.count_step(second));
+        assert_eq!((tls.dtor_steps(first), tls.dtor_steps(second)), (1, 0));
+    }
+
+    #[test]
+    fn threads_to_finalize() {
+        let dl = TargetDataLayout::default();
+        let main = ThreadId::new(0);
+        let threads = [ThreadId::new(1), ThreadId::new(2), ThreadId::new(3)];
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        for thread in threads {
+            tls.store_tls(key, thread, Scalar::from_target_usize(0x10, &dl), &dl).unwrap();
+        }
+        // The main thread is always there, even without any TLS.
+        let [first, second, third] = threads;
+        assert_eq!(
+            tls.threads_to_finalize(main, ThreadOrder::MainLast),
+            [first, second, third, main]
+        );
+        assert_eq!(
+            tls.threads_to_finalize(main, ThreadOrder::SpawnOrder),
+            [main, first, second, third]
+        );
+        assert_eq!(
+            tls.threads_to_finalize(main, ThreadOrder::ReverseSpawn),
+            [third, second, first, main]
+        );
+
+        // Swapping moves the values both ways.
+        let value = Scalar::from_target_usize(0x20, &dl);
+        tls.store_tls(key, main, value, &dl).unwrap();
+        tls.swap_thread_tls(first, main);
+        assert_eq!(tls.load_tls(key, first, &dl).unwrap(), value);
+        assert_eq!(tls.load_tls(key, main, &dl).unwrap(), Scalar::from_target_usize(0x10, &dl));
+        // Threads without TLS are not torn down.
+        tls.delete_all_thread_tls(second);
+        assert_eq!(tls.threads_to_finalize(main, ThreadOrder::MainLast), [first, third, main]);
+    }
+
+    #[test]
+    fn libc_flavor() {
+        let create = |tls: &mut TlsData<'_>| tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+
+        // glibc reuses the lowest free key right away.
+        let mut tls = TlsDataBuilder::new().libc_flavor(Some(LibcFlavor::Glibc)).build().unwrap();
+        let keys: Vec<TlsKey> = (0..3).map(|_| create(&mut tls)).collect();
+        assert_eq!(keys, [1, 2, 3]);
+        tls.delete_tls_key(1).unwrap();
+        assert_eq!(create(&mut tls), 1);
+        assert_eq!(create(&mut tls), 4);
+
+        // musl only reuses it after wrapping around.
+        let mut tls = TlsDataBuilder::new().libc_flavor(Some(LibcFlavor::Musl)).build().unwrap();
+        let keys: Vec<TlsKey> = (0..3).map(|_| create(&mut tls)).collect();
+        assert_eq!(keys, [1, 2, 3]);
+        tls.delete_tls_key(1).unwrap();
+        assert_eq!(create(&mut tls), 4);
+        for _ in 5..128 {
+            create(&mut tls);
+        }
+        assert_eq!(create(&mut tls), 1);
+        // The search starts at the most recently created key.
+        tls.delete_tls_key(5).unwrap();
+        assert_eq!(create(&mut tls), 5);
+        assert!(matches!(
+            tls.create_tls_key(None, Size::from_bytes(4)),
+            Err(TlsError::KeySpaceExhausted)
+        ));
+
+        // Without a flavor, keys are never reused.
+        let mut tls = TlsData::default();
+        create(&mut tls);
+        tls.delete_tls_key(1).unwrap();
+        assert_eq!(create(&mut tls), 2);
+    }
+
+    #[test]
+    fn teardown_metrics() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(1, Some(dtor))
+            .key(2, Some(dtor))
+            .collect_teardown_metrics(true)
+            .build()
+            .unwrap();
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        tls.store_tls(2, thread, value, &dl).unwrap();
+
+        let mut state = RunningDtorState::default();
+        tls.start_teardown(thread);
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_some());
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_some());
+        // The value is set again, as if by a destructor, so there is a second pass.
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_some());
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_none());
+        assert_eq!(tls.last_teardown_metrics(), None);
+        tls.finish_teardown(thread);
+        let metrics = TeardownMetrics { dtor_calls: 3, passes: 2, iteration_cap_hit: false };
+        assert_eq!(tls.last_teardown_metrics(), Some(metrics));
+
+        // The metrics are reset for the next teardown.
+        let other = ThreadId::new(2);
+        tls.start_teardown(other);
+        assert!(tls.next_pthread_dtor(&mut RunningDtorState::default(), other).is_none());
+        tls.finish_teardown(other);
+        assert_eq!(tls.last_teardown_metrics(), Some(TeardownMetrics::default()));
+
+        // Nothing is collected unless enabled.
+        let mut tls = TlsDataBuilder::new().key(1, Some(dtor)).build().unwrap();
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        tls.start_teardown(thread);
+        assert!(tls.next_pthread_dtor(&mut RunningDtorState::default(), thread).is_some());
+        tls.finish_teardown(thread);
+        assert_eq!(tls.last_teardown_metrics(), None);
+    }
+
+    #[test]
+    fn dtor_iteration_cap() {
+        let dl = TargetDataLayout::default();
+        let (capped, uncapped) = (ThreadId::new(1), ThreadId::new(2));
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(1, Some(dtor))
+            .dtor_iteration_cap(Some(3))
+            .collect_teardown_metrics(true)
+            .build()
+            .unwrap();
+        tls.set_thread_dtor_iteration_cap(capped, Some(1));
+
+        // Runs the teardown of `thread`, where each destructor sets the value again, as long as
+        // destructors are called.
+        let teardown = |tls: &mut TlsData<'_>, thread| {
+            tls.store_tls(1, thread, value, &dl).unwrap();
+            let mut state = RunningDtorState::default();
+            tls.start_teardown(thread);
+            while tls.next_pthread_dtor(&mut state, thread).is_some() {
+                tls.store_tls(1, thread, value, &dl).unwrap();
+            }
+            tls.finish_teardown(thread);
+            tls.last_teardown_metrics().unwrap()
+        };
+        let metrics = TeardownMetrics { dtor_calls: 1, passes: 1, iteration_cap_hit: true };
+        assert_eq!(teardown(&mut tls, capped), metrics);
+        let metrics = TeardownMetrics { dtor_calls: 3, passes: 3, iteration_cap_hit: true };
+        assert_eq!(teardown(&mut tls, uncapped), metrics);
+        // The remaining values were deleted without calling their destructors.
+        assert_eq!(tls.load_tls(1, capped, &dl).unwrap(), Scalar::null_ptr(&dl));
+
+        // Removing the override makes the global cap apply again.
+        tls.set_thread_dtor_iteration_cap(capped, None);
+        assert_eq!(teardown(&mut tls, capped).passes, 3);
+    }
+
+    #[test]
+    fn dtor_passes() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls =
+            TlsDataBuilder::new().key(1, Some(dtor)).dtor_iteration_cap(Some(10)).build().unwrap();
+        let mut dtors = TlsDtorsState::default();
+        assert_eq!(dtors.dtor_passes(), None);
+        assert!(!dtors.set_dtor_passes(8));
+
+        dtors.0 = TlsDtorsStatePriv::PthreadDtors(RunningDtorState::default(), &[]);
+        let TlsDtorsStatePriv::PthreadDtors(state, _) = &mut dtors.0 else { unreachable!() };
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        tls.start_teardown(thread);
+        assert!(tls.next_pthread_dtor(state, thread).is_some());
+        assert_eq!(dtors.dtor_passes(), Some(1));
+
+        // Skip ahead to the last pass the cap allows, where each destructor sets the value again.
+        assert!(dtors.set_dtor_passes(9));
+        let TlsDtorsStatePriv::PthreadDtors(state, _) = &mut dtors.0 else { unreachable!() };
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        assert!(tls.next_pthread_dtor(state, thread).is_some());
+        assert_eq!(state.passes, 10);
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        assert!(tls.next_pthread_dtor(state, thread).is_none());
+        assert_eq!(dtors.dtor_passes(), Some(10));
+    }
+
+    #[test]
+    fn record_dtor_returns() {
+        let thread = ThreadId::new(1);
+        let mut tls = TlsDataBuilder::new().record_dtor_returns(true).build().unwrap();
+        // A destructor without a key, like the macOS thread destructor, on an empty stack, and
+        // one that runs on top of it, with a frame of the first destructor in between.
+        tls.push_dtor_frame(thread, 0, None);
+        tls.push_dtor_frame(thread, 2, Some(1));
+        // Frames that do not belong to a destructor are ignored.
+        tls.record_dtor_return(thread, 3, DtorStatus::Returned);
+        tls.record_dtor_return(thread, 2, DtorStatus::Returned);
+        tls.record_dtor_return(thread, 1, DtorStatus::Returned);
+        tls.record_dtor_return(thread, 0, DtorStatus::Unwound);
+        assert_eq!(
+            tls.take_dtor_returns(),
+            [
+                DtorReturn { thread, key: Some(1), status: DtorStatus::Returned },
+                DtorReturn { thread, key: None, status: DtorStatus::Unwound },
+            ]
+        );
+        assert!(tls.take_dtor_returns().is_empty());
+        assert!(tls.dtor_frames.is_empty());
+
+        // Nothing is recorded by default, but the frame is still forgotten.
+        let mut tls = TlsData::default();
+        tls.push_dtor_frame(thread, 0, Some(1));
+        tls.record_dtor_return(thread, 0, DtorStatus::Returned);
+        assert!(tls.take_dtor_returns().is_empty());
+        assert!(tls.dtor_frames.is_empty());
+    }
+
+    #[test]
+    fn freeze() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(Some(dtor), Size::from_bytes(4)).unwrap();
+        let other = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.freeze();
+
+        let err = tls.create_tls_key(None, Size::from_bytes(4)).unwrap_err();
+        assert!(matches!(err, TlsError::KeysFrozen));
+        assert_eq!(err.to_string(), "TLS keys frozen: no new keys may be created");
+
+        // Existing keys can still be used and deleted.
+        tls.store_tls(key, thread, value, &dl).unwrap();
+        assert_eq!(tls.load_tls(key, thread, &dl).unwrap(), value);
+        tls.delete_tls_key(other).unwrap();
+
+        // Destructors still run.
+        let mut state = RunningDtorState::default();
+        tls.start_teardown(thread);
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_some());
+        assert!(tls.next_pthread_dtor(&mut state, thread).is_none());
+        tls.finish_teardown(thread);
+
+        tls.unfreeze();
+        tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+    }
+
+    #[test]
+    fn double_teardown() {
+        let dl = TargetDataLayout::default();
+        let (thread, other) = (ThreadId::new(1), ThreadId::new(2));
+        let value = |value: u64| Scalar::from_target_usize(value, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new().key(1, Some(dtor)).key(2, Some(dtor)).build().unwrap();
+        tls.store_tls(1, thread, value(0x10), &dl).unwrap();
+        tls.store_tls(2, thread, value(0x20), &dl).unwrap();
+        tls.store_tls(1, other, value(0x30), &dl).unwrap();
+
+        // The first destructor sets the other key again, but only the first time it runs.
+        let mut calls = 0;
+        let [first, second] = tls
+            .double_teardown(thread, |tls, key, _, arg| {
+                calls += 1;
+                if calls == 1 {
+                    tls.store_tls(2, thread, value(0x21), &dl).unwrap();
+                }
+                (key, arg)
+            })
+            .unwrap();
+        assert_eq!(first, [(1, value(0x10)), (2, value(0x21))]);
+        assert_eq!(second, [(1, value(0x10)), (2, value(0x20))]);
+        assert!(!tls.dtors_running(thread));
+        assert_eq!(tls.keys_for_thread(thread).count(), 0);
+        // Other threads are left alone.
+        assert_eq!(tls.load_tls(1, other, &dl).unwrap(), value(0x30));
+
+        // Restoring drops values of keys that were created later, and needs the old keys.
+        let snapshot = tls.snapshot();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.store_tls(key, other, value(0x40), &dl).unwrap();
+        tls.restore_thread(&snapshot, other).unwrap();
+        assert_eq!(tls.load_tls(key, other, &dl).unwrap(), Scalar::null_ptr(&dl));
+        tls.delete_tls_key(1).unwrap();
+        assert!(matches!(
+            tls.restore_thread(&snapshot, other),
+            Err(TlsError::StoreNonexistentKey { key: 1, .. })
+        ));
+        tls.start_teardown(other);
+        assert!(matches!(
+            tls.restore_thread(&snapshot, other),
+            Err(TlsError::RestoreWhileDestructing { .. })
+        ));
+
+        // A thread that is already running its destructors cannot be torn down twice.
+        let err = tls.double_teardown(other, |_, key, _, _| key).unwrap_err();
+        assert!(matches!(err, TlsError::DoubleTeardown { thread } if thread == other));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "running the teardown of {other:?} twice while it is already running its destructors"
+            )
+        );
+    }
+
+    #[test]
+    fn delete_keys_for_module() {
+        let dl = TargetDataLayout::default();
+        let (first, second) = (ThreadId::new(0), ThreadId::new(1));
+        let value = |value: u64| Scalar::from_target_usize(value, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let size = Size::from_bytes(4);
+        let mut tls = TlsData::default();
+        let a1 = tls.create_module_tls_key(Some(dtor), Some(1), size).unwrap();
+        let b = tls.create_module_tls_key(Some(dtor), Some(2), size).unwrap();
+        let a2 = tls.create_module_tls_key(None, Some(1), size).unwrap();
+        let plain = tls.create_tls_key(Some(dtor), size).unwrap();
+        tls.store_tls(a1, first, value(0x10), &dl).unwrap();
+        tls.store_tls(a1, second, value(0x20), &dl).unwrap();
+        tls.store_tls(a2, first, value(0x30), &dl).unwrap();
+        tls.store_tls(b, first, value(0x40), &dl).unwrap();
+
+        // Only the keys of module 1 are deleted, and only values with a destructor are returned.
+        let dtors = tls.delete_keys_for_module(1);
+        assert_eq!(dtors.len(), 2);
+        assert_eq!((dtors[0].1, dtors[0].2), (value(0x10), first));
+        assert_eq!((dtors[1].1, dtors[1].2), (value(0x20), second));
+        assert!(matches!(tls.load_tls(a1, first, &dl), Err(TlsError::LoadNonexistentKey { .. })));
+        assert!(matches!(tls.delete_tls_key(a2), Err(TlsError::DeleteNonexistentKey { .. })));
+        assert_eq!(tls.load_tls(b, first, &dl).unwrap(), value(0x40));
+        assert!(tls.key_dtor(plain).unwrap().is_some());
+
+        // Deleting a module without keys does nothing.
+        assert!(tls.delete_keys_for_module(1).is_empty());
+        assert_eq!(tls.delete_keys_for_module(2).len(), 1);
+        assert!(tls.key_dtor(plain).is_ok());
+    }
+
+    #[test]
+    fn tls_value_count() {
+        let dl = TargetDataLayout::default();
+        let (first, second) = (ThreadId::new(0), ThreadId::new(1));
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let mut tls = TlsDataBuilder::new().key(1, None).key(2, None).build().unwrap();
+        assert_eq!(tls.tls_value_count(), 0);
+        tls.store_tls(1, first, value, &dl).unwrap();
+        tls.store_tls(1, second, value, &dl).unwrap();
+        tls.store_tls(2, first, value, &dl).unwrap();
+        assert_eq!(tls.tls_value_count(), 3);
+
+        // Overwriting a value and loading NULL values does not add values.
+        tls.store_tls(1, first, Scalar::from_target_usize(0x20, &dl), &dl).unwrap();
+        tls.load_tls(2, second, &dl).unwrap();
+        assert_eq!(tls.tls_value_count(), 3);
+
+        // Storing NULL removes the value, and so does deleting the key.
+        tls.store_tls(1, second, Scalar::null_ptr(&dl), &dl).unwrap();
+        assert_eq!(tls.tls_value_count(), 2);
+        tls.delete_tls_key(1).unwrap();
+        assert_eq!(tls.tls_value_count(), 1);
+    }
+
+    #[test]
+    fn overwrite_warning() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let first = Scalar::from_target_usize(0x10, &dl);
+        let second = Scalar::from_target_usize(0x20, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(1, Some(dtor))
+            .key(2, None)
+            .warn_on_overwrite(true)
+            .build()
+            .unwrap();
+        assert!(!tls.check_overwrite_warning(1, thread, first));
+        tls.store_tls(1, thread, first, &dl).unwrap();
+        assert!(!tls.check_overwrite_warning(1, thread, first));
+        assert!(tls.check_overwrite_warning(1, thread, second));
+        assert!(tls.check_overwrite_warning(1, thread, Scalar::null_ptr(&dl)));
+        // Values of keys without a destructor do not need to be cleaned up.
+        tls.store_tls(2, thread, first, &dl).unwrap();
+        assert!(!tls.check_overwrite_warning(2, thread, second));
+
+        tls.set_warn_on_overwrite(false);
+        assert!(!tls.check_overwrite_warning(1, thread, second));
+    }
+
+    #[test]
+    fn key_origins() {
+        let origin =
+            SpanData { lo: BytePos(1), hi: BytePos(2), ctxt: SyntaxContext::root(), parent: None };
+        let mut tls = TlsData::default();
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.record_key_origin(key, origin);
+        assert_eq!(tls.key_origin(key), None);
+
+        tls.set_record_key_origins(true);
+        let key = tls.create_tls_key(None, Size::from_bytes(4)).unwrap();
+        tls.record_key_origin(key, origin);
+        assert_eq!(tls.key_origin(key), Some(origin));
+        tls.delete_tls_key(key).unwrap();
+        assert_eq!(tls.key_origin(key), None);
+    }
+
+    #[test]
+    fn teardown_stages() {
+        use TeardownStage::*;
+        assert_eq!(TeardownStage::for_os("linux"), [PthreadDtors]);
+        // The thread destructor, which runs all `thread_local!` destructors, comes first.
+        assert_eq!(TeardownStage::for_os("macos"), [MacosThreadDtor, PthreadDtors]);
+        assert_eq!(TeardownStage::for_os("windows"), [WindowsCallback]);
+        assert_eq!(TeardownStage::for_os("wasi"), []);
+    }
+
+    #[test]
+    fn dtor_unwind_action() {
+        assert_eq!(DtorUnwindAction::for_os("linux"), DtorUnwindAction::Abort);
+        assert_eq!(DtorUnwindAction::for_os("macos"), DtorUnwindAction::Abort);
+        assert_eq!(DtorUnwindAction::for_os("windows"), DtorUnwindAction::Continue);
+        assert_eq!(DtorUnwindAction::for_os("wasi"), DtorUnwindAction::Continue);
+    }
+
+    #[test]
+    fn take_key_dtor() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(1);
+        let value = Scalar::from_target_usize(0x10, &dl);
+        let dtor = TlsDtor::Lazy(Pointer::from_addr_invalid(0x100));
+        let mut tls = TlsDataBuilder::new()
+            .key(1, Some(dtor))
+            .key(2, None)
+            .dedup_dtors(true)
+            .build()
+            .unwrap();
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        tls.store_tls(2, thread, value, &dl).unwrap();
+
+        let (taken, arg) = tls.take_key_dtor(1, thread).unwrap().unwrap();
+        assert_eq!(taken.id(), dtor.id());
+        assert_eq!(arg, value);
+        assert_eq!(tls.load_tls(1, thread, &dl).unwrap(), Scalar::null_ptr(&dl));
+        // The value is gone, so there is nothing left to run.
+        assert!(tls.take_key_dtor(1, thread).unwrap().is_none());
+        // Keys without a destructor keep their value.
+        assert!(tls.take_key_dtor(2, thread).unwrap().is_none());
+        assert_eq!(tls.load_tls(2, thread, &dl).unwrap(), value);
+
+        // During teardown, a destructor that already ran with the same argument is skipped.
+        tls.start_teardown(thread);
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        assert!(tls.take_key_dtor(1, thread).unwrap().is_some());
+        tls.store_tls(1, thread, value, &dl).unwrap();
+        assert!(tls.take_key_dtor(1, thread).unwrap().is_none());
+        assert_eq!(tls.load_tls(1, thread, &dl).unwrap(), Scalar::null_ptr(&dl));
+
+        let err = tls.take_key_dtor(3, thread).unwrap_err();
+        assert!(
+            matches!(err, TlsError::RunDtorOfNonexistentKey { key: 3, thread: t } if t == thread)
+        );
+        assert_eq!(err.to_string(), "running the destructor of a non-existing TLS key: 3");
+    }
+
+    #[test]
+    fn errors() {
+        let dl = TargetDataLayout::default();
+        let thread = ThreadId::new(0);
+        let mut tls = TlsData::default();
+        let value = Scalar::from_target_usize(0x10, &dl);
+
+        let err = tls.delete_tls_key(42).unwrap_err();
+        assert!(matches!(err, TlsError::DeleteNonexistentKey { key: 42 }));
+        assert_eq!(err.to_string(), "removing a nonexistent TLS key: 42");
+
+        let err = tls.load_tls(42, thread, &dl).unwrap_err();
+        assert!(matches!(err, TlsError::LoadNonexistentKey { key: 42, thread: t } if t == thread));
+        assert_eq!(err.to_string(), "loading from a non-existing TLS key: 42");
+
+        let err = tls.store_tls(42, thread, value, &dl).unwrap_err();
+        assert!(matches!(err, TlsError::StoreNonexistentKey { key: 42, thread: t } if t == thread));
+        assert_eq!(err.to_string(), "storing to a non-existing TLS key: 42");
+
+        // With a 1-bit key type, only key 1 is available since we start at 1.
+        tls.create_tls_key(None, Size::from_bits(1)).unwrap();
+        let err = tls.create_tls_key(None, Size::from_bits(1)).unwrap_err();
+        assert!(matches!(err, TlsError::KeySpaceExhausted));
+        assert_eq!(err.to_string(), "we ran out of TLS key space");
     }
 }
@@ -333,10 +333,13 @@ fn emulate_foreign_item_inner(
                 let dtor = this.read_pointer(dtor)?;
 
                 // Extract the function type out of the signature (that seems easier than constructing it ourselves).
-                let dtor = if !this.ptr_is_null(dtor)? {
-                    Some(this.get_ptr_fn(dtor)?.as_instance()?)
-                } else {
+                // A NULL pointer means there is no destructor, no matter when it gets resolved.
+                let dtor = if this.ptr_is_null(dtor)? {
                     None
+                } else if this.machine.tls.lazy_dtors() {
+                    Some(TlsDtor::Lazy(dtor))
+                } else {
+                    Some(TlsDtor::Resolved(this.get_ptr_fn(dtor)?.as_instance()?))
                 };
 
                 // Figure out how large a pthread TLS key actually is.
@@ -350,8 +353,8 @@ fn emulate_foreign_item_inner(
                 let key_layout = this.layout_of(key_type)?;
 
                 // Create key and write it into the memory where `key_ptr` wants it.
-                let key = this.machine.tls.create_tls_key(dtor, key_layout.size)?;
-                this.write_scalar(Scalar::from_uint(key, key_layout.size), &key_place)?;
+                let key = this.create_tls_key_scalar(dtor, key_layout.size)?;
+                this.write_scalar(key, &key_place)?;
 
                 // Return success (`0`).
                 this.write_null(dest)?;
@@ -375,7 +378,7 @@ fn emulate_foreign_item_inner(
                 let key = this.read_scalar(key)?.to_bits(key.layout.size)?;
                 let active_thread = this.get_active_thread();
                 let new_data = this.read_scalar(new_ptr)?;
-                this.machine.tls.store_tls(key, active_thread, new_data, &*this.tcx)?;
+                this.store_tls(key, active_thread, new_data)?;
 
                 // Return success (`0`).
                 this.write_null(dest)?;
@@ -155,6 +155,13 @@ fn emulate_foreign_item_inner(
                 let dtor = this.get_ptr_fn(dtor)?.as_instance()?;
                 let data = this.read_scalar(data)?;
                 let active_thread = this.get_active_thread();
+                if this.is_active_thread_destructing() {
+                    // The thread wide destructor runs first, so this one would never be called.
+                    return Err(TlsError::MacosThreadDtorWhileDestructing {
+                        thread: active_thread,
+                    }
+                    .into());
+                }
                 this.machine.tls.set_macos_thread_dtor(active_thread, dtor, data)?;
             }
 
@@ -44,6 +44,7 @@ fn pthread_join(
 
         let thread_id = this.read_target_usize(thread)?;
         this.join_thread_exclusive(thread_id.try_into().expect("thread ID should fit in u32"))?;
+        this.trigger_tls_dtors(TlsDtorSchedule::OnJoin);
 
         Ok(0)
     }
@@ -114,6 +115,7 @@ fn sched_yield(&mut self) -> InterpResult<'tcx, i32> {
         let this = self.eval_context_mut();
 
         this.yield_active_thread();
+        this.trigger_tls_dtors(TlsDtorSchedule::OnYield);
 
         Ok(0)
     }
@@ -214,8 +214,8 @@ fn emulate_foreign_item_inner(
 
                 // Create key and return it.
                 let [] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
-                let key = this.machine.tls.create_tls_key(None, dest.layout.size)?;
-                this.write_scalar(Scalar::from_uint(key, dest.layout.size), dest)?;
+                let key = this.create_tls_key_scalar(None, dest.layout.size)?;
+                this.write_scalar(key, dest)?;
             }
             "TlsGetValue" => {
                 let [key] = this.check_shim(abi, Abi::System { unwind: false }, link_name, args)?;
@@ -230,7 +230,7 @@ fn emulate_foreign_item_inner(
                 let key = u128::from(this.read_scalar(key)?.to_u32()?);
                 let active_thread = this.get_active_thread();
                 let new_data = this.read_scalar(new_ptr)?;
-                this.machine.tls.store_tls(key, active_thread, new_data, &*this.tcx)?;
+                this.store_tls(key, active_thread, new_data)?;
 
                 // Return success (`1`).
                 this.write_scalar(Scalar::from_i32(1), dest)?;
@@ -0,0 +1,29 @@
+//@ignore-target-windows: No libc on Windows
+//@error-in-other-file: 2 TLS destructors failed
+// Validation makes this fail when the invalid function pointers are created
+//@compile-flags: -Zmiri-disable-validation -Zmiri-lazy-tls-dtors -Zmiri-aggregate-tls-dtor-errors
+//! Test that with `-Zmiri-aggregate-tls-dtor-errors`, a destructor that cannot be called does not
+//! stop the other destructors from running, and all such errors are reported at the end.
+#![feature(start)]
+
+use std::mem;
+use std::ptr;
+
+static A: u8 = 0;
+static B: u8 = 0;
+static mut VALUE: u8 = 0;
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let dtor_a: unsafe extern "C" fn(*mut libc::c_void) = mem::transmute(ptr::addr_of!(A));
+        let dtor_b: unsafe extern "C" fn(*mut libc::c_void) = mem::transmute(ptr::addr_of!(B));
+        let mut key_a = 0;
+        assert_eq!(libc::pthread_key_create(&mut key_a, Some(dtor_a)), 0);
+        let mut key_b = 0;
+        assert_eq!(libc::pthread_key_create(&mut key_b, Some(dtor_b)), 0);
+        assert_eq!(libc::pthread_setspecific(key_a, ptr::addr_of_mut!(VALUE).cast()), 0);
+        assert_eq!(libc::pthread_setspecific(key_b, ptr::addr_of_mut!(VALUE).cast()), 0);
+    }
+    0
+}
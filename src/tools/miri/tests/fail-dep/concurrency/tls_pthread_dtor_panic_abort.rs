@@ -0,0 +1,27 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-dtor-panic=abort
+//@error-in-other-file: panicking in a TLS destructor with panic=abort
+// The panic starts deep inside std, so the backtrace is normalized away.
+//@normalize-stderr-test: ".*(note|-->|\|).*\n" -> ""
+//! Test that with `-Zmiri-tls-dtor-panic=abort`, a panic in a TLS destructor aborts the process
+//! right away, even if the destructor would catch it.
+
+use std::panic;
+use std::ptr;
+use std::thread;
+
+static mut VALUE: u8 = 0;
+
+extern "C" fn dtor(_ptr: *mut libc::c_void) {
+    let _ = panic::catch_unwind(|| panic!("panicking in a TLS destructor"));
+}
+
+fn main() {
+    thread::spawn(|| unsafe {
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,32 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-aggregate-tls-dtor-errors
+//! Test that with `-Zmiri-aggregate-tls-dtor-errors`, Undefined Behavior in the body of a TLS
+//! destructor does not stop the other destructors from running, and the report points at where
+//! each destructor failed.
+#![feature(start)]
+
+use std::ptr;
+
+/// This is a single byte, so reading a larger integer from it is out of bounds.
+static mut VALUE: u8 = 0;
+
+unsafe extern "C" fn dtor_a(ptr: *mut libc::c_void) {
+    let _val = *ptr.cast::<u32>(); //~ ERROR: 2 TLS destructors failed
+}
+
+unsafe extern "C" fn dtor_b(ptr: *mut libc::c_void) {
+    let _val = *ptr.cast::<u64>();
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let mut key_a = 0;
+        assert_eq!(libc::pthread_key_create(&mut key_a, Some(dtor_a)), 0);
+        let mut key_b = 0;
+        assert_eq!(libc::pthread_key_create(&mut key_b, Some(dtor_b)), 0);
+        assert_eq!(libc::pthread_setspecific(key_a, ptr::addr_of_mut!(VALUE).cast()), 0);
+        assert_eq!(libc::pthread_setspecific(key_b, ptr::addr_of_mut!(VALUE).cast()), 0);
+    }
+    0
+}
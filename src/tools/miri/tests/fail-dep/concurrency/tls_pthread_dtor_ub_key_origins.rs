@@ -0,0 +1,25 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-aggregate-tls-dtor-errors -Zmiri-tls-key-origins
+//! Test that with `-Zmiri-tls-key-origins`, the report of the aggregated TLS destructor errors
+//! points at where the key of each failing destructor was created.
+#![feature(start)]
+
+use std::ptr;
+
+/// This is a single byte, so reading a larger integer from it is out of bounds.
+static mut VALUE: u8 = 0;
+
+unsafe extern "C" fn dtor(ptr: *mut libc::c_void) {
+    let _val = *ptr.cast::<u32>(); //~ ERROR: a TLS destructor failed
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let mut key = 0;
+        let res = libc::pthread_key_create(&mut key, Some(dtor));
+        assert_eq!(res, 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+    }
+    0
+}
@@ -0,0 +1,33 @@
+//@ignore-target-windows: No libc on Windows
+
+//@compile-flags: -Zmiri-disable-abi-check
+
+//! A TLS destructor that unwinds out of its frame aborts the process on targets where libc calls
+//! the destructors.
+
+#![feature(c_unwind)]
+
+use std::{mem, ptr, thread};
+
+static mut VALUE: u8 = 0;
+
+extern "C-unwind" fn dtor(_ptr: *mut libc::c_void) {
+    //~^ ERROR: unwinding out of a TLS destructor
+    panic!()
+}
+
+fn main() {
+    unsafe {
+        // Cast to avoid inserting abort-on-unwind.
+        let dtor: extern "C-unwind" fn(*mut libc::c_void) = dtor;
+        let dtor: unsafe extern "C" fn(*mut libc::c_void) = mem::transmute(dtor);
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+
+        thread::spawn(move || {
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-deleted-key-stores=lenient
+//! Test that with `-Zmiri-tls-deleted-key-stores=lenient`, a destructor storing to a deleted
+//! key is ignored with a warning.
+#![feature(start)]
+
+use std::ptr;
+
+static mut DELETED: libc::pthread_key_t = 0;
+static mut VALUE: u8 = 0;
+
+unsafe extern "C" fn dtor(_ptr: *mut libc::c_void) {
+    let res = libc::pthread_setspecific(DELETED, ptr::addr_of_mut!(VALUE).cast());
+    assert_eq!(res, 0);
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(DELETED), None), 0);
+        assert_eq!(libc::pthread_key_delete(DELETED), 0);
+    }
+    0
+}
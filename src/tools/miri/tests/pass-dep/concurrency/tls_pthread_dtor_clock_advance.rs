@@ -0,0 +1,42 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-dtor-clock-advance=1000000000
+//! Test that with `-Zmiri-tls-dtor-clock-advance`, each TLS destructor sees the clock advanced by
+//! the given amount.
+
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+static mut VALUE: u8 = 0;
+static mut START: Option<Instant> = None;
+static mut LAST_DTOR: Option<Instant> = None;
+static DTOR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn dtor(_ptr: *mut libc::c_void) {
+    let now = Instant::now();
+    // Other destructors, like those of std, may run in between, so only the time since the other
+    // destructor of this test is known exactly. The clock also moves while the program runs, but
+    // by far less than a second.
+    assert!(now - START.unwrap() >= Duration::from_secs(1));
+    if let Some(last) = LAST_DTOR {
+        assert!(now - last >= Duration::from_secs(1));
+        assert!(now - last < Duration::from_secs(2));
+    }
+    LAST_DTOR = Some(now);
+    DTOR_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+fn main() {
+    thread::spawn(|| unsafe {
+        for _ in 0..2 {
+            let mut key = 0;
+            assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        }
+        START = Some(Instant::now());
+    })
+    .join()
+    .unwrap();
+    assert_eq!(DTOR_CALLS.load(Ordering::Relaxed), 2);
+}
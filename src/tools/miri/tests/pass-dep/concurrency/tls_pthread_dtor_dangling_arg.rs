@@ -0,0 +1,35 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-validate-tls-dtor-args
+//! Test that with `-Zmiri-validate-tls-dtor-args`, calling a destructor with a pointer to freed
+//! memory is reported.
+
+use std::mem;
+use std::ptr;
+use std::thread;
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn dtor(_ptr: *mut u64) {
+    // The argument is dangling, so it must not be accessed.
+    drop(Box::from_raw(CANARY));
+    CANARY = ptr::null_mut();
+}
+
+fn main() {
+    unsafe {
+        let dtor: unsafe extern "C" fn(*mut u64) = dtor;
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(dtor))), 0);
+
+        thread::spawn(move || {
+            let value = Box::into_raw(Box::new(0u64));
+            assert_eq!(libc::pthread_setspecific(key, value.cast()), 0);
+            // Free the value without clearing the key.
+            drop(Box::from_raw(value));
+            CANARY = Box::into_raw(Box::new(0u64));
+        })
+        .join()
+        .unwrap();
+        assert!(CANARY.is_null());
+    }
+}
@@ -0,0 +1,33 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-dedup-tls-dtors
+//! Test that with `-Zmiri-dedup-tls-dtors`, a destructor that is registered for several keys with
+//! the same value only runs once.
+
+use std::mem;
+use std::ptr;
+
+static mut VALUE: u64 = 0;
+static mut CALLS: usize = 0;
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn dtor(ptr: *mut u64) {
+    assert_eq!(ptr, ptr::addr_of_mut!(VALUE));
+    CALLS += 1;
+    assert_eq!(CALLS, 1, "dtor ran more than once for the same value");
+    drop(Box::from_raw(CANARY));
+    CANARY = ptr::null_mut();
+}
+
+fn main() {
+    unsafe {
+        let dtor: unsafe extern "C" fn(*mut u64) = dtor;
+        for _ in 0..3 {
+            let mut key = 0;
+            assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(dtor))), 0);
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        }
+
+        CANARY = Box::into_raw(Box::new(0u64));
+    }
+}
@@ -0,0 +1,28 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-dtor-iteration-cap=2
+//! Test that with `-Zmiri-tls-dtor-iteration-cap`, a destructor that keeps setting its value again
+//! is only called as many times as there are passes.
+
+use std::ptr;
+use std::thread;
+
+static mut KEY: libc::pthread_key_t = 0;
+static mut CALLS: u32 = 0;
+static mut VALUE: u8 = 0;
+
+unsafe extern "C" fn dtor(_: *mut libc::c_void) {
+    CALLS += 1;
+    assert_eq!(libc::pthread_setspecific(KEY, ptr::addr_of_mut!(VALUE).cast()), 0);
+}
+
+fn main() {
+    unsafe {
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(KEY), Some(dtor)), 0);
+        thread::spawn(|| {
+            assert_eq!(libc::pthread_setspecific(KEY, ptr::addr_of_mut!(VALUE).cast()), 0);
+        })
+        .join()
+        .unwrap();
+        assert_eq!(CALLS, 2);
+    }
+}
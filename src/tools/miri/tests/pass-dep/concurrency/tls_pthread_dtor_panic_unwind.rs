@@ -0,0 +1,28 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-dtor-panic=unwind
+//! Test that with `-Zmiri-tls-dtor-panic=unwind`, a panic in a TLS destructor unwinds and can be
+//! caught in the destructor.
+
+use std::panic;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+static mut VALUE: u8 = 0;
+static CAUGHT: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn dtor(_ptr: *mut libc::c_void) {
+    let result = panic::catch_unwind(|| panic!("panicking in a TLS destructor"));
+    CAUGHT.store(result.is_err(), Ordering::Relaxed);
+}
+
+fn main() {
+    thread::spawn(|| unsafe {
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+    })
+    .join()
+    .unwrap();
+    assert!(CAUGHT.load(Ordering::Relaxed));
+}
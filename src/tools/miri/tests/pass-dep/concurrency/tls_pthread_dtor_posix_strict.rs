@@ -0,0 +1,34 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-dedup-tls-dtors -Zmiri-tls-posix-strict
+//! Test that `-Zmiri-tls-posix-strict` overrides `-Zmiri-dedup-tls-dtors`, so a destructor that
+//! is registered for several keys with the same value runs once per key.
+
+use std::mem;
+use std::ptr;
+
+static mut VALUE: u64 = 0;
+static mut CALLS: usize = 0;
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn dtor(ptr: *mut u64) {
+    assert_eq!(ptr, ptr::addr_of_mut!(VALUE));
+    CALLS += 1;
+    if CALLS == 3 {
+        drop(Box::from_raw(CANARY));
+        CANARY = ptr::null_mut();
+    }
+}
+
+fn main() {
+    unsafe {
+        let dtor: unsafe extern "C" fn(*mut u64) = dtor;
+        for _ in 0..3 {
+            let mut key = 0;
+            assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(dtor))), 0);
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        }
+
+        CANARY = Box::into_raw(Box::new(0u64));
+    }
+}
@@ -0,0 +1,58 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-dtor-schedule=yield
+//! Test that with `-Zmiri-tls-dtor-schedule=yield`, the pthread TLS destructors of a thread run in
+//! the middle of its execution, whenever it yields. The destructors run on top of the frames that
+//! are live at that point, which go on where they left off afterwards.
+// We do not use std's runtime, whose own TLS destructors would run at the yields as well.
+#![feature(start)]
+
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static mut VALUE: u8 = 0;
+static DTOR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn dtor(ptr: *mut libc::c_void) {
+    assert_eq!(ptr, ptr::addr_of_mut!(VALUE).cast());
+    DTOR_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Yields from `depth` frames further down the stack, and returns how many destructors had run
+/// when `sched_yield` returned.
+fn yield_nested(depth: u32) -> usize {
+    let local = [depth; 4];
+    let calls = if depth == 0 {
+        assert_eq!(unsafe { libc::sched_yield() }, 0);
+        DTOR_CALLS.load(Ordering::Relaxed)
+    } else {
+        yield_nested(depth - 1)
+    };
+    // The frame was left intact by the destructors.
+    assert_eq!(local, [depth; 4]);
+    calls
+}
+
+#[start]
+fn start(_argc: isize, _argv: *const *const u8) -> isize {
+    unsafe {
+        let mut keys = [0; 2];
+        for key in &mut keys {
+            assert_eq!(libc::pthread_key_create(key, Some(dtor)), 0);
+            assert_eq!(libc::pthread_setspecific(*key, ptr::addr_of_mut!(VALUE).cast()), 0);
+        }
+        assert_eq!(DTOR_CALLS.load(Ordering::Relaxed), 0);
+
+        assert_eq!(libc::sched_yield(), 0);
+        // Both destructors ran right after `sched_yield`, and the values are gone.
+        assert_eq!(DTOR_CALLS.load(Ordering::Relaxed), 2);
+        for key in keys {
+            assert!(libc::pthread_getspecific(key).is_null());
+        }
+
+        // Only the values set since then are destructed at the next yield.
+        assert_eq!(libc::pthread_setspecific(keys[1], ptr::addr_of_mut!(VALUE).cast()), 0);
+        assert_eq!(yield_nested(3), 3);
+        assert!(libc::pthread_getspecific(keys[1]).is_null());
+    }
+    0
+}
@@ -0,0 +1,60 @@
+//@ignore-target-windows: No libc on Windows
+//! Test that pthread destructors run on the exiting thread when it exits, not when it is joined,
+//! also for detached threads.
+
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+static mut KEY: libc::pthread_key_t = 0;
+static mut VALUE: u8 = 0;
+static DTOR_RAN: AtomicBool = AtomicBool::new(false);
+static OWNER: AtomicUsize = AtomicUsize::new(0);
+static DTOR_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn dtor(_ptr: *mut libc::c_void) {
+    DTOR_THREAD.store(libc::pthread_self() as usize, Ordering::Relaxed);
+    DTOR_RAN.store(true, Ordering::Release);
+}
+
+extern "C" fn thread_start(_null: *mut libc::c_void) -> *mut libc::c_void {
+    unsafe {
+        OWNER.store(libc::pthread_self() as usize, Ordering::Relaxed);
+        assert_eq!(libc::pthread_setspecific(KEY, ptr::addr_of_mut!(VALUE).cast()), 0);
+    }
+    ptr::null_mut()
+}
+
+fn spawn() -> libc::pthread_t {
+    unsafe {
+        let mut native: libc::pthread_t = std::mem::zeroed();
+        let attr: libc::pthread_attr_t = std::mem::zeroed();
+        assert_eq!(libc::pthread_create(&mut native, &attr, thread_start, ptr::null_mut()), 0);
+        native
+    }
+}
+
+/// Waits until the destructor ran and checks that it ran on the thread that set the value.
+fn wait_for_dtor() {
+    while !DTOR_RAN.swap(false, Ordering::Acquire) {
+        thread::yield_now();
+    }
+    assert_eq!(DTOR_THREAD.load(Ordering::Relaxed), OWNER.load(Ordering::Relaxed));
+}
+
+fn main() {
+    unsafe {
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(KEY), Some(dtor)), 0);
+
+        // The destructor runs before the thread is joined, and joining does not run it again.
+        let native = spawn();
+        wait_for_dtor();
+        assert_eq!(libc::pthread_join(native, ptr::null_mut()), 0);
+        assert!(!DTOR_RAN.load(Ordering::Relaxed));
+
+        // A detached thread runs its destructors as well.
+        let native = spawn();
+        assert_eq!(libc::pthread_detach(native), 0);
+        wait_for_dtor();
+    }
+}
@@ -0,0 +1,72 @@
+//@ignore-target-windows: No libc on Windows
+//! Test that `miri_set_tls_dtor_wrapper` routes pthread destructor calls through the wrapper.
+
+#[path = "../../utils/mod.rs"]
+mod utils;
+
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static mut KEYS: [libc::pthread_key_t; 2] = [0; 2];
+static mut VALUES: [u8; 2] = [0; 2];
+/// The destructor and argument of each call the wrapper saw.
+static mut WRAPPED: Vec<(usize, usize)> = Vec::new();
+static DTOR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn dtor_a(_ptr: *mut libc::c_void) {
+    DTOR_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+unsafe extern "C" fn dtor_b(_ptr: *mut libc::c_void) {
+    DTOR_CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+unsafe extern "C" fn wrapper(dtor: unsafe extern "C" fn(*mut u8), value: *mut u8) {
+    WRAPPED.push((dtor as usize, value as usize));
+    dtor(value);
+}
+
+extern "C" fn thread_start(_null: *mut libc::c_void) -> *mut libc::c_void {
+    unsafe {
+        for i in 0..2 {
+            let value = ptr::addr_of_mut!(VALUES[i]).cast();
+            assert_eq!(libc::pthread_setspecific(KEYS[i], value), 0);
+        }
+    }
+    ptr::null_mut()
+}
+
+/// Runs a thread that sets both keys.
+fn run_thread() {
+    unsafe {
+        let mut native: libc::pthread_t = std::mem::zeroed();
+        let attr: libc::pthread_attr_t = std::mem::zeroed();
+        assert_eq!(libc::pthread_create(&mut native, &attr, thread_start, ptr::null_mut()), 0);
+        assert_eq!(libc::pthread_join(native, ptr::null_mut()), 0);
+    }
+}
+
+fn main() {
+    unsafe {
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(KEYS[0]), Some(dtor_a)), 0);
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(KEYS[1]), Some(dtor_b)), 0);
+
+        // Every destructor is called through the wrapper, which then calls it.
+        utils::miri_set_tls_dtor_wrapper(Some(wrapper));
+        run_thread();
+        assert_eq!(
+            WRAPPED,
+            [
+                (dtor_a as usize, ptr::addr_of!(VALUES[0]) as usize),
+                (dtor_b as usize, ptr::addr_of!(VALUES[1]) as usize),
+            ]
+        );
+        assert_eq!(DTOR_CALLS.load(Ordering::Relaxed), 2);
+
+        // Without the wrapper, destructors are called directly.
+        utils::miri_set_tls_dtor_wrapper(None);
+        run_thread();
+        assert_eq!(WRAPPED.len(), 2);
+        assert_eq!(DTOR_CALLS.load(Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,44 @@
+//@ignore-target-windows: No libc on Windows
+//! Test that a key created by a TLS destructor is tracked like any other key: the value stored
+//! for it is kept and its destructor runs during the same teardown.
+
+use std::mem;
+use std::ptr;
+
+static mut FIRST: u64 = 0;
+static mut SECOND: u64 = 0;
+static mut CALLS: [usize; 2] = [0; 2];
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn first_dtor(ptr: *mut u64) {
+    assert_eq!(ptr, ptr::addr_of_mut!(FIRST));
+    CALLS[0] += 1;
+    assert_eq!(CALLS, [1, 0]);
+
+    // Create a new key during teardown and store a value for it.
+    let second_dtor: unsafe extern "C" fn(*mut u64) = second_dtor;
+    let mut key = 0;
+    assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(second_dtor))), 0);
+    assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(SECOND).cast()), 0);
+    assert_eq!(libc::pthread_getspecific(key), ptr::addr_of_mut!(SECOND).cast());
+}
+
+unsafe extern "C" fn second_dtor(ptr: *mut u64) {
+    assert_eq!(ptr, ptr::addr_of_mut!(SECOND));
+    CALLS[1] += 1;
+    assert_eq!(CALLS, [1, 1]);
+    drop(Box::from_raw(CANARY));
+    CANARY = ptr::null_mut();
+}
+
+fn main() {
+    unsafe {
+        let first_dtor: unsafe extern "C" fn(*mut u64) = first_dtor;
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(first_dtor))), 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(FIRST).cast()), 0);
+
+        CANARY = Box::into_raw(Box::new(0u64));
+    }
+}
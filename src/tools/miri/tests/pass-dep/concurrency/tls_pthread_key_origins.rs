@@ -0,0 +1,25 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-warn-tls-overwrite -Zmiri-tls-key-origins
+//! Test that with `-Zmiri-tls-key-origins`, the warning about an overwritten value points at where
+//! its key was created.
+#![feature(start)]
+
+use std::ptr;
+
+static mut FIRST: u8 = 0;
+static mut SECOND: u8 = 0;
+
+unsafe extern "C" fn dtor(_ptr: *mut libc::c_void) {}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let mut key = 0;
+        let res = libc::pthread_key_create(&mut key, Some(dtor));
+        assert_eq!(res, 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(FIRST).cast()), 0);
+        let res = libc::pthread_setspecific(key, ptr::addr_of_mut!(SECOND).cast());
+        assert_eq!(res, 0);
+    }
+    0
+}
@@ -0,0 +1,37 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-lazy-tls-dtors
+//! Test that with `-Zmiri-lazy-tls-dtors`, destructors still run and keys without a destructor
+//! are not affected.
+
+use std::mem;
+use std::ptr;
+use std::thread;
+
+static mut VALUE: u64 = 0;
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn dtor(ptr: *mut u64) {
+    assert_eq!(ptr, ptr::addr_of_mut!(VALUE));
+    drop(Box::from_raw(CANARY));
+    CANARY = ptr::null_mut();
+}
+
+fn main() {
+    unsafe {
+        let dtor: unsafe extern "C" fn(*mut u64) = dtor;
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(dtor))), 0);
+        let mut no_dtor_key = 0;
+        assert_eq!(libc::pthread_key_create(&mut no_dtor_key, None), 0);
+
+        thread::spawn(move || {
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(VALUE).cast()), 0);
+            assert_eq!(libc::pthread_setspecific(no_dtor_key, ptr::addr_of_mut!(VALUE).cast()), 0);
+            CANARY = Box::into_raw(Box::new(0u64));
+        })
+        .join()
+        .unwrap();
+        assert!(CANARY.is_null());
+    }
+}
@@ -0,0 +1,48 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-libc-flavor=glibc
+//! Test that with `-Zmiri-tls-libc-flavor=glibc`, a new key reuses the slot of a deleted one, so
+//! its destructor runs before those of older keys.
+
+use std::mem;
+use std::ptr;
+use std::thread;
+
+static mut ORDER: [u64; 2] = [0; 2];
+static mut RECORDED: usize = 0;
+
+static mut CANARY: *mut u64 = ptr::null_mut(); // this serves as a canary: if the dtor does not run, this will not get deallocated, making the test fail.
+
+unsafe extern "C" fn dtor(ptr: *mut u64) {
+    ORDER[RECORDED] = *ptr;
+    RECORDED += 1;
+    if RECORDED == ORDER.len() {
+        drop(Box::from_raw(CANARY));
+        CANARY = ptr::null_mut();
+    }
+}
+
+fn main() {
+    unsafe {
+        let dtor: unsafe extern "C" fn(*mut u64) = dtor;
+        let mut old_key = 0;
+        assert_eq!(libc::pthread_key_create(&mut old_key, Some(mem::transmute(dtor))), 0);
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(mem::transmute(dtor))), 0);
+        assert_eq!(libc::pthread_key_delete(old_key), 0);
+        let mut new_key = 0;
+        assert_eq!(libc::pthread_key_create(&mut new_key, Some(mem::transmute(dtor))), 0);
+        assert_eq!(new_key, old_key);
+
+        thread::spawn(move || {
+            static mut OLD: u64 = 1;
+            static mut NEW: u64 = 2;
+            assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(OLD).cast()), 0);
+            assert_eq!(libc::pthread_setspecific(new_key, ptr::addr_of_mut!(NEW).cast()), 0);
+            CANARY = Box::into_raw(Box::new(0u64));
+        })
+        .join()
+        .unwrap();
+        assert!(CANARY.is_null());
+        assert_eq!(ORDER, [2, 1]);
+    }
+}
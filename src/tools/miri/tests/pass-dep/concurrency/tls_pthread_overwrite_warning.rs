@@ -0,0 +1,26 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-warn-tls-overwrite
+//! Test that with `-Zmiri-warn-tls-overwrite`, overwriting a value that still needs its destructor
+//! is reported.
+#![feature(start)]
+
+use std::ptr;
+
+static mut FIRST: u8 = 0;
+static mut SECOND: u8 = 0;
+
+unsafe extern "C" fn dtor(_ptr: *mut libc::c_void) {}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        let mut key = 0;
+        assert_eq!(libc::pthread_key_create(&mut key, Some(dtor)), 0);
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(FIRST).cast()), 0);
+        // Storing the same value again does not lose anything.
+        assert_eq!(libc::pthread_setspecific(key, ptr::addr_of_mut!(FIRST).cast()), 0);
+        let res = libc::pthread_setspecific(key, ptr::addr_of_mut!(SECOND).cast());
+        assert_eq!(res, 0);
+    }
+    0
+}
@@ -0,0 +1,60 @@
+//@ignore-target-windows: No libc on Windows
+//@compile-flags: -Zmiri-tls-thread-order=spawn-order -Zmiri-ignore-leaks
+//! Test that with `-Zmiri-tls-thread-order=spawn-order`, the destructors of the threads that are
+//! still running when the main thread exits run in spawn order, after the one of the main thread.
+#![feature(start)]
+
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static mut KEY: libc::pthread_key_t = 0;
+static mut OTHER_KEY: libc::pthread_key_t = 0;
+static mut LOCK: libc::pthread_mutex_t = libc::PTHREAD_MUTEX_INITIALIZER;
+static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+// The index of each thread, with the main thread first.
+static mut INDICES: [u8; 4] = [0, 1, 2, 3];
+static mut RECORD: [u8; 4] = [0; 4];
+static mut RECORDED: usize = 0;
+
+unsafe extern "C" fn dtor(ptr: *mut libc::c_void) {
+    // The destructor sees the other values of the thread that is torn down.
+    assert_eq!(libc::pthread_getspecific(OTHER_KEY), ptr);
+    RECORD[RECORDED] = *ptr.cast::<u8>();
+    RECORDED += 1;
+    if RECORDED == RECORD.len() {
+        assert_eq!(RECORD, [0, 1, 2, 3]);
+    }
+}
+
+extern "C" fn thread_start(index: *mut libc::c_void) -> *mut libc::c_void {
+    unsafe {
+        assert_eq!(libc::pthread_setspecific(KEY, index), 0);
+        assert_eq!(libc::pthread_setspecific(OTHER_KEY, index), 0);
+        STARTED.fetch_add(1, Ordering::SeqCst);
+        // Block until the program exits.
+        libc::pthread_mutex_lock(ptr::addr_of_mut!(LOCK));
+    }
+    ptr::null_mut()
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(KEY), Some(dtor)), 0);
+        assert_eq!(libc::pthread_key_create(ptr::addr_of_mut!(OTHER_KEY), None), 0);
+        let main_index = ptr::addr_of_mut!(INDICES[0]).cast();
+        assert_eq!(libc::pthread_setspecific(KEY, main_index), 0);
+        assert_eq!(libc::pthread_setspecific(OTHER_KEY, main_index), 0);
+        assert_eq!(libc::pthread_mutex_lock(ptr::addr_of_mut!(LOCK)), 0);
+        for index in 1..INDICES.len() {
+            let mut thread = 0;
+            let arg = ptr::addr_of_mut!(INDICES[index]).cast();
+            assert_eq!(libc::pthread_create(&mut thread, ptr::null(), thread_start, arg), 0);
+        }
+        while STARTED.load(Ordering::SeqCst) < 3 {
+            libc::sched_yield();
+        }
+    }
+    0
+}
@@ -0,0 +1,56 @@
+//@ignore-target-windows: No libc on Windows
+//! Test the order in which the different kinds of TLS destructors run when a thread exits. On
+//! macOS, the thread destructor registered with `_tlv_atexit` (which std uses for `thread_local!`)
+//! runs before the pthread key destructors, even though it is registered after them. Elsewhere,
+//! only the pthread key destructors run, in key order.
+#![feature(start)]
+
+use std::ptr;
+
+static mut RECORD: [u8; 3] = [0; 3];
+static mut RECORDED: usize = 0;
+
+// This serves as a canary: if the last destructor does not run, this is leaked and the test fails.
+static mut CANARY: *mut u64 = ptr::null_mut();
+
+unsafe fn record(r: u8) {
+    RECORD[RECORDED] = r;
+    RECORDED += 1;
+}
+
+#[cfg(target_os = "macos")]
+extern "C" {
+    fn _tlv_atexit(dtor: unsafe extern "C" fn(*mut u8), arg: *mut u8);
+}
+
+#[cfg(target_os = "macos")]
+unsafe extern "C" fn thread_dtor(_ptr: *mut u8) {
+    record(b't');
+}
+
+unsafe extern "C" fn first_dtor(_ptr: *mut libc::c_void) {
+    record(b'1');
+}
+
+unsafe extern "C" fn second_dtor(_ptr: *mut libc::c_void) {
+    record(b'2');
+    let expected: &[u8] = if cfg!(target_os = "macos") { b"t12" } else { b"12" };
+    assert_eq!(&RECORD[..RECORDED], expected);
+    drop(Box::from_raw(CANARY));
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    unsafe {
+        CANARY = Box::into_raw(Box::new(0));
+        let mut first = 0;
+        assert_eq!(libc::pthread_key_create(&mut first, Some(first_dtor)), 0);
+        let mut second = 0;
+        assert_eq!(libc::pthread_key_create(&mut second, Some(second_dtor)), 0);
+        assert_eq!(libc::pthread_setspecific(first, CANARY.cast()), 0);
+        assert_eq!(libc::pthread_setspecific(second, CANARY.cast()), 0);
+        #[cfg(target_os = "macos")]
+        _tlv_atexit(thread_dtor, ptr::null_mut());
+    }
+    0
+}
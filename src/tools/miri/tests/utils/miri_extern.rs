@@ -119,6 +119,13 @@ pub fn miri_resolve_frame_names(
     /// Miri-provided extern function to deallocate memory.
     pub fn miri_dealloc(ptr: *mut u8, size: usize, align: usize);
 
+    /// Miri-provided extern function to call every pthread and macOS TLS destructor through
+    /// `wrapper`, which gets the destructor and its argument and should call the destructor
+    /// itself. Passing `None` calls destructors directly again.
+    pub fn miri_set_tls_dtor_wrapper(
+        wrapper: Option<unsafe extern "C" fn(unsafe extern "C" fn(*mut u8), *mut u8)>,
+    );
+
     /// Convert a path from the host Miri runs on to the target Miri interprets.
     /// Performs conversion of path separators as needed.
     ///